@@ -0,0 +1,162 @@
+//! Embedding API for using cambi as a library.
+//!
+//! Unlike the command entry points, which operate on the current working
+//! directory and print their results, [`Cambi`] works against an explicit
+//! repository path and returns values.
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use cambi::{
+//!   Cambi,
+//!   config::{ConfigOverrides, EffectiveConfig},
+//! };
+//! # use git2::{Repository, Signature};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! # let dir = tempfile::tempdir()?;
+//! # let repo = Repository::init(dir.path())?;
+//! # let signature = Signature::now("cambi", "cambi@example.com")?;
+//! # let mut commit = |message: &str| -> anyhow::Result<git2::Oid> {
+//! #   let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+//! #   let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+//! #   let parents = parent.iter().collect::<Vec<_>>();
+//! #   Ok(repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents)?)
+//! # };
+//! # let released = commit("fix: first release")?;
+//! # repo.tag_lightweight("v1.0.0", &repo.find_object(released, None)?, false)?;
+//! # commit("feat: add the embedding API")?;
+//! // The repository has a v1.0.0 tag followed by a feat commit.
+//! let config = EffectiveConfig::from_sources(None, "release", &HashMap::new(), ConfigOverrides::default())?;
+//!
+//! let cambi = Cambi::open(dir.path(), config)?;
+//! assert_eq!(cambi.current_version()?.to_string(), "1.0.0");
+//! assert_eq!(cambi.next_version()?.to_string(), "1.1.0");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use git2::Repository;
+use semver::Version;
+
+use crate::{
+  changelog::{
+    ChangelogSection, apply_default_sorting, bump_version, format_date, read_pending_changelog_in, render_section,
+  },
+  config::EffectiveConfig,
+  conventional::BumpLevel,
+  filters::CommitFilter,
+  release::{build_latest_release_candidate_in, read_required_tags_in},
+  version::{current_base_version_in, detect_bump_in, initial_release_version_in},
+};
+
+/// A repository opened for version and release computations.
+pub struct Cambi {
+  repo_path: PathBuf,
+  config: EffectiveConfig,
+}
+
+impl Cambi {
+  /// Opens the git repository containing `repo_path`.
+  pub fn open(repo_path: impl AsRef<Path>, config: EffectiveConfig) -> Result<Self> {
+    let repo = Repository::discover(repo_path.as_ref()).context("Failed to discover git repository")?;
+    let repo_path = repo
+      .workdir()
+      .ok_or(anyhow!("Repository has no working directory"))?
+      .to_path_buf();
+
+    Ok(Self { repo_path, config })
+  }
+
+  /// The root of the repository working directory.
+  pub fn repo_path(&self) -> &Path {
+    &self.repo_path
+  }
+
+  /// The configuration used for all computations.
+  pub fn config(&self) -> &EffectiveConfig {
+    &self.config
+  }
+
+  /// Returns the version of the latest matching tag, falling back to the
+  /// configured current version and then `0.0.0`.
+  ///
+  /// ```no_run
+  /// # use cambi::{Cambi, config::EffectiveConfig};
+  /// # fn main() -> anyhow::Result<()> {
+  /// let cambi = Cambi::open(".", EffectiveConfig::default())?;
+  /// assert!(cambi.current_version()? >= semver::Version::new(0, 0, 0));
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn current_version(&self) -> Result<Version> {
+    current_base_version_in(&self.repo_path, &self.config, self.config.reachable_only)
+  }
+
+  /// Returns the bump inferred from the commits since the latest matching tag.
+  pub fn next_bump(&self) -> Result<BumpLevel> {
+    detect_bump_in(&self.repo_path, None, &self.config, false)
+  }
+
+  /// Returns the version of the next release: the configured initial version
+  /// when no tag matches yet, otherwise the current version bumped by
+  /// [`Cambi::next_bump`].
+  pub fn next_version(&self) -> Result<Version> {
+    if let Some(initial) = initial_release_version_in(&self.repo_path, &self.config)? {
+      return Ok(initial);
+    }
+
+    Ok(bump_version(Some(self.current_version()?), self.next_bump()?))
+  }
+
+  /// Renders the CHANGELOG.md section for the pending commits, or `None` when
+  /// there is nothing to release.
+  ///
+  /// ```no_run
+  /// # use cambi::{Cambi, config::EffectiveConfig};
+  /// # fn main() -> anyhow::Result<()> {
+  /// let cambi = Cambi::open(".", EffectiveConfig::default())?;
+  ///
+  /// if let Some(section) = cambi.generate_changelog_section()? {
+  ///   println!("{section}");
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn generate_changelog_section(&self) -> Result<Option<String>> {
+    let filter = CommitFilter::from_config(&self.config)?;
+    let pending = read_pending_changelog_in(&self.repo_path, None, None, &self.config, &filter)?;
+    let mut commits = pending.commits;
+
+    if commits.is_empty() {
+      return Ok(None);
+    }
+
+    apply_default_sorting(&mut commits);
+
+    let date = format_date(commits.first().map(|commit| commit.time).unwrap_or(0));
+    let section = ChangelogSection::from_commits_in(
+      &self.repo_path,
+      date,
+      pending.version.to_string(),
+      commits,
+      &self.config,
+    );
+
+    Ok(Some(render_section(
+      &section,
+      self.config.changelog_template.as_deref(),
+    )))
+  }
+
+  /// Renders the release body for the latest matching tag.
+  pub fn release_notes(&self) -> Result<String> {
+    let tags = read_required_tags_in(&self.repo_path, &self.config.tag_pattern)?;
+    let filter = CommitFilter::from_config(&self.config)?;
+
+    Ok(build_latest_release_candidate_in(&self.repo_path, tags, &filter, &self.config)?.body)
+  }
+}
@@ -0,0 +1,76 @@
+use std::{thread, time::Duration};
+
+use anyhow::{Result, anyhow};
+
+/// Applies the failure policy of batch operations: fail on the first error,
+/// or keep going and report every failure at the end.
+pub struct BatchErrors {
+  continue_on_error: bool,
+  failures: Vec<String>,
+}
+
+impl BatchErrors {
+  pub fn new(continue_on_error: bool) -> Self {
+    Self {
+      continue_on_error,
+      failures: Vec::new(),
+    }
+  }
+
+  /// Records the outcome of one step, returning its error right away when
+  /// failing fast.
+  pub fn record(&mut self, result: Result<()>) -> Result<()> {
+    match result {
+      Err(error) if self.continue_on_error => {
+        self.failures.push(format!("{error:#}"));
+        Ok(())
+      }
+      result => result,
+    }
+  }
+
+  /// Returns an error listing every recorded failure, if any.
+  pub fn finish(self) -> Result<()> {
+    if self.failures.is_empty() {
+      return Ok(());
+    }
+
+    Err(anyhow!(
+      "{} operation(s) failed:\n{}",
+      self.failures.len(),
+      self
+        .failures
+        .iter()
+        .map(|failure| format!("- {failure}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+    ))
+  }
+}
+
+/// Delay before retry number `attempt + 1`: 250ms, doubling each time.
+pub fn retry_backoff(attempt: u32) -> Duration {
+  Duration::from_millis(250 * 2u64.pow(attempt.min(6)))
+}
+
+/// Runs `operation` until it succeeds, retrying it up to `max_retries` times
+/// after a [`retry_backoff`] delay. `on_retry` sees every failure that is
+/// retried.
+pub fn retry<T, E>(
+  max_retries: u32,
+  mut operation: impl FnMut() -> std::result::Result<T, E>,
+  mut on_retry: impl FnMut(&E),
+) -> std::result::Result<T, E> {
+  let mut attempt = 0;
+
+  loop {
+    match operation() {
+      Err(error) if attempt < max_retries => {
+        on_retry(&error);
+        thread::sleep(retry_backoff(attempt));
+        attempt += 1;
+      }
+      result => return result,
+    }
+  }
+}
@@ -8,16 +8,123 @@ use semver::Version;
 
 use crate::{
   cli::ChangelogArgs,
-  config::EffectiveConfig,
-  conventional::{BumpLevel, infer_bump},
+  config::{ChangelogDateSource, ChangelogStyle, EffectiveConfig, OutputFormat, Provider},
+  conventional::{BumpLevel, infer_commit_bump, strip_prefix, strip_scope},
+  files::write_atomic,
   filters::CommitFilter,
-  git::{GitCommit, GitTag, read_commits, read_commits_between_tags, read_tags},
+  git::{
+    GitCommit, GitTag, create_commit, describe_commit_range, read_commits, read_commits_between_tags, read_commits_in,
+    read_head_oid, read_tags, read_tags_in, signing_key, verify_clean_worktree,
+  },
+  gitlab::generate_remote_changelog,
+  release::{resolve_owner_repo, resolve_owner_repo_in, resolve_token},
+  version::{initial_release_version_in, tag_name},
 };
 
+const KEEPACHANGELOG_HEADING: &str = "## [$VERSION] - $DATE";
+
+/// Semver capture shared by the version patterns of section headings.
+const SEMVER_CAPTURE: &str = r"([0-9]+\.[0-9]+\.[0-9]+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?)";
+
+#[derive(Default)]
 pub struct ChangelogSection {
   pub date: String,
   pub version: String,
   pub commits: Vec<String>,
+  /// Bodies shown beneath the matching `commits` entries, empty when bodies
+  /// are not displayed.
+  pub bodies: Vec<String>,
+  /// Bump levels shown before the matching `commits` entries, empty when
+  /// levels are not annotated.
+  pub bumps: Vec<BumpLevel>,
+  /// Author names of the matching `commits` entries, listed by `$AUTHOR`.
+  pub authors: Vec<String>,
+  /// Credit each author after their entry in the default rendering.
+  pub show_authors: bool,
+  /// Commit references appended to the matching `commits` entries, empty when
+  /// commits are not linked.
+  pub links: Vec<String>,
+  /// Abbreviated hash of the most recent commit, exposed as `$HASH`.
+  pub hash: String,
+  /// URL of the most recent commit, exposed as `$URL` (empty when the
+  /// repository is unknown).
+  pub url: String,
+  /// Issues URL `#123` and `GH-123` references link to, unset when issues are
+  /// not linked or the repository is unknown.
+  pub issues_url: Option<String>,
+  /// Heading of the default rendering, with `$DATE` and `$VERSION`
+  /// placeholders (`### $DATE / $VERSION` when unset).
+  pub heading_format: Option<String>,
+}
+
+impl ChangelogSection {
+  /// Builds the section of `commits` (already sorted), applying the
+  /// configured display options.
+  pub fn from_commits(date: String, version: String, commits: Vec<GitCommit>, config: &EffectiveConfig) -> Self {
+    Self::from_commits_in(Path::new("."), date, version, commits, config)
+  }
+
+  /// Like [`ChangelogSection::from_commits`], linking the commits to the
+  /// repository at `repo_path`.
+  pub fn from_commits_in(
+    repo_path: &Path,
+    date: String,
+    version: String,
+    commits: Vec<GitCommit>,
+    config: &EffectiveConfig,
+  ) -> Self {
+    // Links are a nicety: without a known repository, plain hashes are shown.
+    let repository = resolve_owner_repo_in(repo_path, config).ok();
+    let commit_url = |id: &str| {
+      repository
+        .as_ref()
+        .map(|(owner, repo)| config.provider.commit_url(config.web_host(), owner, repo, id))
+    };
+
+    let latest = commits.iter().max_by_key(|commit| commit.time);
+    let hash = latest.map(|commit| commit.id.clone()).unwrap_or_default();
+    let url = latest.and_then(|commit| commit_url(&commit.id)).unwrap_or_default();
+
+    let links = if config.link_commits {
+      commits
+        .iter()
+        .map(|commit| {
+          match commit_url(&commit.id) {
+            Some(url) => format!("([{}]({url}))", commit.id),
+            None => format!("({})", commit.id),
+          }
+        })
+        .collect()
+    } else {
+      Vec::new()
+    };
+
+    let bumps = if config.changelog_annotate_bump {
+      commits.iter().map(|commit| infer_commit_bump(commit, config)).collect()
+    } else {
+      Vec::new()
+    };
+
+    let issues_url = repository
+      .as_ref()
+      .filter(|_| config.link_issues)
+      .map(|(owner, repo)| config.provider.issues_url(config.web_host(), owner, repo));
+
+    Self {
+      date,
+      version,
+      bodies: display_bodies(&commits, config),
+      bumps,
+      authors: display_authors(&commits),
+      show_authors: config.show_authors,
+      links,
+      hash,
+      url,
+      issues_url,
+      heading_format: config.heading_format.clone(),
+      commits: changelog_subjects(commits, config),
+    }
+  }
 }
 
 fn priority(subject: &str) -> i32 {
@@ -89,8 +196,35 @@ fn resolve_changelog_target(
   }
 }
 
-fn commit_changelog(commit_message: &str, verbose: bool) -> Result<()> {
+fn changelog_matches_head(repo: &Repository) -> Result<bool> {
+  let Some(head_tree) = repo.head().ok().and_then(|head| head.peel_to_tree().ok()) else {
+    return Ok(false);
+  };
+
+  let Ok(entry) = head_tree.get_path(Path::new("CHANGELOG.md")) else {
+    return Ok(false);
+  };
+
+  let blob = repo
+    .find_blob(entry.id())
+    .context("Cannot read CHANGELOG.md from HEAD")?;
+  let current = fs::read("CHANGELOG.md").context("Failed to read CHANGELOG.md")?;
+
+  Ok(blob.content() == current.as_slice())
+}
+
+fn commit_changelog(commit_message: &str, verbose: bool, signing_key: Option<&str>) -> Result<()> {
   let repo = Repository::discover(".").context("Failed to discover git repository")?;
+
+  // Regenerating identical content must not produce an empty commit.
+  if changelog_matches_head(&repo)? {
+    if verbose {
+      eprintln!("CHANGELOG.md is unchanged. Skipping auto-commit.");
+    }
+
+    return Ok(());
+  }
+
   let mut options = StatusOptions::new();
   options.include_untracked(false).recurse_untracked_dirs(false);
 
@@ -122,16 +256,7 @@ fn commit_changelog(commit_message: &str, verbose: bool) -> Result<()> {
     }
 
     let parent_refs = parents.iter().collect::<Vec<_>>();
-    repo
-      .commit(
-        Some("HEAD"),
-        &signature,
-        &signature,
-        commit_message,
-        &tree,
-        &parent_refs,
-      )
-      .context("Cannot create git commit")?;
+    create_commit(&repo, &signature, commit_message, &tree, &parent_refs, signing_key)?;
   } else if verbose {
     eprintln!("Skipping auto-commit: files changed are {:?}", changed_paths);
   }
@@ -151,14 +276,87 @@ pub fn apply_default_sorting(commits: &mut [GitCommit]) {
 }
 
 pub fn extract_versions(markdown: &str) -> HashSet<String> {
-  let re =
-    Regex::new(r"(?m)^###\s+\d{4}-\d{2}-\d{2}\s*/\s*([0-9]+\.[0-9]+\.[0-9]+)\s*$").expect("version regex must compile");
+  collect_versions(&default_version_regex(), markdown)
+}
 
+fn default_version_regex() -> Regex {
+  Regex::new(&format!(
+    r"(?m)^###\s+\d{{4}}-\d{{2}}-\d{{2}}\s*/\s*{SEMVER_CAPTURE}\s*$"
+  ))
+  .expect("version regex must compile")
+}
+
+/// Regex of the section headings, capturing their version: the configured
+/// `version_pattern`, or else a pattern derived from `heading_format`.
+fn configured_version_regex(config: &EffectiveConfig) -> Result<Regex> {
+  let pattern = match (&config.version_pattern, &config.heading_format) {
+    (Some(pattern), _) => pattern.clone(),
+    (None, Some(heading_format)) => {
+      format!(
+        r"(?m)^{}\s*$",
+        regex::escape(heading_format.trim())
+          .replace(r"\$DATE", r"\d{4}-\d{2}-\d{2}")
+          .replace(r"\$VERSION", SEMVER_CAPTURE)
+      )
+    }
+    (None, None) => return Ok(default_version_regex()),
+  };
+
+  Regex::new(&pattern).context(format!("Invalid version_pattern regex: {pattern}"))
+}
+
+/// Extracts the versions of the sections of `markdown` using the configured
+/// `version_pattern`, or else a pattern derived from `heading_format`.
+pub fn extract_configured_versions(markdown: &str, config: &EffectiveConfig) -> Result<HashSet<String>> {
+  Ok(collect_versions(&configured_version_regex(config)?, markdown))
+}
+
+/// Returns the text of the `version` section of `markdown`, between its
+/// heading and the next one, or `None` when there is no such section.
+pub fn extract_section(markdown: &str, version: &str) -> Option<String> {
+  find_section(&default_version_regex(), markdown, version)
+}
+
+/// Like [`extract_section`], locating the headings like
+/// [`extract_configured_versions`].
+pub fn extract_configured_section(markdown: &str, version: &str, config: &EffectiveConfig) -> Result<Option<String>> {
+  Ok(find_section(&configured_version_regex(config)?, markdown, version))
+}
+
+fn find_section(re: &Regex, markdown: &str, version: &str) -> Option<String> {
+  let mut headings = re.captures_iter(markdown);
+  let heading = headings.find(|capture| capture.get(1).is_some_and(|m| m.as_str() == version))?;
+  let start = heading.get(0)?.end();
+  let end = headings
+    .next()
+    .and_then(|capture| capture.get(0))
+    .map_or(markdown.len(), |m| m.start());
+
+  Some(markdown[start..end].trim().to_string())
+}
+
+fn collect_versions(re: &Regex, markdown: &str) -> HashSet<String> {
   re.captures_iter(markdown)
     .filter_map(|capture| capture.get(1).map(|m| m.as_str().to_string()))
     .collect::<HashSet<_>>()
 }
 
+/// Returns whether `version` is already covered by one of the `existing`
+/// CHANGELOG.md versions.
+///
+/// Versions are compared by semver precedence (build metadata is ignored) and
+/// only against sections sharing the same major, minor and patch numbers. A
+/// version is covered when such a section has equal or higher precedence, so
+/// `1.2.3` is covered by `1.2.3` but not by `1.2.3-rc.1`, while `1.2.3-rc.1`
+/// is covered by `1.2.3-rc.2` or `1.2.3`.
+pub fn is_version_recorded(version: &Version, existing: &HashSet<String>) -> bool {
+  existing
+    .iter()
+    .filter_map(|raw| Version::parse(raw).ok())
+    .filter(|other| other.major == version.major && other.minor == version.minor && other.patch == version.patch)
+    .any(|other| other.cmp_precedence(version) != std::cmp::Ordering::Less)
+}
+
 pub fn format_date(timestamp: i64) -> String {
   DateTime::<Utc>::from_timestamp(timestamp, 0)
     .unwrap_or(DateTime::<Utc>::UNIX_EPOCH)
@@ -166,33 +364,125 @@ pub fn format_date(timestamp: i64) -> String {
     .to_string()
 }
 
+/// Links `#123` and `GH-123` references to `issues_url`, leaving references
+/// embedded in longer tokens (like `abc#123def`) untouched.
+fn link_issue_references(subject: &str, issues_url: &str) -> String {
+  Regex::new(r"(^|[^\w#\[-])(#|GH-)(\d+)\b")
+    .expect("issue reference regex must compile")
+    .replace_all(subject, |captures: &regex::Captures| {
+      format!(
+        "{}[{}{}]({issues_url}/{})",
+        &captures[1], &captures[2], &captures[3], &captures[3]
+      )
+    })
+    .into_owned()
+}
+
+fn render_entry(subject: &str, body: Option<&str>) -> String {
+  let mut entry = format!("- {subject}");
+
+  for line in body.unwrap_or_default().lines() {
+    entry.push('\n');
+    if !line.trim().is_empty() {
+      entry.push_str("  ");
+      entry.push_str(line.trim_end());
+    }
+  }
+
+  entry
+}
+
 pub fn render_section(section: &ChangelogSection, template: Option<&str>) -> String {
+  let entries = section
+    .commits
+    .iter()
+    .enumerate()
+    .map(|(index, subject)| {
+      let subject = match section.issues_url.as_deref() {
+        Some(issues_url) => link_issue_references(subject, issues_url),
+        None => subject.clone(),
+      };
+      let subject = match section.bumps.get(index) {
+        Some(bump) => format!("[{}] {subject}", bump.as_str()),
+        None => subject,
+      };
+      let subject = match section.authors.get(index) {
+        Some(author) if section.show_authors && template.is_none() && !author.is_empty() => {
+          format!("{subject} (@{author})")
+        }
+        _ => subject,
+      };
+      let subject = match section.links.get(index) {
+        Some(link) => format!("{subject} {link}"),
+        None => subject,
+      };
+
+      render_entry(&subject, section.bodies.get(index).map(String::as_str))
+    })
+    .collect::<Vec<_>>();
+
   if let Some(template) = template {
-    let commits = section
-      .commits
-      .iter()
-      .map(|entry| format!("- {entry}"))
-      .collect::<Vec<_>>()
-      .join("\n");
-
-    return template
-      .replace("$DATE", &section.date)
-      .replace("$VERSION", &section.version)
-      .replace("$COMMITS", &commits)
-      .trim()
-      .to_string();
+    let commits = entries.join("\n");
+    let mut authors = Vec::new();
+    for author in &section.authors {
+      if !author.is_empty() && !authors.contains(author) {
+        authors.push(author.clone());
+      }
+    }
+
+    return render_template(
+      template,
+      &[
+        ("DATE", &section.date),
+        ("VERSION", &section.version),
+        ("COMMITS", &commits),
+        ("AUTHOR", &authors.join(", ")),
+        ("HASH", &section.hash),
+        ("URL", &section.url),
+      ],
+    )
+    .trim()
+    .to_string();
   }
 
-  let mut output = format!("### {} / {}\n\n", section.date, section.version);
-  for commit in &section.commits {
-    output.push_str("- ");
-    output.push_str(commit);
+  let mut output = format!(
+    "{}\n\n",
+    render_template(
+      section.heading_format.as_deref().unwrap_or("### $DATE / $VERSION"),
+      &[("DATE", &section.date), ("VERSION", &section.version)],
+    )
+  );
+  for entry in &entries {
+    output.push_str(entry);
     output.push('\n');
   }
 
   output.trim().to_string()
 }
 
+/// Replaces the `$NAME` placeholders of `template` with their values in a
+/// single pass, so placeholders inside the values are left as they are.
+pub fn render_template(template: &str, values: &[(&str, &str)]) -> String {
+  let mut output = String::with_capacity(template.len());
+  let mut rest = template;
+
+  while let Some(position) = rest.find('$') {
+    output.push_str(&rest[..position]);
+    rest = &rest[position + 1..];
+
+    match values.iter().find(|(name, _)| rest.starts_with(name)) {
+      Some((name, value)) => {
+        output.push_str(value);
+        rest = &rest[name.len()..];
+      }
+      None => output.push('$'),
+    }
+  }
+
+  output.push_str(rest);
+  output
+}
+
 pub fn with_prepended_section(existing: &str, section_markdown: &str) -> String {
   let existing = existing.trim();
   if existing.is_empty() {
@@ -202,49 +492,303 @@ pub fn with_prepended_section(existing: &str, section_markdown: &str) -> String
   format!("{section_markdown}\n\n{existing}\n")
 }
 
+/// Parts of a keep-a-changelog file around its version sections.
+struct KeepAChangelog {
+  /// Text before the `## [Unreleased]` section, like the `# Changelog` title.
+  preamble: String,
+  /// Notes written by hand in the `## [Unreleased]` section.
+  unreleased: String,
+  /// The version sections.
+  sections: String,
+}
+
+/// Splits a keep-a-changelog file, dropping its link definitions since they
+/// are regenerated.
+fn parse_keepachangelog(markdown: &str) -> KeepAChangelog {
+  let link_definition = Regex::new(r"^\[[^\]]+\]:\s+\S+\s*$").expect("link definition regex must compile");
+  let lines = markdown
+    .lines()
+    .filter(|line| !link_definition.is_match(line))
+    .collect::<Vec<_>>();
+
+  let unreleased_at = lines
+    .iter()
+    .position(|line| line.trim().eq_ignore_ascii_case("## [Unreleased]"));
+  let sections_from = unreleased_at.map_or(0, |index| index + 1);
+  let sections_at = lines[sections_from..]
+    .iter()
+    .position(|line| line.starts_with("## "))
+    .map_or(lines.len(), |index| index + sections_from);
+
+  KeepAChangelog {
+    preamble: lines[..unreleased_at.unwrap_or(sections_at)]
+      .join("\n")
+      .trim()
+      .to_string(),
+    unreleased: unreleased_at
+      .map(|index| lines[index + 1..sections_at].join("\n").trim().to_string())
+      .unwrap_or_default(),
+    sections: lines[sections_at..].join("\n").trim().to_string(),
+  }
+}
+
+/// Builds the link definitions of the `## [<version>]` headings of
+/// `sections`, comparing each tag with the previous one. Nothing is linked when
+/// the repository is unknown.
+fn keepachangelog_links(sections: &str, config: &EffectiveConfig) -> Result<Vec<String>> {
+  let Ok((owner, repo)) = resolve_owner_repo(config) else {
+    return Ok(Vec::new());
+  };
+
+  let heading = Regex::new(r"(?m)^## \[([^\]]+)\]").expect("keepachangelog heading regex must compile");
+  let versions = heading
+    .captures_iter(sections)
+    .map(|capture| capture[1].to_string())
+    .collect::<Vec<_>>();
+  let tags = versions
+    .iter()
+    .map(|version| tag_name(version, config))
+    .collect::<Result<Vec<_>>>()?;
+
+  let mut links = Vec::new();
+  if let Some(latest) = tags.first() {
+    links.push(format!(
+      "[Unreleased]: {}",
+      config
+        .provider
+        .compare_url(config.web_host(), &owner, &repo, latest, "HEAD")
+    ));
+  }
+
+  for (index, (version, tag)) in versions.iter().zip(&tags).enumerate() {
+    let url = match tags.get(index + 1) {
+      Some(previous) => {
+        config
+          .provider
+          .compare_url(config.web_host(), &owner, &repo, previous, tag)
+      }
+      None => config.provider.tag_url(config.web_host(), &owner, &repo, tag),
+    };
+    links.push(format!("[{version}]: {url}"));
+  }
+
+  Ok(links)
+}
+
+fn render_keepachangelog(changelog: &KeepAChangelog, config: &EffectiveConfig) -> Result<String> {
+  let mut blocks = Vec::new();
+
+  if !changelog.preamble.is_empty() {
+    blocks.push(changelog.preamble.clone());
+  }
+
+  if changelog.unreleased.is_empty() {
+    blocks.push("## [Unreleased]".to_string());
+  } else {
+    blocks.push(format!("## [Unreleased]\n\n{}", changelog.unreleased));
+  }
+
+  if !changelog.sections.is_empty() {
+    blocks.push(changelog.sections.clone());
+  }
+
+  let links = keepachangelog_links(&changelog.sections, config)?;
+  if !links.is_empty() {
+    blocks.push(links.join("\n"));
+  }
+
+  Ok(format!("{}\n", blocks.join("\n\n")))
+}
+
+/// Rewrites a GitHub pull request merge commit (`Merge pull request #12 from
+/// user/feature`, with the pull request title as the first body line) into a
+/// `<title> (#12)` entry.
+fn merge_pr_entry(commit: GitCommit) -> GitCommit {
+  let number = Regex::new(r"^Merge pull request #(\d+) from \S+")
+    .expect("merge pull request regex must compile")
+    .captures(&commit.subject)
+    .map(|captures| captures[1].to_string());
+
+  let Some(number) = number.filter(|_| commit.parent_count >= 2) else {
+    return commit;
+  };
+
+  let mut lines = commit.body.lines().skip_while(|line| line.trim().is_empty());
+  let Some(title) = lines.next().map(str::trim) else {
+    return commit;
+  };
+
+  GitCommit {
+    subject: format!("{title} (#{number})"),
+    body: lines.collect::<Vec<_>>().join("\n").trim().to_string(),
+    ..commit
+  }
+}
+
+/// Drops `git revert` commits (with a `This reverts commit <sha>.` body line)
+/// together with the commit they revert, when both are in the range.
+fn drop_reverted_commits(commits: Vec<GitCommit>) -> Vec<GitCommit> {
+  let revert_regex =
+    Regex::new(r"(?m)^This reverts commit ([0-9a-f]{7,40})\b").expect("revert body regex must compile");
+  let is_reverted = |commit: &GitCommit, reverted: &str| !commit.id.is_empty() && reverted.starts_with(&commit.id);
+
+  let pairs = commits
+    .iter()
+    .filter_map(|commit| {
+      let reverted = revert_regex.captures(&commit.body)?[1].to_string();
+      commits
+        .iter()
+        .any(|original| is_reverted(original, &reverted))
+        .then(|| (commit.id.clone(), reverted))
+    })
+    .collect::<Vec<_>>();
+
+  commits
+    .into_iter()
+    .filter(|commit| {
+      !pairs
+        .iter()
+        .any(|(revert, reverted)| commit.id == *revert || is_reverted(commit, reverted))
+    })
+    .collect()
+}
+
 pub fn collect_releasable_commits(mut commits: Vec<GitCommit>, filter: &CommitFilter) -> Vec<GitCommit> {
-  commits.retain(|commit| !filter.is_ignored(&commit.subject) && !commit.subject.starts_with("chore"));
+  if filter.includes_merge_prs() {
+    commits = commits.into_iter().map(merge_pr_entry).collect();
+  }
+
+  let mut commits = drop_reverted_commits(commits);
+
+  commits.retain(|commit| {
+    !filter.is_ignored(&commit.subject) && !filter.is_type_excluded(commit) && filter.is_author_kept(commit)
+  });
+  commits
+}
+
+/// Turns commits into the subjects shown in changelog sections and release
+/// bodies, applying the configured display options.
+pub fn display_subjects(commits: Vec<GitCommit>, config: &EffectiveConfig) -> Vec<String> {
   commits
+    .into_iter()
+    .map(|commit| {
+      if config.changelog_strip_scope {
+        strip_scope(&commit.subject)
+      } else {
+        commit.subject
+      }
+    })
+    .collect()
+}
+
+/// Turns commits into the subjects shown in changelog sections, which can
+/// additionally drop the conventional-commit header.
+pub fn changelog_subjects(commits: Vec<GitCommit>, config: &EffectiveConfig) -> Vec<String> {
+  let subjects = display_subjects(commits, config);
+
+  if !config.strip_prefixes {
+    return subjects;
+  }
+
+  subjects.iter().map(|subject| strip_prefix(subject)).collect()
 }
 
-fn render_tag_history_sections(tags: &[GitTag], filter: &CommitFilter, template: Option<&str>) -> Result<Vec<String>> {
+/// Returns the part of a commit body shown in changelog entries.
+///
+/// The last paragraph is dropped when every line in it is a git trailer: a
+/// hyphenated token (`Co-authored-by:`, `Signed-off-by:`), `BREAKING CHANGE:`
+/// or a known single-word token like `Refs:`, so prose like `Note: ...` stays.
+pub fn display_body(body: &str) -> String {
+  let trailer =
+    Regex::new(r"^(?:BREAKING CHANGE|[A-Za-z][A-Za-z0-9]*(?:-[A-Za-z0-9]+)+|(?i:Refs|Ref|Fixes|Closes|Resolves)):\s")
+      .expect("trailer regex must compile");
+  let body = body.trim();
+
+  let (content, last_paragraph) = match body.rsplit_once("\n\n") {
+    Some((content, last_paragraph)) => (content, last_paragraph),
+    None => ("", body),
+  };
+
+  if !last_paragraph.is_empty() && last_paragraph.lines().all(|line| trailer.is_match(line)) {
+    content.trim_end().to_string()
+  } else {
+    body.to_string()
+  }
+}
+
+/// Returns the author names of changelog entries.
+pub fn display_authors(commits: &[GitCommit]) -> Vec<String> {
+  commits.iter().map(|commit| commit.author_name.clone()).collect()
+}
+
+/// Returns the bodies shown beneath changelog entries, or nothing when bodies
+/// are not displayed.
+pub fn display_bodies(commits: &[GitCommit], config: &EffectiveConfig) -> Vec<String> {
+  if !config.changelog_include_body {
+    return Vec::new();
+  }
+
+  commits.iter().map(|commit| display_body(&commit.body)).collect()
+}
+
+/// Renders the sections of the tagged releases, oldest first. Anchor tags
+/// bound the ranges like any other tag but get no section: their commits are
+/// carried into the next section, and returned when no tag follows them.
+fn render_tag_history_sections(
+  tags: &[GitTag],
+  filter: &CommitFilter,
+  config: &EffectiveConfig,
+) -> Result<(Vec<String>, Vec<GitCommit>)> {
   let mut historical = Vec::new();
   let mut previous_tag_name: Option<String> = None;
+  let mut carried = Vec::new();
 
   for tag in tags.iter().rev() {
-    let commits = collect_releasable_commits(
-      read_commits_between_tags(previous_tag_name.as_deref(), &tag.name)?,
+    let mut commits = std::mem::take(&mut carried);
+    commits.extend(collect_releasable_commits(
+      read_commits_between_tags(
+        previous_tag_name.as_deref(),
+        &tag.name,
+        config.max_commits,
+        &config.paths,
+      )?,
       filter,
-    );
+    ));
+    previous_tag_name = Some(tag.name.clone());
+
+    if config.anchor_tags.contains(&tag.name) {
+      carried = commits;
+      continue;
+    }
 
     if !commits.is_empty() {
-      let mut commits = commits;
       apply_default_sorting(&mut commits);
 
       if let Some(version) = normalize_tag_version(&tag.name) {
-        let date = format_date(commits.first().map(|commit| commit.time).unwrap_or(tag.time));
-        let section = ChangelogSection {
-          date,
-          version: version.to_string(),
-          commits: commits.into_iter().map(|commit| commit.subject).collect::<Vec<_>>(),
-        };
-
-        historical.push(render_section(&section, template));
+        let date = format_date(match config.changelog_date_source {
+          ChangelogDateSource::Commit => commits.first().map(|commit| commit.time).unwrap_or(tag.time),
+          ChangelogDateSource::Tag => tag.tagged_at,
+        });
+        let section = ChangelogSection::from_commits(date, version.to_string(), commits, config);
+
+        historical.push(render_section(&section, config.changelog_template.as_deref()));
       }
     }
-
-    previous_tag_name = Some(tag.name.clone());
   }
 
-  Ok(historical)
+  Ok((historical, carried))
 }
 
 fn build_rebuild_output(config: &EffectiveConfig, filter: &CommitFilter, template: Option<&str>) -> Result<String> {
   let tags = read_tags(&config.tag_pattern)?;
-  let historical = render_tag_history_sections(&tags, filter, template)?;
+  let (historical, carried) = render_tag_history_sections(&tags, filter, config)?;
   let latest_version = tags.first().and_then(|tag| normalize_tag_version(&tag.name));
 
-  let mut pending_commits = collect_releasable_commits(read_commits(None, &config.tag_pattern)?, filter);
+  let mut pending_commits = carried;
+  pending_commits.extend(collect_releasable_commits(
+    read_commits(None, &config.tag_pattern, config.max_commits, &config.paths)?,
+    filter,
+  ));
   let mut sections = Vec::new();
 
   if !pending_commits.is_empty() {
@@ -252,18 +796,13 @@ fn build_rebuild_output(config: &EffectiveConfig, filter: &CommitFilter, templat
 
     let bump = pending_commits
       .iter()
-      .map(|commit| infer_bump(&commit.subject, &commit.body))
+      .map(|commit| infer_commit_bump(commit, config))
       .max()
       .unwrap_or(BumpLevel::Patch);
 
-    let section = ChangelogSection {
-      date: format_date(pending_commits.first().map(|commit| commit.time).unwrap_or(0)),
-      version: bump_version(latest_version, bump).to_string(),
-      commits: pending_commits
-        .into_iter()
-        .map(|commit| commit.subject)
-        .collect::<Vec<_>>(),
-    };
+    let date = format_date(pending_commits.first().map(|commit| commit.time).unwrap_or(0));
+    let version = bump_version(latest_version, bump).to_string();
+    let section = ChangelogSection::from_commits(date, version, pending_commits, config);
 
     sections.push(render_section(&section, template));
   }
@@ -279,104 +818,330 @@ fn build_rebuild_output(config: &EffectiveConfig, filter: &CommitFilter, templat
   Ok(format!("{}\n", sections.join("\n\n")))
 }
 
-pub fn execute_changelog_command(changelog_args: &ChangelogArgs, config: &EffectiveConfig) -> Result<()> {
-  if changelog_args.rebuild && changelog_args.target.is_some() {
-    return Err(anyhow!("Cannot combine --rebuild with an explicit changelog target"));
+fn execute_remote_changelog(
+  version: &Version,
+  latest_tag: Option<&GitTag>,
+  dry_run: bool,
+  config: &EffectiveConfig,
+) -> Result<()> {
+  if config.provider != Provider::Gitlab {
+    return Err(anyhow!("--remote requires the gitlab provider"));
   }
 
-  let filter = CommitFilter::new(&config.ignore_patterns)?;
-  let template = config.changelog_template.as_deref();
-  let changelog_path = Path::new("CHANGELOG.md");
+  let (owner, repo) = resolve_owner_repo(config)?;
 
-  if changelog_args.rebuild {
-    let output = build_rebuild_output(config, &filter, template)?;
-
-    if changelog_args.dry_run {
-      println!("{output}");
-      if changelog_args.commit {
-        let commit_message = changelog_args
-          .commit_message
-          .as_deref()
-          .unwrap_or("chore: Updated CHANGELOG.md.");
-        println!("dry-run: would commit CHANGELOG.md with message '{commit_message}'");
-      }
-      return Ok(());
-    }
+  if dry_run {
+    println!("dry-run: would generate the GitLab changelog for {version} in {owner}/{repo}");
+    return Ok(());
+  }
 
-    fs::write(changelog_path, output).context("Failed to write CHANGELOG.md")?;
+  if config.offline {
+    return Err(anyhow!("Cannot call the GitLab API in offline mode"));
+  }
 
-    if changelog_args.commit {
-      let commit_message = changelog_args
-        .commit_message
-        .as_deref()
-        .unwrap_or("chore: Updated CHANGELOG.md.");
-      commit_changelog(commit_message, config.verbose)?;
-    }
+  let from = latest_tag.map(|tag| tag.oid.to_string());
+  let to = read_head_oid()?.to_string();
+
+  generate_remote_changelog(
+    &config.api_base_url()?,
+    &owner,
+    &repo,
+    &resolve_token(config)?,
+    &version.to_string(),
+    from.as_deref(),
+    &to,
+  )
+}
 
+/// Prints the pending section under an "Unreleased" heading, leaving
+/// CHANGELOG.md untouched.
+fn print_unreleased_section(mut commits: Vec<GitCommit>, config: &EffectiveConfig) -> Result<()> {
+  if commits.is_empty() {
+    if config.verbose {
+      eprintln!("No releasable commits found.");
+    }
     return Ok(());
   }
 
-  let tags = read_tags(&config.tag_pattern)?;
-  let latest_version = tags.first().and_then(|tag| normalize_tag_version(&tag.name));
-  let commits = collect_releasable_commits(read_commits(None, &config.tag_pattern)?, &filter);
+  apply_default_sorting(&mut commits);
+
+  let date = format_date(commits.first().map(|commit| commit.time).unwrap_or(0));
+  let section = ChangelogSection::from_commits(date, "Unreleased".to_string(), commits, config);
+
+  println!("{}", render_section(&section, config.changelog_template.as_deref()));
+  Ok(())
+}
+
+/// Pending commits with the version they would be released as.
+pub struct PendingChangelog {
+  pub commits: Vec<GitCommit>,
+  pub version: Version,
+  pub latest_tag: Option<GitTag>,
+}
+
+/// Markdown of CHANGELOG.md, with the section added to it when there is one.
+struct GeneratedChangelog {
+  markdown: String,
+  section: Option<String>,
+  /// Computed version of the section, unset when rebuilding.
+  version: Option<Version>,
+}
+
+impl GeneratedChangelog {
+  /// Machine-readable summary, so a later step can reuse the version and notes.
+  fn summary(&self, written: bool) -> serde_json::Value {
+    serde_json::json!({
+      "version": self.version.as_ref().map(Version::to_string),
+      "written": written,
+      "path": "CHANGELOG.md",
+      "section": self.section,
+    })
+  }
+}
+
+fn changelog_config(changelog_args: &ChangelogArgs, config: &EffectiveConfig) -> EffectiveConfig {
+  let changelog_style = changelog_args.style.unwrap_or(config.changelog_style);
+  let heading_format = match changelog_style {
+    ChangelogStyle::Cambi => config.heading_format.clone(),
+    ChangelogStyle::Keepachangelog => Some(KEEPACHANGELOG_HEADING.to_string()),
+  };
+
+  EffectiveConfig {
+    changelog_include_body: config.changelog_include_body || changelog_args.include_body,
+    changelog_annotate_bump: config.changelog_annotate_bump || changelog_args.annotate_bump,
+    changelog_style,
+    heading_format,
+    ..config.clone()
+  }
+}
+
+fn read_pending_changelog(
+  changelog_args: &ChangelogArgs,
+  config: &EffectiveConfig,
+  filter: &CommitFilter,
+) -> Result<PendingChangelog> {
+  read_pending_changelog_in(
+    Path::new("."),
+    changelog_args.since.as_deref(),
+    changelog_args.target.as_deref(),
+    config,
+    filter,
+  )
+}
+
+/// Reads the commits pending in the repository at `repo_path` since `since`
+/// (the latest matching tag when unset) and the version they would be
+/// released as, `target` when given.
+pub fn read_pending_changelog_in(
+  repo_path: &Path,
+  since: Option<&str>,
+  target: Option<&str>,
+  config: &EffectiveConfig,
+  filter: &CommitFilter,
+) -> Result<PendingChangelog> {
+  let tags = read_tags_in(repo_path, &config.tag_pattern)?;
+  // With --since, the section is computed from that tag's version, so a past
+  // section can be regenerated.
+  let latest_version = since
+    .and_then(normalize_tag_version)
+    .or_else(|| tags.first().and_then(|tag| normalize_tag_version(&tag.name)));
+  let commits = collect_releasable_commits(
+    read_commits_in(repo_path, since, &config.tag_pattern, config.max_commits, &config.paths)?,
+    filter,
+  );
 
   let bump = commits
     .iter()
-    .map(|commit| infer_bump(&commit.subject, &commit.body))
+    .map(|commit| infer_commit_bump(commit, config))
     .max()
     .unwrap_or(BumpLevel::Patch);
 
-  let next_version = resolve_changelog_target(latest_version, changelog_args.target.as_deref(), bump)?;
-  let existing = fs::read_to_string(changelog_path).unwrap_or_default();
-  let existing_versions = extract_versions(&existing);
-  let next_version_string = next_version.to_string();
+  let initial = match (&latest_version, target) {
+    (None, None) => initial_release_version_in(repo_path, config)?,
+    _ => None,
+  };
+  let version = match initial {
+    Some(initial) => initial,
+    None => resolve_changelog_target(latest_version, target, bump)?,
+  };
+
+  Ok(PendingChangelog {
+    commits,
+    version,
+    latest_tag: tags.into_iter().next(),
+  })
+}
 
-  if existing_versions.contains(&next_version_string) {
+fn build_changelog(changelog_args: &ChangelogArgs, config: &EffectiveConfig) -> Result<GeneratedChangelog> {
+  if changelog_args.rebuild && changelog_args.target.is_some() {
+    return Err(anyhow!("Cannot combine --rebuild with an explicit changelog target"));
+  }
+
+  let filter = CommitFilter::from_config(config)?;
+  let template = config.changelog_template.as_deref();
+  let existing = fs::read_to_string("CHANGELOG.md").unwrap_or_default();
+
+  if changelog_args.rebuild {
+    let mut markdown = build_rebuild_output(config, &filter, template)?;
+
+    if config.changelog_style == ChangelogStyle::Keepachangelog {
+      markdown = render_keepachangelog(
+        &KeepAChangelog {
+          sections: markdown.trim().to_string(),
+          ..parse_keepachangelog(&existing)
+        },
+        config,
+      )?;
+    }
+
+    return Ok(GeneratedChangelog {
+      markdown,
+      section: None,
+      version: None,
+    });
+  }
+
+  let PendingChangelog {
+    mut commits, version, ..
+  } = read_pending_changelog(changelog_args, config, &filter)?;
+  let existing_versions = extract_configured_versions(&existing, config)?;
+  let unchanged = |existing: String| {
+    GeneratedChangelog {
+      markdown: existing,
+      section: None,
+      version: Some(version.clone()),
+    }
+  };
+
+  if is_version_recorded(&version, &existing_versions) {
     if config.verbose {
-      eprintln!("Version {} already exists in CHANGELOG.md", next_version_string);
+      eprintln!("Version {} already exists in CHANGELOG.md", version);
     }
-    return Ok(());
+    return Ok(unchanged(existing));
   }
 
-  let mut commits = commits;
   if commits.is_empty() {
     if config.verbose {
       eprintln!("No releasable commits found. CHANGELOG.md not updated.");
     }
-    return Ok(());
+    return Ok(unchanged(existing));
   }
 
   apply_default_sorting(&mut commits);
 
-  let section = ChangelogSection {
-    date: format_date(commits.first().map(|commit| commit.time).unwrap_or(0)),
-    version: next_version_string,
-    commits: commits.into_iter().map(|commit| commit.subject).collect::<Vec<_>>(),
-  };
+  let date = format_date(commits.first().map(|commit| commit.time).unwrap_or(0));
+  let section = ChangelogSection::from_commits(date, version.to_string(), commits, config);
 
   let section_markdown = render_section(&section, template);
-  let output = with_prepended_section(&existing, &section_markdown);
+  let markdown = match config.changelog_style {
+    ChangelogStyle::Cambi => with_prepended_section(&existing, &section_markdown),
+    ChangelogStyle::Keepachangelog => {
+      let existing = parse_keepachangelog(&existing);
+      let sections = with_prepended_section(&existing.sections, &section_markdown);
+      render_keepachangelog(
+        &KeepAChangelog {
+          sections: sections.trim().to_string(),
+          ..existing
+        },
+        config,
+      )?
+    }
+  };
+
+  Ok(GeneratedChangelog {
+    markdown,
+    section: Some(section_markdown),
+    version: Some(version),
+  })
+}
+
+/// Returns the full CHANGELOG.md markdown the changelog command would write,
+/// without writing or committing anything.
+///
+/// When there is nothing to add, the current content of CHANGELOG.md is
+/// returned unchanged.
+pub fn generate_changelog(config: &EffectiveConfig, changelog_args: &ChangelogArgs) -> Result<String> {
+  let config = changelog_config(changelog_args, config);
+  Ok(build_changelog(changelog_args, &config)?.markdown)
+}
+
+pub fn execute_changelog_command(changelog_args: &ChangelogArgs, config: &EffectiveConfig) -> Result<()> {
+  let config = &changelog_config(changelog_args, config);
+
+  if changelog_args.print_range {
+    eprintln!(
+      "{}",
+      describe_commit_range(changelog_args.since.as_deref(), &config.tag_pattern)?
+    );
+  }
+
+  if changelog_args.unreleased || changelog_args.remote {
+    let filter = CommitFilter::from_config(config)?;
+    let pending = read_pending_changelog(changelog_args, config, &filter)?;
+
+    if changelog_args.unreleased {
+      return print_unreleased_section(pending.commits, config);
+    }
+
+    return execute_remote_changelog(
+      &pending.version,
+      pending.latest_tag.as_ref(),
+      changelog_args.dry_run,
+      config,
+    );
+  }
+
+  // A missing key must fail before CHANGELOG.md is touched.
+  let signing_key = if changelog_args.commit && (changelog_args.sign || config.sign) {
+    Some(signing_key()?)
+  } else {
+    None
+  };
+  let generated = build_changelog(changelog_args, config)?;
+  let json = config.format == OutputFormat::Json;
+
+  if !changelog_args.rebuild && generated.section.is_none() {
+    if json {
+      println!("{}", generated.summary(false));
+    }
+    return Ok(());
+  }
+
+  let commit_message = changelog_args
+    .commit_message
+    .as_deref()
+    .unwrap_or("chore: Updated CHANGELOG.md.");
 
   if changelog_args.dry_run {
-    println!("{output}");
-    if changelog_args.commit {
-      let commit_message = changelog_args
-        .commit_message
-        .as_deref()
-        .unwrap_or("chore: Updated CHANGELOG.md.");
+    if json {
+      println!("{}", generated.summary(false));
+    } else {
+      println!("{}", generated.markdown);
+    }
+    if changelog_args.commit && json {
+      eprintln!("dry-run: would commit CHANGELOG.md with message '{commit_message}'");
+    } else if changelog_args.commit {
       println!("dry-run: would commit CHANGELOG.md with message '{commit_message}'");
     }
     return Ok(());
   }
 
-  fs::write(changelog_path, output).context("Failed to write CHANGELOG.md")?;
+  write_atomic(Path::new("CHANGELOG.md"), &generated.markdown).context("Failed to write CHANGELOG.md")?;
 
   if changelog_args.commit {
-    let commit_message = changelog_args
-      .commit_message
-      .as_deref()
-      .unwrap_or("chore: Updated CHANGELOG.md.");
-    commit_changelog(commit_message, config.verbose)?;
+    commit_changelog(commit_message, config.verbose, signing_key.as_deref())?;
+
+    if changelog_args.verify_clean {
+      verify_clean_worktree()?;
+    }
+  }
+
+  if json {
+    println!("{}", generated.summary(true));
+  } else if changelog_args.print
+    && let Some(section) = generated.section
+  {
+    println!("{section}");
   }
 
   Ok(())
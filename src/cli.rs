@@ -1,12 +1,32 @@
-use std::path::PathBuf;
+use std::{io::Write, path::PathBuf};
 
-use clap::{Parser, Subcommand};
+use clap::{ArgGroup, CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+
+use crate::{
+  config::{ChangelogStyle, OutputFormat, Provider},
+  conventional::BumpLevel,
+};
 
 #[derive(clap::Args, Debug)]
 pub struct SemverArgs {
   /// Override start tag instead of auto-detecting latest version tag.
   #[arg(long, short = 'f')]
   pub from_tag: Option<String>,
+
+  /// Override the inferred bump with semver:* labels of referenced GitHub
+  /// pull requests.
+  #[arg(long)]
+  pub bump_from_labels: bool,
+
+  /// Print the commit range being considered to stderr.
+  #[arg(long)]
+  pub print_range: bool,
+
+  /// Also print how many pending commits are features, fixes, breaking
+  /// changes or other.
+  #[arg(long)]
+  pub stats: bool,
 }
 
 #[derive(clap::Args, Debug)]
@@ -14,34 +34,121 @@ pub struct VersionArgs {
   /// Override start tag instead of auto-detecting latest version tag.
   #[arg(long, short = 'f')]
   pub from_tag: Option<String>,
+
+  /// Write CAMBI_VERSION, CAMBI_TAG and CAMBI_BUMP to a dot-env file.
+  #[arg(long, value_name = "PATH")]
+  pub write_env: Option<PathBuf>,
+
+  /// Preview the versions produced by each comma-separated bump level.
+  #[arg(long, value_name = "LEVELS", value_delimiter = ',')]
+  pub next: Vec<BumpLevel>,
+
+  /// Fail if the version file and the latest tag disagree on the version.
+  #[arg(long, conflicts_with_all = ["from_tag", "write_env", "next"])]
+  pub check_consistency: bool,
+
+  /// Only consider tags reachable from HEAD, so a branch reports the latest
+  /// version of its own lineage.
+  #[arg(long, conflicts_with = "from_tag")]
+  pub reachable: bool,
+
+  /// Print the tag derived for the current version, failing if the tag pattern
+  /// cannot produce one.
+  #[arg(long, conflicts_with_all = ["write_env", "next", "check_consistency"])]
+  pub validate_tag_pattern: bool,
 }
 
 #[derive(clap::Args, Debug, Default)]
+#[command(group(ArgGroup::new("committing").args(["commit", "commit_all"]).multiple(true)))]
 pub struct UpdateArgs {
-  /// Optional explicit update target (major|minor|patch or a semver like 1.2.3
-  /// / v1.2.3).
+  /// Optional explicit update target (major|minor|patch|prerelease|release or
+  /// a semver like 1.2.3 / v1.2.3).
   pub target: Option<String>,
 
+  /// Bump to a prerelease with this identifier (like rc), or advance the
+  /// current one.
+  #[arg(long, value_name = "ID")]
+  pub pre: Option<String>,
+
+  /// Set the build metadata of the new version (like build.42) instead of
+  /// keeping the current one.
+  #[arg(long, value_name = "STRING")]
+  pub build: Option<String>,
+
   /// Override start tag instead of auto-detecting latest version tag.
   #[arg(long, short = 'f')]
   pub from_tag: Option<String>,
 
+  /// Override the inferred bump with semver:* labels of referenced GitHub
+  /// pull requests.
+  #[arg(long)]
+  pub bump_from_labels: bool,
+
+  /// Update this file instead of auto-detecting the version file.
+  #[arg(long, visible_alias = "version-file", value_name = "PATH")]
+  pub file: Option<PathBuf>,
+
+  /// Update every detected version file instead of the first one.
+  #[arg(long, conflicts_with = "file")]
+  pub all: bool,
+
+  /// Print a JSON summary of the update to stdout (same as --format json).
+  #[arg(long)]
+  pub json: bool,
+
+  /// Allow an explicit target not greater than the current version.
+  #[arg(long)]
+  pub allow_downgrade: bool,
+
   /// Update CHANGELOG.md before updating the version file.
   #[arg(long, short = 'l')]
   pub changelog: bool,
 
-  /// Auto-commit updated version file.
+  /// Auto-commit updated version file, along with other modified tracked files
+  /// unless commit_scope is version-file (deprecated sweep, use --commit-all).
   #[arg(long, short = 'o')]
   pub commit: bool,
 
-  /// Custom commit message (requires --commit).
-  #[arg(long, short = 'm', requires = "commit", value_name = "MESSAGE")]
+  /// Auto-commit updated version file and every other modified tracked file.
+  #[arg(long)]
+  pub commit_all: bool,
+
+  /// Custom commit message (requires --commit or --commit-all).
+  #[arg(long, short = 'm', requires = "committing", value_name = "MESSAGE")]
   pub commit_message: Option<String>,
 
-  /// Create a tag for the new version (requires --commit).
-  #[arg(long, short = 't', requires = "commit")]
+  /// Create a tag for the new version (requires --commit or --commit-all).
+  #[arg(long, short = 't', requires = "committing")]
   pub tag: bool,
 
+  /// Fail if tracked files are still modified after committing (requires
+  /// --commit or --commit-all).
+  #[arg(long, requires = "committing")]
+  pub verify_clean: bool,
+
+  /// GPG-sign the commit and the tag, which is then annotated (requires
+  /// --commit or --commit-all).
+  #[arg(long, requires = "committing")]
+  pub sign: bool,
+
+  /// Push the commit, and the tag with --tag, to the remote (requires --commit
+  /// or --commit-all).
+  #[arg(long, requires = "committing")]
+  pub push: bool,
+
+  /// Remote receiving --push.
+  #[arg(long, requires = "push", value_name = "NAME", default_value = "origin")]
+  pub remote: String,
+
+  /// Create an annotated tag instead of a lightweight one (requires --tag).
+  #[arg(long, short = 'a', requires = "tag")]
+  pub annotate: bool,
+
+  /// Message of the annotated tag, with $VERSION substitution (requires
+  /// --annotate).
+  #[arg(long, requires = "annotate", value_name = "MESSAGE")]
+  pub tag_message: Option<String>,
+
   /// Preview changes without writing files.
   #[arg(long, short = 'd')]
   pub dry_run: bool,
@@ -49,6 +156,10 @@ pub struct UpdateArgs {
   /// Show the computed version without updating files.
   #[arg(long, short = 's')]
   pub show: bool,
+
+  /// Write CAMBI_VERSION, CAMBI_TAG and CAMBI_BUMP to a dot-env file.
+  #[arg(long, value_name = "PATH")]
+  pub write_env: Option<PathBuf>,
 }
 
 #[derive(clap::Args, Debug, Default)]
@@ -62,6 +173,31 @@ pub struct ChangelogArgs {
   #[arg(long, short = 'r')]
   pub rebuild: bool,
 
+  /// Start the pending section after this tag instead of the latest one.
+  #[arg(long, value_name = "TAG", conflicts_with_all = ["rebuild", "remote"])]
+  pub since: Option<String>,
+
+  /// Print the commit range of the pending section to stderr.
+  #[arg(long)]
+  pub print_range: bool,
+
+  /// Show commit bodies beneath their entries.
+  #[arg(long)]
+  pub include_body: bool,
+
+  /// Prefix entries with the bump level of their commit, like "[minor]".
+  #[arg(long)]
+  pub annotate_bump: bool,
+
+  /// Layout of CHANGELOG.md.
+  #[arg(long, value_enum)]
+  pub style: Option<ChangelogStyle>,
+
+  /// Let the provider generate and commit the changelog remotely (gitlab
+  /// only).
+  #[arg(long, conflicts_with_all = ["rebuild", "commit"])]
+  pub remote: bool,
+
   /// Auto-commit if CHANGELOG.md is the only changed file.
   #[arg(long, short = 'o')]
   pub commit: bool,
@@ -70,9 +206,26 @@ pub struct ChangelogArgs {
   #[arg(long, short = 'm', requires = "commit", value_name = "MESSAGE")]
   pub commit_message: Option<String>,
 
+  /// Fail if tracked files are still modified after committing (requires
+  /// --commit).
+  #[arg(long, requires = "commit")]
+  pub verify_clean: bool,
+
+  /// GPG-sign the commit (requires --commit).
+  #[arg(long, requires = "commit")]
+  pub sign: bool,
+
   /// Preview changes without writing files.
   #[arg(long, short = 'd')]
   pub dry_run: bool,
+
+  /// Also print the newly added section to stdout after writing it.
+  #[arg(long, conflicts_with_all = ["dry_run", "rebuild", "remote"])]
+  pub print: bool,
+
+  /// Print the pending section headed "Unreleased" without writing files.
+  #[arg(long, conflicts_with_all = ["target", "rebuild", "remote", "commit", "print"])]
+  pub unreleased: bool,
 }
 
 #[derive(clap::Args, Debug, Default)]
@@ -86,6 +239,11 @@ pub struct ReleaseArgs {
   #[arg(long, short = 'r', conflicts_with = "notes_only")]
   pub rebuild: bool,
 
+  /// Continue an interrupted rebuild, reporting which releases were already
+  /// up to date and which were acted on (requires --rebuild).
+  #[arg(long, alias = "progress", requires = "rebuild")]
+  pub resume: bool,
+
   /// Print only the notes that would be used for the release body.
   #[arg(
     long,
@@ -114,9 +272,75 @@ pub struct ReleaseArgs {
   #[arg(long, short = 'd', conflicts_with = "notes_only")]
   pub dry_run: bool,
 
+  /// Print a JSON summary of the created, updated, skipped and deleted
+  /// releases to stdout (same as --format json).
+  #[arg(long, conflicts_with_all = ["notes_only", "dry_run"])]
+  pub json: bool,
+
   /// Mark the GitHub release as a pre-release (requires positional target).
   #[arg(long, short = 'a', conflicts_with = "notes_only")]
   pub prerelease: bool,
+
+  /// Create or update the releases as drafts, to publish them after review.
+  #[arg(long, conflicts_with = "notes_only")]
+  pub draft: bool,
+
+  /// Use the matching CHANGELOG.md section as the release body, falling back
+  /// to the notes derived from git when it has none.
+  #[arg(long)]
+  pub from_changelog: bool,
+
+  /// Append the contents of this file to every release body.
+  #[arg(long, value_name = "PATH")]
+  pub notes_footer_file: Option<PathBuf>,
+
+  /// Upload this file as an asset of the published release (repeatable).
+  #[arg(
+    long = "asset",
+    value_name = "PATH",
+    conflicts_with = "rebuild",
+    conflicts_with = "notes_only"
+  )]
+  pub assets: Vec<PathBuf>,
+
+  /// Delete the GitHub release for this tag.
+  #[arg(
+    long,
+    value_name = "TAG",
+    conflicts_with = "target",
+    conflicts_with = "rebuild",
+    conflicts_with = "notes_only"
+  )]
+  pub delete: Option<String>,
+
+  /// Also delete the tag itself, on GitHub and locally (requires --delete).
+  #[arg(long, requires = "delete")]
+  pub delete_tag: bool,
+
+  /// Skip the confirmation prompt of destructive operations.
+  #[arg(long, short = 'y')]
+  pub yes: bool,
+
+  /// Print the commit range of the latest release notes to stderr.
+  #[arg(long)]
+  pub print_range: bool,
+}
+
+#[derive(clap::Args, Debug, Default)]
+pub struct TagsArgs {
+  /// List the local tags not matching the tag pattern instead.
+  #[arg(long)]
+  pub prune: bool,
+
+  /// Delete the tags listed by --prune (requires --prune).
+  #[arg(long, short = 'y', requires = "prune")]
+  pub yes: bool,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct CompletionsArgs {
+  /// Shell to generate the completion script for.
+  pub shell: Shell,
 }
 
 #[derive(Subcommand, Debug)]
@@ -136,6 +360,10 @@ pub enum Command {
   /// Publish releases on GitHub from git history derived by tags.
   #[command(alias = "r")]
   Release(ReleaseArgs),
+  /// List the local tags matching the tag pattern, or prune the others.
+  Tags(TagsArgs),
+  /// Print the shell completion script.
+  Completions(CompletionsArgs),
 }
 
 impl Command {
@@ -146,14 +374,21 @@ impl Command {
       Self::Update(_) => "update",
       Self::Changelog(_) => "changelog",
       Self::Release(_) => "release",
+      Self::Tags(_) => "tags",
+      Self::Completions(_) => "completions",
     }
   }
 }
 
+/// Writes the completion script of the whole CLI for `shell`.
+pub fn write_completions(shell: Shell, out: &mut dyn Write) {
+  clap_complete::generate(shell, &mut Args::command(), env!("CARGO_PKG_NAME"), out);
+}
+
 #[derive(Parser, Debug)]
 #[command(version, about)]
 pub struct Args {
-  /// Optional explicit config file path.
+  /// Optional explicit config file path ("none" ignores every config file).
   #[arg(long, short = 'c', global = true)]
   pub config: Option<PathBuf>,
 
@@ -161,10 +396,81 @@ pub struct Args {
   #[arg(long, short = 'p', global = true)]
   pub tag_pattern: Option<String>,
 
+  /// Match and create tags made of this literal prefix and a semver (like
+  /// release- for release-1.2.3).
+  #[arg(long, global = true, value_name = "STRING", conflicts_with = "tag_pattern")]
+  pub prefix: Option<String>,
+
+  /// Version of the first release when no tag matches the tag pattern (like
+  /// 1.0.0).
+  #[arg(long, global = true, value_name = "SEMVER")]
+  pub initial_version: Option<String>,
+
+  /// Hosting service used for remote operations.
+  #[arg(long, global = true, value_enum)]
+  pub provider: Option<Provider>,
+
+  /// Override the provider API base URL (e.g. a GitHub Enterprise instance).
+  #[arg(long, global = true, value_name = "URL")]
+  pub api_base: Option<String>,
+
+  /// Web host of the repository (e.g. a self-hosted GitLab or Gitea instance).
+  #[arg(long, global = true, value_name = "HOST")]
+  pub web_host: Option<String>,
+
+  /// Read at most this many commits from history.
+  #[arg(long, global = true, value_name = "N")]
+  pub max_commits: Option<usize>,
+
+  /// Only consider commits touching this path prefix (repeatable; any match
+  /// counts).
+  #[arg(long = "path", global = true, value_name = "PATH")]
+  pub paths: Vec<String>,
+
+  /// Only include commits by this author (name or email) in changelogs and
+  /// release notes (repeatable).
+  #[arg(long = "author", global = true, value_name = "AUTHOR")]
+  pub authors: Vec<String>,
+
+  /// Leave out commits by this author (name or email) from changelogs and
+  /// release notes (repeatable).
+  #[arg(long = "exclude-author", global = true, value_name = "AUTHOR")]
+  pub exclude_authors: Vec<String>,
+
+  /// List merged pull requests by the title in their merge commit body.
+  #[arg(long, global = true)]
+  pub include_merge_prs: bool,
+
+  /// Keep going after failures in batch operations and report them all at the
+  /// end.
+  #[arg(long, global = true)]
+  pub continue_on_error: bool,
+
+  /// Fetch tags from the origin remote before reading them, retrying transient
+  /// failures (see --max-retries).
+  #[arg(long, global = true)]
+  pub fetch: bool,
+
+  /// Retry transient provider API failures up to this many times.
+  #[arg(long, global = true, value_name = "N")]
+  pub max_retries: Option<u32>,
+
+  /// Output format of command results.
+  #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+  pub format: OutputFormat,
+
+  /// Fail any provider API call instead of sending it.
+  #[arg(long, global = true)]
+  pub offline: bool,
+
   /// Enable verbose output.
   #[arg(long, short, global = true)]
   pub verbose: bool,
 
+  /// Print the JSON Schema of the config file and exit.
+  #[arg(long, exclusive = true)]
+  pub config_schema: bool,
+
   #[command(subcommand)]
   pub command: Command,
 }
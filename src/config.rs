@@ -4,10 +4,17 @@ use std::{
   path::{Path, PathBuf},
 };
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow};
+use regex::Regex;
+use schemars::JsonSchema;
 use serde::Deserialize;
 
+use crate::conventional::BumpLevel;
+
 const DEFAULT_TAG_PATTERN: &str = r"^v\d+\.\d+\.\d+$";
+const DEFAULT_MAX_RETRIES: u32 = 3;
+/// Stays below the 125000 characters GitHub accepts in release bodies.
+const DEFAULT_RELEASE_MAX_BODY_BYTES: usize = 125_000;
 const DEFAULT_IGNORE_PATTERNS: [&str; 7] = [
   r"^.+: fixup$",
   r"^.+: wip$",
@@ -18,42 +25,537 @@ const DEFAULT_IGNORE_PATTERNS: [&str; 7] = [
   r"^Merge .+$",
 ];
 
-#[derive(Debug, Clone, Deserialize, Default)]
-pub struct FileConfig {
+/// Hosting service used for remote operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+  #[default]
+  Github,
+  Gitlab,
+  Gitea,
+}
+
+impl Provider {
+  /// API base URL of the public hosting service.
+  pub fn default_api_base(self) -> &'static str {
+    match self {
+      Self::Github => "https://api.github.com",
+      Self::Gitlab => "https://gitlab.com/api/v4",
+      Self::Gitea => "https://gitea.com/api/v1",
+    }
+  }
+
+  /// Web host of the public hosting service.
+  pub fn default_web_host(self) -> &'static str {
+    match self {
+      Self::Github => "github.com",
+      Self::Gitlab => "gitlab.com",
+      Self::Gitea => "gitea.com",
+    }
+  }
+
+  /// Web URL of `path` within a repository on `host`. GitLab serves
+  /// repository pages below `/-/`.
+  fn web_url(self, host: &str, owner: &str, repo: &str, path: &str) -> String {
+    match self {
+      Self::Github | Self::Gitea => format!("https://{host}/{owner}/{repo}/{path}"),
+      Self::Gitlab => format!("https://{host}/{owner}/{repo}/-/{path}"),
+    }
+  }
+
+  /// Web URL of a commit.
+  pub fn commit_url(self, host: &str, owner: &str, repo: &str, id: &str) -> String {
+    self.web_url(host, owner, repo, &format!("commit/{id}"))
+  }
+
+  /// Web URL of the issues of a repository.
+  pub fn issues_url(self, host: &str, owner: &str, repo: &str) -> String {
+    self.web_url(host, owner, repo, "issues")
+  }
+
+  /// Web URL comparing two refs of a repository.
+  pub fn compare_url(self, host: &str, owner: &str, repo: &str, from: &str, to: &str) -> String {
+    self.web_url(host, owner, repo, &format!("compare/{from}...{to}"))
+  }
+
+  /// Web URL of a tag of a repository.
+  pub fn tag_url(self, host: &str, owner: &str, repo: &str, tag: &str) -> String {
+    match self {
+      Self::Github | Self::Gitea => self.web_url(host, owner, repo, &format!("releases/tag/{tag}")),
+      Self::Gitlab => self.web_url(host, owner, repo, &format!("tags/{tag}")),
+    }
+  }
+
+  /// Web URL of a file at a ref of a repository.
+  pub fn file_url(self, host: &str, owner: &str, repo: &str, reference: &str, path: &str) -> String {
+    match self {
+      Self::Github | Self::Gitlab => self.web_url(host, owner, repo, &format!("blob/{reference}/{path}")),
+      Self::Gitea => self.web_url(host, owner, repo, &format!("src/{reference}/{path}")),
+    }
+  }
+
+  /// Whether releases can be created as drafts, to publish them after review.
+  pub fn supports_drafts(self) -> bool {
+    matches!(self, Self::Github)
+  }
+
+  /// Display name of the hosting service.
+  pub fn host(self) -> &'static str {
+    match self {
+      Self::Github => "GitHub",
+      Self::Gitlab => "GitLab",
+      Self::Gitea => "Gitea",
+    }
+  }
+}
+
+impl std::str::FromStr for Provider {
+  type Err = String;
+
+  fn from_str(raw: &str) -> Result<Self, Self::Err> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+      "github" => Ok(Self::Github),
+      "gitlab" => Ok(Self::Gitlab),
+      "gitea" => Ok(Self::Gitea),
+      _ => Err(format!("Invalid provider '{raw}' (expected github, gitlab or gitea)")),
+    }
+  }
+}
+
+/// Output format of command results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+  #[default]
+  Text,
+  Json,
+}
+
+impl OutputFormat {
+  /// Picks the plain `text` or the `json` rendering of a command result.
+  pub fn render(self, text: String, json: serde_json::Value) -> String {
+    match self {
+      Self::Text => text,
+      Self::Json => json.to_string(),
+    }
+  }
+}
+
+/// How the `+build` number of a Flutter `pubspec.yaml` version changes on
+/// update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FlutterBuild {
+  /// Increment the build number on every update.
+  #[default]
+  Increment,
+  /// Restart the build number from 1 when the version changes.
+  Reset,
+  /// Leave the build number untouched.
+  Keep,
+}
+
+/// Which files `update --commit` stages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommitScope {
+  /// Only the files written by the update.
+  VersionFile,
+  /// The written files and every other modified tracked file.
+  #[default]
+  All,
+}
+
+/// Which date heads the historical sections of a rebuilt changelog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangelogDateSource {
+  /// Date of the first releasable commit of the section.
+  #[default]
+  Commit,
+  /// Date the tag was created.
+  Tag,
+}
+
+/// Layout of CHANGELOG.md.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangelogStyle {
+  /// `### <date> / <version>` sections.
+  #[default]
+  Cambi,
+  /// keepachangelog.com layout: an `## [Unreleased]` section,
+  /// `## [<version>] - <date>` sections and compare link definitions.
+  Keepachangelog,
+}
+
+/// Keys of `cambi.yml` that apply to every command, also allowed in the
+/// per-command sections.
+#[derive(Debug, Clone, Deserialize, Default, JsonSchema)]
+pub struct CommandConfig {
+  /// Hosting service used for remote operations (github, gitlab or gitea).
+  pub provider: Option<Provider>,
+  /// GitHub token used for releases.
   pub token: Option<String>,
+  /// API base URL, for self-hosted GitHub Enterprise, GitLab or Gitea
+  /// instances.
+  pub api_base: Option<String>,
+  /// Web host of the repository, for self-hosted instances.
+  pub web_host: Option<String>,
+  /// GitHub repository owner.
   pub owner: Option<String>,
+  /// GitHub repository name.
   pub repo: Option<String>,
+  /// Repository URL used when owner and repo are not set.
+  pub repository_url: Option<String>,
+  /// Regex matching release tags.
   pub tag_pattern: Option<String>,
+  /// Template of created tag names ({version}), overriding the name derived
+  /// from tag_pattern.
+  pub tag_format: Option<String>,
+  /// Version of the first release, used when no tag matches tag_pattern.
+  pub initial_version: Option<String>,
+  /// Template for CHANGELOG.md sections ($DATE, $VERSION, $COMMITS).
   pub changelog_template: Option<String>,
+  /// Heading of changelog sections ($DATE, $VERSION).
+  pub heading_format: Option<String>,
+  /// Regex locating existing changelog versions, with the version as its
+  /// first capture group.
+  pub version_pattern: Option<String>,
+  /// Layout of CHANGELOG.md (cambi or keepachangelog).
+  pub changelog_style: Option<ChangelogStyle>,
+  /// Template for annotated tag messages ($VERSION, $NOTES).
+  pub tag_message_template: Option<String>,
+  /// Regexes of commit subjects to ignore.
   pub ignore_patterns: Option<Vec<String>>,
+  /// Tags used only as version anchors, without their own release or
+  /// changelog section.
+  pub anchor_tags: Option<Vec<String>>,
+  /// Dotted path of the version key in JSON and YAML files.
+  pub version_json_path: Option<String>,
+  /// Dotted path of the version key in TOML files passed with --file.
+  pub toml_version_path: Option<String>,
+  /// Skip tags not reachable from HEAD when building releases.
+  pub reachable_only: Option<bool>,
+  /// Drop conventional-commit scopes from displayed entries.
+  pub changelog_strip_scope: Option<bool>,
+  /// Drop the whole conventional-commit header from changelog entries.
+  pub strip_prefixes: Option<bool>,
+  /// Show commit bodies beneath changelog entries.
+  pub changelog_include_body: Option<bool>,
+  /// Prefix changelog entries with the bump level of their commit.
+  pub changelog_annotate_bump: Option<bool>,
+  /// Credit commit authors after changelog entries.
+  pub show_authors: Option<bool>,
+  /// Link changelog entries to their commits.
+  pub link_commits: Option<bool>,
+  /// Link `#123` and `GH-123` references in changelog entries to issues.
+  pub link_issues: Option<bool>,
+  /// Date of the historical changelog sections (commit or tag).
+  pub changelog_date_source: Option<ChangelogDateSource>,
+  /// Regex of release titles published as prereleases.
+  pub prerelease_title_pattern: Option<String>,
+  /// Use the tag name as is as release title, keeping its `v` prefix.
+  pub release_title_keep_prefix: Option<bool>,
+  /// Longest release body, in bytes, before it is replaced by a link to the
+  /// full changelog.
+  pub release_max_body_bytes: Option<usize>,
+  /// Group release notes under a heading per commit type.
+  pub release_grouped: Option<bool>,
+  /// Headings of the release note groups by commit type (like `feat:
+  /// Features`), overriding the defaults.
+  pub release_group_labels: Option<HashMap<String, String>>,
+  /// Commit types in the order of their grouped release note sections (like
+  /// `[feat, fix, perf, other]`); types not listed go under `other`.
+  pub changelog_type_order: Option<Vec<String>>,
+  /// Bump level of commit types (like `perf: minor`), overriding the defaults.
+  /// Breaking changes are always major.
+  pub bump_map: Option<HashMap<String, BumpLevel>>,
+  /// Scopes whose `!` marks a breaking change; when set, a `!` on any other
+  /// scope is not major. The `BREAKING CHANGE:` footer always is.
+  pub breaking_scopes: Option<Vec<String>>,
+  /// Update debian/changelog instead of the detected version file.
+  pub debian_changelog: Option<bool>,
+  /// Update dependency pins on the bumped crate in other workspace members.
+  pub sync_workspace_deps: Option<bool>,
+  /// How the Flutter build number changes (increment, reset or keep).
+  pub flutter_build: Option<FlutterBuild>,
+  /// Files staged by `update --commit`: `version-file` or `all` (default).
+  pub commit_scope: Option<CommitScope>,
+  /// GPG-sign the commits and tags created by cambi with user.signingkey.
+  pub sign: Option<bool>,
+  /// Maximum number of commits read from history.
+  pub max_commits: Option<usize>,
+  /// Only consider commits touching at least one of these path prefixes.
+  pub paths: Option<Vec<String>>,
+  /// Authors (name or email) whose commits are left out of changelogs and
+  /// release notes.
+  pub exclude_authors: Option<Vec<String>>,
+  /// Commit types left out of changelogs and release notes (default: chore),
+  /// unless breaking.
+  pub excluded_types: Option<Vec<String>>,
+  /// List merged pull requests by title, taken from their merge commits.
+  pub include_merge_prs: Option<bool>,
+  /// Keep going after failures in batch operations and report them all at the
+  /// end.
+  pub continue_on_error: Option<bool>,
+  /// Fetch tags from the origin remote before reading them.
+  pub fetch: Option<bool>,
+}
+
+/// Contents of `cambi.yml`: the keys of every command, with per-command
+/// sections applied on top of them.
+#[derive(Debug, Clone, Deserialize, Default, JsonSchema)]
+pub struct FileConfig {
+  #[serde(flatten)]
+  pub common: CommandConfig,
+  /// Overrides for the `version` command.
+  pub version: Option<CommandConfig>,
+  /// Overrides for the `semver` command.
+  pub semver: Option<CommandConfig>,
+  /// Overrides for the `update` command.
+  pub update: Option<CommandConfig>,
+  /// Overrides for the `changelog` command.
+  pub changelog: Option<CommandConfig>,
+  /// Overrides for the `release` command.
+  pub release: Option<CommandConfig>,
+}
+
+/// Returns the JSON Schema of `cambi.yml`.
+pub fn config_schema() -> String {
+  serde_json::to_string_pretty(&schemars::schema_for!(FileConfig)).expect("config schema must serialize")
+}
+
+fn overlay_section(base: Option<CommandConfig>, overlay: Option<CommandConfig>) -> Option<CommandConfig> {
+  match (base, overlay) {
+    (Some(base), Some(overlay)) => Some(base.overlay(overlay)),
+    (base, overlay) => overlay.or(base),
+  }
+}
+
+impl CommandConfig {
+  /// Returns a copy of this config where every key set in `overlay` wins.
+  pub fn overlay(self, overlay: CommandConfig) -> CommandConfig {
+    CommandConfig {
+      provider: overlay.provider.or(self.provider),
+      api_base: overlay.api_base.or(self.api_base),
+      web_host: overlay.web_host.or(self.web_host),
+      token: overlay.token.or(self.token),
+      owner: overlay.owner.or(self.owner),
+      repo: overlay.repo.or(self.repo),
+      repository_url: overlay.repository_url.or(self.repository_url),
+      tag_pattern: overlay.tag_pattern.or(self.tag_pattern),
+      tag_format: overlay.tag_format.or(self.tag_format),
+      initial_version: overlay.initial_version.or(self.initial_version),
+      changelog_template: overlay.changelog_template.or(self.changelog_template),
+      heading_format: overlay.heading_format.or(self.heading_format),
+      changelog_style: overlay.changelog_style.or(self.changelog_style),
+      version_pattern: overlay.version_pattern.or(self.version_pattern),
+      tag_message_template: overlay.tag_message_template.or(self.tag_message_template),
+      ignore_patterns: overlay.ignore_patterns.or(self.ignore_patterns),
+      anchor_tags: overlay.anchor_tags.or(self.anchor_tags),
+      version_json_path: overlay.version_json_path.or(self.version_json_path),
+      toml_version_path: overlay.toml_version_path.or(self.toml_version_path),
+      reachable_only: overlay.reachable_only.or(self.reachable_only),
+      changelog_strip_scope: overlay.changelog_strip_scope.or(self.changelog_strip_scope),
+      strip_prefixes: overlay.strip_prefixes.or(self.strip_prefixes),
+      changelog_include_body: overlay.changelog_include_body.or(self.changelog_include_body),
+      changelog_annotate_bump: overlay.changelog_annotate_bump.or(self.changelog_annotate_bump),
+      show_authors: overlay.show_authors.or(self.show_authors),
+      link_commits: overlay.link_commits.or(self.link_commits),
+      link_issues: overlay.link_issues.or(self.link_issues),
+      changelog_date_source: overlay.changelog_date_source.or(self.changelog_date_source),
+      prerelease_title_pattern: overlay.prerelease_title_pattern.or(self.prerelease_title_pattern),
+      release_title_keep_prefix: overlay.release_title_keep_prefix.or(self.release_title_keep_prefix),
+      release_max_body_bytes: overlay.release_max_body_bytes.or(self.release_max_body_bytes),
+      release_grouped: overlay.release_grouped.or(self.release_grouped),
+      release_group_labels: overlay.release_group_labels.or(self.release_group_labels),
+      changelog_type_order: overlay.changelog_type_order.or(self.changelog_type_order),
+      bump_map: overlay.bump_map.or(self.bump_map),
+      breaking_scopes: overlay.breaking_scopes.or(self.breaking_scopes),
+      debian_changelog: overlay.debian_changelog.or(self.debian_changelog),
+      sync_workspace_deps: overlay.sync_workspace_deps.or(self.sync_workspace_deps),
+      flutter_build: overlay.flutter_build.or(self.flutter_build),
+      commit_scope: overlay.commit_scope.or(self.commit_scope),
+      sign: overlay.sign.or(self.sign),
+      max_commits: overlay.max_commits.or(self.max_commits),
+      paths: overlay.paths.or(self.paths),
+      exclude_authors: overlay.exclude_authors.or(self.exclude_authors),
+      excluded_types: overlay.excluded_types.or(self.excluded_types),
+      include_merge_prs: overlay.include_merge_prs.or(self.include_merge_prs),
+      continue_on_error: overlay.continue_on_error.or(self.continue_on_error),
+      fetch: overlay.fetch.or(self.fetch),
+    }
+  }
+}
+
+impl FileConfig {
+  /// Returns a copy of this config where every key set in `overlay` wins,
+  /// within the per-command sections too.
+  pub fn overlay(self, overlay: FileConfig) -> FileConfig {
+    FileConfig {
+      common: self.common.overlay(overlay.common),
+      version: overlay_section(self.version, overlay.version),
+      semver: overlay_section(self.semver, overlay.semver),
+      update: overlay_section(self.update, overlay.update),
+      changelog: overlay_section(self.changelog, overlay.changelog),
+      release: overlay_section(self.release, overlay.release),
+    }
+  }
+
+  /// Resolves the config for a single command by applying its section, if
+  /// any, on top of the top-level keys.
+  pub fn for_command(mut self, command: &str) -> CommandConfig {
+    let section = match command {
+      "version" => self.version.take(),
+      "semver" => self.semver.take(),
+      "update" => self.update.take(),
+      "changelog" => self.changelog.take(),
+      "release" => self.release.take(),
+      _ => None,
+    };
+
+    match section {
+      Some(section) => self.common.overlay(section),
+      None => self.common,
+    }
+  }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct ConfigOverrides {
+  pub provider: Option<Provider>,
+  pub api_base: Option<String>,
+  pub web_host: Option<String>,
   pub token: Option<String>,
   pub owner: Option<String>,
   pub repo: Option<String>,
   pub tag_pattern: Option<String>,
+  /// Literal tag prefix, expanding into both the tag pattern and format.
+  pub tag_prefix: Option<String>,
+  pub initial_version: Option<String>,
+  pub max_commits: Option<usize>,
+  pub paths: Option<Vec<String>>,
+  pub authors: Option<Vec<String>>,
+  pub exclude_authors: Option<Vec<String>>,
+  pub include_merge_prs: Option<bool>,
+  pub continue_on_error: Option<bool>,
+  pub fetch: Option<bool>,
+  pub max_retries: Option<u32>,
+  pub format: Option<OutputFormat>,
+  pub offline: Option<bool>,
   pub verbose: Option<bool>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct EffectiveConfig {
+  pub provider: Provider,
+  pub api_base: String,
+  /// Web host of the repository; the public host of the provider when unset.
+  pub web_host: Option<String>,
   pub token: Option<String>,
   pub owner: Option<String>,
   pub repo: Option<String>,
+  pub repository_url: Option<String>,
   pub tag_pattern: String,
+  pub tag_format: Option<String>,
+  pub initial_version: Option<String>,
   pub changelog_template: Option<String>,
+  pub heading_format: Option<String>,
+  pub version_pattern: Option<String>,
+  pub changelog_style: ChangelogStyle,
+  pub tag_message_template: Option<String>,
   pub ignore_patterns: Vec<String>,
+  pub anchor_tags: Vec<String>,
+  pub version_json_path: Option<String>,
+  pub toml_version_path: Option<String>,
+  pub reachable_only: bool,
+  pub changelog_strip_scope: bool,
+  pub strip_prefixes: bool,
+  pub changelog_include_body: bool,
+  pub changelog_annotate_bump: bool,
+  pub show_authors: bool,
+  pub link_commits: bool,
+  pub link_issues: bool,
+  pub changelog_date_source: ChangelogDateSource,
+  pub prerelease_title_pattern: Option<String>,
+  pub release_title_keep_prefix: bool,
+  pub release_max_body_bytes: usize,
+  pub release_grouped: bool,
+  pub release_group_labels: HashMap<String, String>,
+  pub changelog_type_order: Vec<String>,
+  pub bump_map: HashMap<String, BumpLevel>,
+  pub breaking_scopes: Option<Vec<String>>,
+  pub debian_changelog: bool,
+  pub sync_workspace_deps: bool,
+  pub flutter_build: FlutterBuild,
+  pub commit_scope: CommitScope,
+  pub sign: bool,
+  pub current_version: Option<String>,
+  pub max_commits: Option<usize>,
+  pub paths: Vec<String>,
+  pub authors: Vec<String>,
+  pub exclude_authors: Vec<String>,
+  /// Unset to exclude the default types.
+  pub excluded_types: Option<Vec<String>>,
+  pub include_merge_prs: bool,
+  pub continue_on_error: bool,
+  pub fetch: bool,
+  pub max_retries: u32,
+  pub format: OutputFormat,
+  pub offline: bool,
   pub verbose: bool,
 }
 
 impl EffectiveConfig {
-  pub fn from_sources(config: Option<FileConfig>, env: &HashMap<String, String>, flags: ConfigOverrides) -> Self {
-    let config = config.unwrap_or_default();
+  pub fn from_sources(
+    config: Option<FileConfig>,
+    command: &str,
+    env: &HashMap<String, String>,
+    flags: ConfigOverrides,
+  ) -> Result<Self> {
+    let config = config.unwrap_or_default().for_command(command);
 
     let env_var = |key: &str| env.get(key).cloned();
 
+    let provider = match flags.provider {
+      Some(provider) => provider,
+      None => {
+        env_var("CAMBI_PROVIDER")
+          .map(|raw| raw.parse::<Provider>().map_err(|error| anyhow!(error)))
+          .transpose()?
+          .or(config.provider)
+          .unwrap_or_default()
+      }
+    };
+
+    // The provider specific variables predate api_base and are kept as a
+    // fallback.
+    let legacy_api_base = match provider {
+      Provider::Github => Some("CAMBI_GITHUB_API_BASE"),
+      Provider::Gitlab => Some("CAMBI_GITLAB_API_BASE"),
+      Provider::Gitea => None,
+    };
+    let api_base = flags
+      .api_base
+      .or_else(|| env_var("CAMBI_API_BASE"))
+      .or(config.api_base)
+      .or_else(|| legacy_api_base.and_then(env_var))
+      .unwrap_or_else(|| provider.default_api_base().to_string());
+
+    // URLs are built from the bare host, so a pasted URL is reduced to it.
+    let web_host = flags
+      .web_host
+      .or_else(|| env_var("CAMBI_WEB_HOST"))
+      .or(config.web_host)
+      .map(|host| {
+        let host = host.trim();
+        let host = host
+          .strip_prefix("https://")
+          .or_else(|| host.strip_prefix("http://"))
+          .unwrap_or(host);
+        host.trim_end_matches('/').to_string()
+      });
+
     let token = flags
       .token
       .or_else(|| env_var("CAMBI_TOKEN"))
@@ -66,12 +568,28 @@ impl EffectiveConfig {
 
     let tag_pattern = flags
       .tag_pattern
+      .or_else(|| {
+        flags
+          .tag_prefix
+          .as_deref()
+          .map(|prefix| format!(r"^{}\d+\.\d+\.\d+$", regex::escape(prefix)))
+      })
       .or_else(|| env_var("CAMBI_TAG_PATTERN"))
       .or(config.tag_pattern)
       .unwrap_or_else(|| DEFAULT_TAG_PATTERN.to_string());
 
     let changelog_template = env_var("CAMBI_CHANGELOG_TEMPLATE").or(config.changelog_template);
 
+    // A known prefix makes the tag name exact, without deriving it from the
+    // pattern.
+    let tag_format = flags
+      .tag_prefix
+      .map(|prefix| format!("{prefix}{{version}}"))
+      .or_else(|| env_var("CAMBI_TAG_FORMAT"))
+      .or(config.tag_format);
+
+    let tag_message_template = env_var("CAMBI_TAG_MESSAGE_TEMPLATE").or(config.tag_message_template);
+
     let ignore_patterns = env_var("CAMBI_IGNORE_PATTERNS")
       .map(|raw| {
         raw
@@ -84,20 +602,114 @@ impl EffectiveConfig {
       .or(config.ignore_patterns)
       .unwrap_or_else(|| DEFAULT_IGNORE_PATTERNS.iter().map(ToString::to_string).collect());
 
+    let max_commits = match flags.max_commits {
+      Some(max_commits) => Some(max_commits),
+      None => {
+        env_var("CAMBI_MAX_COMMITS")
+          .map(|raw| {
+            raw
+              .trim()
+              .parse::<usize>()
+              .context(format!("Invalid CAMBI_MAX_COMMITS '{raw}' (expected a number)"))
+          })
+          .transpose()?
+          .or(config.max_commits)
+      }
+    };
+
     let verbose = flags
       .verbose
       .or_else(|| env_var("CAMBI_VERBOSE").map(|v| matches!(v.as_str(), "1" | "true" | "yes")))
       .unwrap_or(false);
 
-    Self {
+    Ok(Self {
+      provider,
+      api_base,
+      web_host,
       token,
       owner,
       repo,
+      repository_url: config.repository_url,
       tag_pattern,
+      tag_format,
+      initial_version: flags.initial_version.or(config.initial_version),
       changelog_template,
+      heading_format: config.heading_format,
+      version_pattern: config.version_pattern,
+      changelog_style: config.changelog_style.unwrap_or_default(),
+      tag_message_template,
       ignore_patterns,
+      anchor_tags: config.anchor_tags.unwrap_or_default(),
+      version_json_path: config.version_json_path,
+      toml_version_path: config.toml_version_path,
+      reachable_only: config.reachable_only.unwrap_or(false),
+      changelog_strip_scope: config.changelog_strip_scope.unwrap_or(false),
+      strip_prefixes: env_var("CAMBI_STRIP_PREFIXES")
+        .map(|v| matches!(v.as_str(), "1" | "true" | "yes"))
+        .or(config.strip_prefixes)
+        .unwrap_or(false),
+      changelog_include_body: config.changelog_include_body.unwrap_or(false),
+      changelog_annotate_bump: config.changelog_annotate_bump.unwrap_or(false),
+      show_authors: config.show_authors.unwrap_or(false),
+      link_commits: config.link_commits.unwrap_or(false),
+      link_issues: config.link_issues.unwrap_or(false),
+      changelog_date_source: config.changelog_date_source.unwrap_or_default(),
+      prerelease_title_pattern: config.prerelease_title_pattern,
+      release_title_keep_prefix: config.release_title_keep_prefix.unwrap_or(false),
+      release_max_body_bytes: config.release_max_body_bytes.unwrap_or(DEFAULT_RELEASE_MAX_BODY_BYTES),
+      release_grouped: config.release_grouped.unwrap_or(false),
+      release_group_labels: config.release_group_labels.unwrap_or_default(),
+      changelog_type_order: config.changelog_type_order.unwrap_or_default(),
+      bump_map: config.bump_map.unwrap_or_default(),
+      breaking_scopes: config.breaking_scopes,
+      debian_changelog: config.debian_changelog.unwrap_or(false),
+      sync_workspace_deps: config.sync_workspace_deps.unwrap_or(false),
+      flutter_build: config.flutter_build.unwrap_or_default(),
+      commit_scope: config.commit_scope.unwrap_or_default(),
+      sign: config.sign.unwrap_or(false),
+      current_version: env_var("CAMBI_CURRENT_VERSION"),
+      max_commits,
+      paths: flags.paths.or(config.paths).unwrap_or_default(),
+      authors: flags.authors.unwrap_or_default(),
+      exclude_authors: flags.exclude_authors.or(config.exclude_authors).unwrap_or_default(),
+      excluded_types: env_var("CAMBI_EXCLUDED_TYPES")
+        .map(|raw| {
+          raw
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(ToOwned::to_owned)
+            .collect()
+        })
+        .or(config.excluded_types),
+      include_merge_prs: flags.include_merge_prs.or(config.include_merge_prs).unwrap_or(false),
+      continue_on_error: flags.continue_on_error.or(config.continue_on_error).unwrap_or(false),
+      fetch: flags.fetch.or(config.fetch).unwrap_or(false),
+      max_retries: flags.max_retries.unwrap_or(DEFAULT_MAX_RETRIES),
+      format: flags.format.unwrap_or_default(),
+      offline: flags.offline.unwrap_or(false),
       verbose,
+    })
+  }
+
+  /// Web host of the repository: the configured one, or the public host of
+  /// the provider.
+  pub fn web_host(&self) -> &str {
+    self.web_host.as_deref().unwrap_or(self.provider.default_web_host())
+  }
+
+  /// Returns the API base URL without trailing slashes, failing when it is not
+  /// an http(s) URL.
+  pub fn api_base_url(&self) -> Result<String> {
+    let re = Regex::new(r"^https?://[^\s/]+(/\S*)?$").expect("api base regex must compile");
+    if !re.is_match(&self.api_base) {
+      return Err(anyhow!(
+        "Invalid API base URL '{}' (expected an http:// or https:// URL)",
+        self.api_base
+      ));
     }
+
+    Ok(self.api_base.trim_end_matches('/').to_string())
   }
 }
 
@@ -107,7 +719,13 @@ fn read_config(path: &Path) -> Result<FileConfig> {
     .with_context(|| format!("Invalid YAML in config file: {}", path.display()))
 }
 
+/// Loads the config file, from the override path or by merging the global
+/// and local `cambi.yml`; the `none` override skips config files entirely.
 pub fn load_file(config_path_override: Option<&Path>) -> Result<Option<FileConfig>> {
+  if config_path_override == Some(Path::new("none")) {
+    return Ok(None);
+  }
+
   if let Some(path) = config_path_override {
     return read_config(path).map(Some);
   }
@@ -128,16 +746,7 @@ pub fn load_file(config_path_override: Option<&Path>) -> Result<Option<FileConfi
 
   if local.exists() {
     let overlay = read_config(&local)?;
-    let mut merged = result.unwrap_or_default();
-
-    merged.token = overlay.token.or(merged.token);
-    merged.owner = overlay.owner.or(merged.owner);
-    merged.repo = overlay.repo.or(merged.repo);
-    merged.tag_pattern = overlay.tag_pattern.or(merged.tag_pattern);
-    merged.changelog_template = overlay.changelog_template.or(merged.changelog_template);
-    merged.ignore_patterns = overlay.ignore_patterns.or(merged.ignore_patterns);
-
-    result = Some(merged);
+    result = Some(result.unwrap_or_default().overlay(overlay));
   }
 
   Ok(result)
@@ -1,4 +1,12 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{config::EffectiveConfig, git::GitCommit};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum BumpLevel {
   Patch,
   Minor,
@@ -15,7 +23,132 @@ impl BumpLevel {
   }
 }
 
-pub fn infer_bump(subject: &str, body: &str) -> BumpLevel {
+impl std::str::FromStr for BumpLevel {
+  type Err = String;
+
+  fn from_str(raw: &str) -> Result<Self, Self::Err> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+      "patch" => Ok(Self::Patch),
+      "minor" => Ok(Self::Minor),
+      "major" => Ok(Self::Major),
+      _ => Err(format!("Invalid bump level '{raw}' (expected patch, minor or major)")),
+    }
+  }
+}
+
+/// Maps `semver:major|minor|patch` labels to the highest bump they request.
+pub fn bump_from_labels(labels: &[String]) -> Option<BumpLevel> {
+  labels
+    .iter()
+    .filter_map(|label| {
+      match label.trim().to_ascii_lowercase().as_str() {
+        "semver:major" => Some(BumpLevel::Major),
+        "semver:minor" => Some(BumpLevel::Minor),
+        "semver:patch" => Some(BumpLevel::Patch),
+        _ => None,
+      }
+    })
+    .max()
+}
+
+/// Removes the `(scope)` from a conventional header while keeping the type,
+/// the breaking `!` and the description: `feat(api)!: add` becomes
+/// `feat!: add`.
+pub fn strip_scope(subject: &str) -> String {
+  let Some((header, description)) = subject.split_once(": ") else {
+    return subject.to_string();
+  };
+
+  let (header, breaking) = match header.strip_suffix('!') {
+    Some(header) => (header, "!"),
+    None => (header, ""),
+  };
+
+  match header.split_once('(') {
+    Some((commit_type, scope))
+      if !commit_type.is_empty() && commit_type.chars().all(|c| c.is_ascii_alphanumeric()) && scope.ends_with(')') =>
+    {
+      format!("{commit_type}{breaking}: {description}")
+    }
+    _ => subject.to_string(),
+  }
+}
+
+/// Removes the whole conventional header, keeping only the description:
+/// `feat(api): add` becomes `add`, and `feat!: redesign` becomes
+/// `redesign (breaking)`.
+pub fn strip_prefix(subject: &str) -> String {
+  let Some((header, description)) = subject.split_once(": ") else {
+    return subject.to_string();
+  };
+
+  let (header, breaking) = match header.strip_suffix('!') {
+    Some(header) => (header, true),
+    None => (header, false),
+  };
+
+  let commit_type = match header.split_once('(') {
+    Some((commit_type, scope)) if scope.ends_with(')') => commit_type,
+    Some(_) => return subject.to_string(),
+    None => header,
+  };
+
+  if commit_type.is_empty() || !commit_type.chars().all(|c| c.is_ascii_alphanumeric()) {
+    return subject.to_string();
+  }
+
+  if breaking {
+    format!("{description} (breaking)")
+  } else {
+    description.to_string()
+  }
+}
+
+/// Returns the type of a conventional header, like `feat` for
+/// `feat(api)!: add`.
+pub fn commit_type(subject: &str) -> Option<&str> {
+  let (header, _) = subject.split_once(": ")?;
+  let header = header.strip_suffix('!').unwrap_or(header);
+  Some(header.split_once('(').map_or(header, |(kind, _)| kind))
+}
+
+/// Infers the bump of a commit with the configured `bump_map` and
+/// `breaking_scopes`.
+pub fn infer_commit_bump(commit: &GitCommit, config: &EffectiveConfig) -> BumpLevel {
+  infer_bump(
+    &commit.subject,
+    &commit.body,
+    Some(&config.bump_map),
+    config.breaking_scopes.as_deref(),
+  )
+}
+
+/// Infers the bump of a commit from its conventional type, looked up in
+/// `bump_map` before the defaults (`feat` is minor, anything else patch).
+/// Breaking changes are always major, except a `!` on a scope missing from
+/// `breaking_scopes` (when given).
+/// Whether the commit is a breaking change: a `!` header (within
+/// `breaking_scopes` when set) or a `BREAKING CHANGE:` footer.
+pub fn is_breaking_change(subject: &str, body: &str, breaking_scopes: Option<&[String]>) -> bool {
+  let (_, scope, header_breaking) = parse_header(subject);
+
+  let header_breaking = header_breaking
+    && match (scope, breaking_scopes) {
+      (Some(scope), Some(allowed)) => allowed.iter().any(|entry| entry == scope),
+      _ => true,
+    };
+
+  let footer_breaking = body.lines().any(|line| {
+    let normalized = line.trim_start();
+    normalized.starts_with("BREAKING CHANGE:") || normalized.starts_with("BREAKING-CHANGE:")
+  });
+
+  header_breaking || footer_breaking
+}
+
+/// Splits the conventional header of `subject` into its type, its scope and
+/// whether it carries the `!` breaking marker.
+fn parse_header(subject: &str) -> (&str, Option<&str>, bool) {
   let header = subject.split_once(": ").map(|(prefix, _)| prefix).unwrap_or("");
   let header_breaking = header.ends_with('!');
 
@@ -25,20 +158,28 @@ pub fn infer_bump(subject: &str, body: &str) -> BumpLevel {
     header
   };
 
-  let commit_type = header_without_breaking
-    .split_once('(')
-    .map(|(kind, _)| kind)
-    .unwrap_or(header_without_breaking);
-
-  let footer_breaking = body.lines().any(|line| {
-    let normalized = line.trim_start();
-    normalized.starts_with("BREAKING CHANGE:") || normalized.starts_with("BREAKING-CHANGE:")
-  });
+  match header_without_breaking.split_once('(') {
+    Some((kind, scope)) => (kind, scope.strip_suffix(')'), header_breaking),
+    None => (header_without_breaking, None, header_breaking),
+  }
+}
 
-  if header_breaking || footer_breaking {
+pub fn infer_bump(
+  subject: &str,
+  body: &str,
+  bump_map: Option<&HashMap<String, BumpLevel>>,
+  breaking_scopes: Option<&[String]>,
+) -> BumpLevel {
+  if is_breaking_change(subject, body, breaking_scopes) {
     return BumpLevel::Major;
   }
 
+  let (commit_type, _, _) = parse_header(subject);
+
+  if let Some(level) = bump_map.and_then(|map| map.get(commit_type)) {
+    return *level;
+  }
+
   if commit_type == "feat" {
     return BumpLevel::Minor;
   }
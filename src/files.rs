@@ -0,0 +1,40 @@
+use std::{
+  fs,
+  io::{self, Write},
+  path::Path,
+};
+
+/// Writes `content` to `path` by writing a sibling temporary file first and
+/// renaming it over the target, so readers never observe a partial write.
+pub fn write_atomic(path: impl AsRef<Path>, content: impl AsRef<[u8]>) -> io::Result<()> {
+  let path = path.as_ref();
+  let file_name = path
+    .file_name()
+    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+
+  // The temporary file must live in the same directory, since rename is only
+  // atomic within a single filesystem.
+  let temp_path = path.with_file_name(format!(
+    ".{}.cambi-{}.tmp",
+    file_name.to_string_lossy(),
+    std::process::id()
+  ));
+
+  let result = (|| {
+    let mut file = fs::File::create(&temp_path)?;
+    file.write_all(content.as_ref())?;
+    file.sync_all()?;
+
+    if let Ok(metadata) = fs::metadata(path) {
+      fs::set_permissions(&temp_path, metadata.permissions())?;
+    }
+
+    fs::rename(&temp_path, path)
+  })();
+
+  if result.is_err() {
+    let _ = fs::remove_file(&temp_path);
+  }
+
+  result
+}
@@ -1,8 +1,22 @@
 use anyhow::Result;
 use regex::Regex;
 
+use crate::{
+  config::EffectiveConfig,
+  conventional::{commit_type, is_breaking_change},
+  git::GitCommit,
+};
+
+/// Commit types excluded when `excluded_types` is not configured.
+const DEFAULT_EXCLUDED_TYPES: [&str; 1] = ["chore"];
+
 pub struct CommitFilter {
   patterns: Vec<Regex>,
+  authors: Vec<String>,
+  excluded_authors: Vec<String>,
+  excluded_types: Vec<String>,
+  include_merge_prs: bool,
+  breaking_scopes: Option<Vec<String>>,
 }
 
 impl CommitFilter {
@@ -12,10 +26,84 @@ impl CommitFilter {
       .map(|entry| Regex::new(entry))
       .collect::<std::result::Result<Vec<_>, _>>()?;
 
-    Ok(Self { patterns })
+    Ok(Self {
+      patterns,
+      authors: Vec::new(),
+      excluded_authors: Vec::new(),
+      excluded_types: DEFAULT_EXCLUDED_TYPES.map(ToOwned::to_owned).to_vec(),
+      include_merge_prs: false,
+      breaking_scopes: None,
+    })
+  }
+
+  /// Builds the filter of the configured ignore patterns, author lists,
+  /// excluded types and merge commit handling.
+  pub fn from_config(config: &EffectiveConfig) -> Result<Self> {
+    let filter = Self::new(&config.ignore_patterns)?
+      .with_authors(&config.authors, &config.exclude_authors)
+      .with_merge_prs(config.include_merge_prs)
+      .with_breaking_scopes(config.breaking_scopes.as_deref());
+
+    Ok(match config.excluded_types.as_deref() {
+      Some(types) => filter.with_excluded_types(types),
+      None => filter,
+    })
+  }
+
+  /// Keeps only commits by one of `authors` (when not empty) and drops those by
+  /// any of `excluded`. Entries match either the author name or email.
+  pub fn with_authors(mut self, authors: &[String], excluded: &[String]) -> Self {
+    self.authors = authors.to_vec();
+    self.excluded_authors = excluded.to_vec();
+    self
+  }
+
+  /// Turns pull request merge commits into entries titled after the pull
+  /// request instead of dropping them.
+  pub fn with_merge_prs(mut self, include_merge_prs: bool) -> Self {
+    self.include_merge_prs = include_merge_prs;
+    self
+  }
+
+  /// Only treats `!` headers within these scopes as the breaking changes that
+  /// are never excluded.
+  pub fn with_breaking_scopes(mut self, breaking_scopes: Option<&[String]>) -> Self {
+    self.breaking_scopes = breaking_scopes.map(<[String]>::to_vec);
+    self
+  }
+
+  /// Drops commits of these conventional types (compared case-insensitively),
+  /// replacing the default `chore`. Breaking changes are always kept.
+  pub fn with_excluded_types(mut self, types: &[String]) -> Self {
+    self.excluded_types = types.to_vec();
+    self
+  }
+
+  pub fn is_type_excluded(&self, commit: &GitCommit) -> bool {
+    let Some(kind) = commit_type(&commit.subject) else {
+      return false;
+    };
+
+    self
+      .excluded_types
+      .iter()
+      .any(|excluded| excluded.eq_ignore_ascii_case(kind))
+      && !is_breaking_change(&commit.subject, &commit.body, self.breaking_scopes.as_deref())
+  }
+
+  pub fn includes_merge_prs(&self) -> bool {
+    self.include_merge_prs
   }
 
   pub fn is_ignored(&self, subject: &str) -> bool {
     subject.starts_with("Merge ") || self.patterns.iter().any(|pattern| pattern.is_match(subject))
   }
+
+  pub fn is_author_kept(&self, commit: &GitCommit) -> bool {
+    let matches = |entry: &String| {
+      entry.eq_ignore_ascii_case(&commit.author_name) || entry.eq_ignore_ascii_case(&commit.author_email)
+    };
+
+    (self.authors.is_empty() || self.authors.iter().any(matches)) && !self.excluded_authors.iter().any(matches)
+  }
 }
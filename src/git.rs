@@ -1,21 +1,152 @@
-use anyhow::{Context, Result};
-use git2::{ObjectType, Oid, Repository, Sort};
+use std::{
+  io::Write,
+  path::Path,
+  process::{Command, Stdio},
+};
+
+use anyhow::{Context, Result, anyhow};
+use git2::{
+  Commit, Cred, CredentialType, ErrorCode, Object, ObjectType, Oid, PushOptions, RemoteCallbacks, Repository,
+  Signature, Sort, StatusOptions, Tree,
+};
 use regex::Regex;
 
+use crate::{batch::retry, config::EffectiveConfig};
+
 pub struct GitTag {
   pub name: String,
   pub oid: Oid,
   pub time: i64,
+  /// When the tag was created: the tagger date of annotated tags, the commit
+  /// time otherwise.
+  pub tagged_at: i64,
 }
 
 pub struct GitCommit {
+  /// Abbreviated commit hash.
+  pub id: String,
   pub subject: String,
   pub body: String,
   pub time: i64,
+  pub author_name: String,
+  pub author_email: String,
+  pub parent_count: usize,
+}
+
+fn discover_repository(repo_path: &Path) -> Result<Repository> {
+  Repository::discover(repo_path).context("Failed to discover git repository")
+}
+
+/// Fetches the tags of the origin remote, retrying failures with backoff up
+/// to `max_retries` times. When every attempt fails, it warns and keeps going
+/// with the local tags.
+pub fn fetch_tags(config: &EffectiveConfig) {
+  if let Err(error) = fetch_tags_in(Path::new("."), config.max_retries, config.verbose) {
+    eprintln!("{error:#}. Using local tags.");
+  }
+}
+
+/// URL of the origin remote of the current repository, if any.
+pub fn read_origin_url() -> Option<String> {
+  read_origin_url_in(Path::new("."))
+}
+
+pub fn read_origin_url_in(repo_path: &Path) -> Option<String> {
+  let repo = discover_repository(repo_path).ok()?;
+  let remote = repo.find_remote("origin").ok()?;
+  remote.url().map(ToOwned::to_owned)
+}
+
+fn fetch_tags_in(repo_path: &Path, max_retries: u32, verbose: bool) -> Result<()> {
+  let repo = discover_repository(repo_path)?;
+  let mut remote = repo.find_remote("origin").context("Cannot find the origin remote")?;
+
+  retry(
+    max_retries,
+    || remote.fetch(&["+refs/tags/*:refs/tags/*"], None, None),
+    |error| {
+      if verbose {
+        eprintln!("Fetching tags failed ({}), retrying.", error.message());
+      }
+    },
+  )
+  .context("Cannot fetch tags from origin")
+}
+
+/// Pushes the branch checked out at HEAD, and `tag` when given, to `remote`.
+/// Credentials come from the ssh agent or the git credential helpers.
+pub fn push_head(remote_name: &str, tag: Option<&str>) -> Result<()> {
+  let repo = discover_repository(Path::new("."))?;
+  let head = repo.head().context("Cannot read git HEAD")?;
+  if !head.is_branch() {
+    return Err(anyhow!("Cannot push a detached HEAD"));
+  }
+  let branch = head.name().ok_or(anyhow!("Cannot resolve git HEAD"))?;
+
+  let mut refspecs = vec![format!("{branch}:{branch}")];
+  if let Some(tag) = tag {
+    refspecs.push(format!("refs/tags/{tag}:refs/tags/{tag}"));
+  }
+
+  let config = repo.config().context("Cannot read git config")?;
+  let mut remote = repo
+    .find_remote(remote_name)
+    .context(format!("Cannot find the {remote_name} remote"))?;
+
+  // libgit2 asks again after each failed attempt, so give up once every
+  // method had its chance instead of looping forever.
+  let mut attempts = 0;
+  let mut rejected = Vec::new();
+  let mut callbacks = RemoteCallbacks::new();
+  callbacks.credentials(|url, username, allowed| {
+    attempts += 1;
+    if attempts > 3 {
+      return Err(git2::Error::from_str("authentication failed"));
+    }
+
+    if allowed.contains(CredentialType::SSH_KEY) {
+      Cred::ssh_key_from_agent(username.unwrap_or("git"))
+    } else if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+      Cred::credential_helper(&config, url, username)
+    } else {
+      Cred::default()
+    }
+  });
+  callbacks.push_update_reference(|reference, status| {
+    if let Some(status) = status {
+      rejected.push(format!("{reference} ({status})"));
+    }
+    Ok(())
+  });
+
+  let mut options = PushOptions::new();
+  options.remote_callbacks(callbacks);
+  let result = remote.push(&refspecs, Some(&mut options));
+  drop(options);
+
+  // Local remotes fail the whole push on non-fast-forwards, while servers
+  // report every rejected reference.
+  match result {
+    Err(error) if error.code() == ErrorCode::NotFastForward => rejected.push(format!("{branch} (non-fast-forward)")),
+    result => result.context(format!("Cannot push to {remote_name}"))?,
+  }
+
+  if !rejected.is_empty() {
+    return Err(anyhow!(
+      "{remote_name} rejected the push of {}. Pull the remote changes and push again.",
+      rejected.join(", ")
+    ));
+  }
+
+  Ok(())
 }
 
 pub fn read_tags(tag_pattern: &str) -> Result<Vec<GitTag>> {
-  let repo = Repository::discover(".").context("Failed to discover git repository")?;
+  read_tags_in(Path::new("."), tag_pattern)
+}
+
+pub fn read_tags_in(repo_path: &Path, tag_pattern: &str) -> Result<Vec<GitTag>> {
+  let repo = discover_repository(repo_path)?;
   let tag_regex = Regex::new(tag_pattern).context(format!("Invalid tag regex pattern: {tag_pattern}"))?;
 
   let mut tags = repo
@@ -26,6 +157,10 @@ pub fn read_tags(tag_pattern: &str) -> Result<Vec<GitTag>> {
     .filter(|name| tag_regex.is_match(name))
     .filter_map(|name| {
       let object = repo.revparse_single(&format!("refs/tags/{name}")).ok()?;
+      let tagger_time = object
+        .as_tag()
+        .and_then(|tag| tag.tagger())
+        .map(|tagger| tagger.when().seconds());
 
       // Tags can point directly to commits or to tag objects; normalize both to
       // the commit so callers can sort and diff them consistently.
@@ -39,6 +174,7 @@ pub fn read_tags(tag_pattern: &str) -> Result<Vec<GitTag>> {
         name: name.to_string(),
         oid: commit.id(),
         time: commit.time().seconds(),
+        tagged_at: tagger_time.unwrap_or(commit.time().seconds()),
       })
     })
     .collect::<Vec<_>>();
@@ -47,9 +183,242 @@ pub fn read_tags(tag_pattern: &str) -> Result<Vec<GitTag>> {
   Ok(tags)
 }
 
-fn read_commits_between_oids(start_oid: Option<Oid>, end_oid: Option<Oid>) -> Result<Vec<GitCommit>> {
-  let repo = Repository::discover(".").context("Failed to discover git repository")?;
+pub fn verify_clean_worktree() -> Result<()> {
+  let repo = discover_repository(Path::new("."))?;
+  let mut options = StatusOptions::new();
+  options.include_untracked(false).recurse_untracked_dirs(false);
+
+  let statuses = repo.statuses(Some(&mut options)).context("Failed to read git status")?;
+  let dirty_paths = statuses
+    .iter()
+    .filter_map(|entry| entry.path().map(ToOwned::to_owned))
+    .collect::<Vec<_>>();
+
+  if !dirty_paths.is_empty() {
+    return Err(anyhow!(
+      "Working tree is not clean after commit; modified files: {}",
+      dirty_paths.join(", ")
+    ));
+  }
+
+  Ok(())
+}
+
+/// GPG key configured as `user.signingkey`, failing when signing was requested
+/// but no key is configured.
+pub fn signing_key() -> Result<String> {
+  discover_repository(Path::new("."))?
+    .config()
+    .context("Cannot read git config")?
+    .get_string("user.signingkey")
+    .map_err(|_| anyhow!("Cannot sign without a signing key. Set user.signingkey in the git config."))
+}
+
+/// Detached ASCII-armored signature of `payload`, made by `gpg.program` (gpg by
+/// default) like git does.
+fn gpg_sign(repo: &Repository, payload: &str, key: &str) -> Result<String> {
+  let program = repo
+    .config()
+    .and_then(|config| config.get_string("gpg.program"))
+    .unwrap_or_else(|_| "gpg".to_string());
+
+  let mut child = Command::new(&program)
+    .args(["--status-fd=2", "-bsau", key])
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .context(format!("Cannot run {program}"))?;
+
+  child
+    .stdin
+    .take()
+    .ok_or(anyhow!("Cannot write to {program}"))?
+    .write_all(payload.as_bytes())
+    .context(format!("Cannot write to {program}"))?;
+
+  let output = child.wait_with_output().context(format!("Cannot run {program}"))?;
+  if !output.status.success() {
+    return Err(anyhow!(
+      "Cannot sign with key {key}: {}",
+      String::from_utf8_lossy(&output.stderr).trim()
+    ));
+  }
+
+  String::from_utf8(output.stdout).context(format!("Invalid signature returned by {program}"))
+}
+
+/// Commits `tree` on top of `parents` and moves HEAD to the new commit,
+/// GPG-signing it when a `signing_key` is given.
+pub fn create_commit(
+  repo: &Repository,
+  signature: &Signature,
+  message: &str,
+  tree: &Tree,
+  parents: &[&Commit],
+  signing_key: Option<&str>,
+) -> Result<Oid> {
+  let Some(key) = signing_key else {
+    return repo
+      .commit(Some("HEAD"), signature, signature, message, tree, parents)
+      .context("Cannot create git commit");
+  };
+
+  let buffer = repo
+    .commit_create_buffer(signature, signature, message, tree, parents)
+    .context("Cannot create git commit")?;
+  let buffer = buffer.as_str().ok_or(anyhow!("Cannot sign a non UTF-8 git commit"))?;
+  let gpg_signature = gpg_sign(repo, buffer, key)?;
+  let oid = repo
+    .commit_signed(buffer, &gpg_signature, Some("gpgsig"))
+    .context("Cannot create signed git commit")?;
+
+  // commit_signed only writes the object, so HEAD (or the unborn branch it
+  // points to) is moved like `git commit` would.
+  let log_message = format!("commit: {}", message.lines().next().unwrap_or_default());
+  match repo.head() {
+    Ok(mut head) => {
+      head.set_target(oid, &log_message).context("Cannot update HEAD")?;
+    }
+    Err(_) => {
+      let head = repo.find_reference("HEAD").context("Cannot read git HEAD")?;
+      let branch = head.symbolic_target().ok_or(anyhow!("Cannot resolve git HEAD"))?;
+      repo
+        .reference(branch, oid, false, &log_message)
+        .context("Cannot update HEAD")?;
+    }
+  }
+
+  Ok(oid)
+}
+
+/// Creates the annotated tag `name` of `target`, GPG-signing it when a
+/// `signing_key` is given.
+pub fn create_annotated_tag(
+  repo: &Repository,
+  name: &str,
+  target: &Object,
+  tagger: &Signature,
+  message: &str,
+  signing_key: Option<&str>,
+) -> Result<Oid> {
+  let Some(key) = signing_key else {
+    return repo
+      .tag(name, target, tagger, message, false)
+      .context(format!("Cannot create git tag '{name}'"));
+  };
+
+  // git2 cannot sign tags, so the tag object is assembled by hand: the
+  // signature is appended to the signed payload.
+  let when = tagger.when();
+  let offset = when.offset_minutes();
+  let payload = format!(
+    "object {}\ntype {}\ntag {name}\ntagger {} <{}> {} {}{:02}{:02}\n\n{}\n",
+    target.id(),
+    target.kind().map(|kind| kind.str()).unwrap_or("commit"),
+    tagger.name().unwrap_or_default(),
+    tagger.email().unwrap_or_default(),
+    when.seconds(),
+    if offset < 0 { '-' } else { '+' },
+    offset.abs() / 60,
+    offset.abs() % 60,
+    message.trim_end()
+  );
+  let gpg_signature = gpg_sign(repo, &payload, key)?;
+
+  let oid = repo
+    .odb()
+    .context("Cannot open git object database")?
+    .write(ObjectType::Tag, format!("{payload}{gpg_signature}").as_bytes())
+    .context(format!("Cannot create git tag '{name}'"))?;
+  repo
+    .reference(&format!("refs/tags/{name}"), oid, false, "tag: signed")
+    .context(format!("Cannot create git tag '{name}'"))?;
+
+  Ok(oid)
+}
 
+pub fn read_head_oid() -> Result<Oid> {
+  discover_repository(Path::new("."))?
+    .head()
+    .context("Cannot read git HEAD")?
+    .target()
+    .context("HEAD is not pointing to a direct commit")
+}
+
+/// Lists the names of the local tags not matching `tag_pattern`, sorted by
+/// name.
+pub fn read_unmatched_tag_names(tag_pattern: &str) -> Result<Vec<String>> {
+  let repo = discover_repository(Path::new("."))?;
+  let tag_regex = Regex::new(tag_pattern).context(format!("Invalid tag regex pattern: {tag_pattern}"))?;
+
+  let mut names = repo
+    .tag_names(None)
+    .context("Cannot read git tag names")?
+    .iter()
+    .flatten()
+    .filter(|name| !tag_regex.is_match(name))
+    .map(ToOwned::to_owned)
+    .collect::<Vec<_>>();
+
+  names.sort();
+  Ok(names)
+}
+
+/// Deletes a local tag, doing nothing when it does not exist.
+pub fn delete_local_tag(name: &str) -> Result<()> {
+  let repo = discover_repository(Path::new("."))?;
+
+  if repo.refname_to_id(&format!("refs/tags/{name}")).is_err() {
+    return Ok(());
+  }
+
+  repo.tag_delete(name).context(format!("Cannot delete tag '{name}'"))
+}
+
+/// Drops anchor tags, so the ranges of the remaining tags span over them.
+pub fn exclude_anchor_tags(tags: Vec<GitTag>, anchor_tags: &[String]) -> Vec<GitTag> {
+  tags
+    .into_iter()
+    .filter(|tag| !anchor_tags.contains(&tag.name))
+    .collect()
+}
+
+pub fn retain_reachable_tags(tags: Vec<GitTag>) -> Result<Vec<GitTag>> {
+  retain_reachable_tags_in(Path::new("."), tags)
+}
+
+pub fn retain_reachable_tags_in(repo_path: &Path, tags: Vec<GitTag>) -> Result<Vec<GitTag>> {
+  let repo = discover_repository(repo_path)?;
+  let head = repo
+    .head()
+    .context("Cannot read git HEAD")?
+    .target()
+    .context("HEAD is not pointing to a direct commit")?;
+
+  let mut reachable = Vec::new();
+
+  for tag in tags {
+    let is_reachable = tag.oid == head
+      || repo
+        .graph_descendant_of(head, tag.oid)
+        .context(format!("Cannot check reachability of tag '{}'", tag.name))?;
+
+    if is_reachable {
+      reachable.push(tag);
+    }
+  }
+
+  Ok(reachable)
+}
+
+fn read_commits_between_oids(
+  repo: &Repository,
+  start_oid: Option<Oid>,
+  end_oid: Option<Oid>,
+  max_commits: Option<usize>,
+  paths: &[String],
+) -> Result<Vec<GitCommit>> {
   let end_oid = if let Some(end_oid) = end_oid {
     end_oid
   } else {
@@ -74,20 +443,33 @@ fn read_commits_between_oids(start_oid: Option<Oid>, end_oid: Option<Oid>) -> Re
 
   let mut commits = Vec::new();
 
-  for oid_result in revwalk {
+  // Capping the walk keeps huge histories fast, at the cost of ignoring older
+  // commits in the range.
+  for oid_result in revwalk.take(max_commits.unwrap_or(usize::MAX)) {
     let oid = oid_result.context("Failed iterating git history")?;
     let commit = repo.find_commit(oid).context(format!("Cannot find commit {oid}"))?;
 
+    if !paths.is_empty() && !commit_touches_paths(repo, &commit, paths)? {
+      continue;
+    }
+
     let message = commit.message().unwrap_or_default();
     let mut lines = message.lines();
     let subject = lines.next().unwrap_or_default().trim().to_string();
     let body = lines.collect::<Vec<_>>().join("\n").trim().to_string();
 
     if !subject.is_empty() {
+      let author = commit.author();
+      let mut id = oid.to_string();
+      id.truncate(7);
       commits.push(GitCommit {
+        id,
         subject,
         body,
         time: commit.time().seconds(),
+        author_name: author.name().unwrap_or_default().to_string(),
+        author_email: author.email().unwrap_or_default().to_string(),
+        parent_count: commit.parent_count(),
       });
     }
   }
@@ -95,46 +477,147 @@ fn read_commits_between_oids(start_oid: Option<Oid>, end_oid: Option<Oid>) -> Re
   Ok(commits)
 }
 
-pub fn read_commits_between_tags(from_tag: Option<&str>, to_tag: &str) -> Result<Vec<GitCommit>> {
-  let repo = Repository::discover(".").context("Failed to discover git repository")?;
+/// Checks whether the commit changed a file under any of the path prefixes,
+/// comparing against its first parent.
+fn commit_touches_paths(repo: &Repository, commit: &Commit, paths: &[String]) -> Result<bool> {
+  let prefixes = paths
+    .iter()
+    .map(|path| Path::new(path.trim_start_matches("./")))
+    .collect::<Vec<_>>();
+
+  let tree = commit
+    .tree()
+    .context(format!("Cannot read tree of commit {}", commit.id()))?;
+  let parent_tree = match commit.parents().next() {
+    Some(parent) => {
+      Some(
+        parent
+          .tree()
+          .context(format!("Cannot read tree of commit {}", parent.id()))?,
+      )
+    }
+    None => None,
+  };
+
+  let diff = repo
+    .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+    .context(format!("Cannot diff commit {}", commit.id()))?;
+
+  Ok(diff.deltas().any(|delta| {
+    [delta.old_file().path(), delta.new_file().path()]
+      .into_iter()
+      .flatten()
+      .any(|file| prefixes.iter().any(|prefix| file.starts_with(prefix)))
+  }))
+}
+
+fn resolve_tag_commit(repo: &Repository, tag: &str) -> Result<Oid> {
+  Ok(
+    repo
+      .revparse_single(tag)
+      .context(format!("Cannot resolve tag '{tag}'"))?
+      .peel_to_commit()
+      .context(format!("Tag '{tag}' does not resolve to a commit"))?
+      .id(),
+  )
+}
+
+pub fn read_commits_between_tags(
+  from_tag: Option<&str>,
+  to_tag: &str,
+  max_commits: Option<usize>,
+  paths: &[String],
+) -> Result<Vec<GitCommit>> {
+  read_commits_between_tags_in(Path::new("."), from_tag, to_tag, max_commits, paths)
+}
+
+pub fn read_commits_between_tags_in(
+  repo_path: &Path,
+  from_tag: Option<&str>,
+  to_tag: &str,
+  max_commits: Option<usize>,
+  paths: &[String],
+) -> Result<Vec<GitCommit>> {
+  let repo = discover_repository(repo_path)?;
 
   let start_oid = if let Some(tag) = from_tag {
-    Some(
-      repo
-        .revparse_single(tag)
-        .context(format!("Cannot resolve tag '{tag}'"))?
-        .peel_to_commit()
-        .context(format!("Tag '{tag}' does not resolve to a commit"))?
-        .id(),
-    )
+    Some(resolve_tag_commit(&repo, tag)?)
   } else {
     None
   };
 
-  let end_oid = repo
-    .revparse_single(to_tag)
-    .context(format!("Cannot resolve tag '{to_tag}'"))?
-    .peel_to_commit()
-    .context(format!("Tag '{to_tag}' does not resolve to a commit"))?
-    .id();
+  let end_oid = resolve_tag_commit(&repo, to_tag)?;
 
-  read_commits_between_oids(start_oid, Some(end_oid))
+  read_commits_between_oids(&repo, start_oid, Some(end_oid), max_commits, paths)
 }
 
-pub fn read_commits(from_tag: Option<&str>, tag_pattern: &str) -> Result<Vec<GitCommit>> {
-  let start_oid = if let Some(tag) = from_tag {
-    let repo = Repository::discover(".").context("Failed to discover git repository")?;
-    Some(
-      repo
-        .revparse_single(tag)
-        .context(format!("Cannot resolve tag '{tag}'"))?
-        .peel_to_commit()
-        .context(format!("Tag '{tag}' does not resolve to a commit"))?
-        .id(),
-    )
-  } else {
-    read_tags(tag_pattern)?.first().map(|tag| tag.oid)
+pub fn read_commits(
+  from_tag: Option<&str>,
+  tag_pattern: &str,
+  max_commits: Option<usize>,
+  paths: &[String],
+) -> Result<Vec<GitCommit>> {
+  read_commits_in(Path::new("."), from_tag, tag_pattern, max_commits, paths)
+}
+
+pub fn read_commits_in(
+  repo_path: &Path,
+  from_tag: Option<&str>,
+  tag_pattern: &str,
+  max_commits: Option<usize>,
+  paths: &[String],
+) -> Result<Vec<GitCommit>> {
+  let repo = discover_repository(repo_path)?;
+  let start_oid = resolve_start_tag(&repo, repo_path, from_tag, tag_pattern)?.map(|(_, oid)| oid);
+
+  read_commits_between_oids(&repo, start_oid, None, max_commits, paths)
+}
+
+/// Resolves the tag `read_commits` starts from: `from_tag`, or else the latest
+/// tag matching `tag_pattern`.
+fn resolve_start_tag(
+  repo: &Repository,
+  repo_path: &Path,
+  from_tag: Option<&str>,
+  tag_pattern: &str,
+) -> Result<Option<(String, Oid)>> {
+  if let Some(tag) = from_tag {
+    return Ok(Some((tag.to_string(), resolve_tag_commit(repo, tag)?)));
+  }
+
+  Ok(
+    read_tags_in(repo_path, tag_pattern)?
+      .into_iter()
+      .next()
+      .map(|tag| (tag.name, tag.oid)),
+  )
+}
+
+fn format_range(start: Option<(String, Oid)>, end: (&str, Oid)) -> String {
+  let short = |oid: Oid| oid.to_string()[..7].to_string();
+  let start = start
+    .map(|(name, oid)| format!("{name} ({})", short(oid)))
+    .unwrap_or_else(|| "<root>".to_string());
+
+  format!("Commit range: {start}..{} ({})", end.0, short(end.1))
+}
+
+/// Describes the range walked by `read_commits`, naming the start tag and
+/// HEAD with their short SHAs.
+pub fn describe_commit_range(from_tag: Option<&str>, tag_pattern: &str) -> Result<String> {
+  let repo = discover_repository(Path::new("."))?;
+  let start = resolve_start_tag(&repo, Path::new("."), from_tag, tag_pattern)?;
+
+  Ok(format_range(start, ("HEAD", read_head_oid()?)))
+}
+
+/// Describes the range walked by `read_commits_between_tags`.
+pub fn describe_tag_range(from_tag: Option<&str>, to_tag: &str) -> Result<String> {
+  let repo = discover_repository(Path::new("."))?;
+  let start = match from_tag {
+    Some(tag) => Some((tag.to_string(), resolve_tag_commit(&repo, tag)?)),
+    None => None,
   };
 
-  read_commits_between_oids(start_oid, None)
+  Ok(format_range(start, (to_tag, resolve_tag_commit(&repo, to_tag)?)))
 }
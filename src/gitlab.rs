@@ -0,0 +1,200 @@
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  config::EffectiveConfig,
+  release::{ExistingRelease, PublishedRelease, ReleasePayload, ReleaseProvider, percent_encode},
+};
+
+#[derive(Debug, Clone, Serialize)]
+struct ChangelogPayload {
+  version: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  from: Option<String>,
+  to: String,
+}
+
+fn gitlab_client() -> ureq::Agent {
+  ureq::AgentBuilder::new().build()
+}
+
+/// Returns the URL-encoded `owner/repo` path GitLab accepts as a project id.
+pub fn project_id(owner: &str, repo: &str) -> String {
+  percent_encode(&format!("{owner}/{repo}"))
+}
+
+/// Asks GitLab to generate and commit the changelog section for `version`,
+/// covering the commits after `from` (when given) up to `to`.
+pub fn generate_remote_changelog(
+  api_base: &str,
+  owner: &str,
+  repo: &str,
+  token: &str,
+  version: &str,
+  from: Option<&str>,
+  to: &str,
+) -> Result<()> {
+  let url = format!("{api_base}/projects/{}/repository/changelog", project_id(owner, repo));
+  let payload = ChangelogPayload {
+    version: version.to_string(),
+    from: from.map(ToOwned::to_owned),
+    to: to.to_string(),
+  };
+
+  gitlab_client()
+    .post(&url)
+    .set("PRIVATE-TOKEN", token)
+    .set("User-Agent", "cambi")
+    .send_json(serde_json::to_value(payload).context("Cannot serialize changelog payload")?)
+    .map_err(|error| anyhow!("GitLab API error while generating changelog for {version}: {error}"))?;
+
+  Ok(())
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GitlabRelease {
+  tag_name: String,
+  name: Option<String>,
+  description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GitlabReleasePayload<'a> {
+  tag_name: &'a str,
+  name: &'a str,
+  description: &'a str,
+}
+
+impl<'a> From<&'a ReleasePayload> for GitlabReleasePayload<'a> {
+  fn from(payload: &'a ReleasePayload) -> Self {
+    Self {
+      tag_name: &payload.tag_name,
+      name: &payload.name,
+      description: &payload.body,
+    }
+  }
+}
+
+/// GitLab release provider. Releases are addressed by tag and carry no
+/// prerelease flag.
+pub struct GitlabProvider {
+  api_base: String,
+  project: String,
+  token: String,
+  offline: bool,
+}
+
+impl GitlabProvider {
+  pub fn new(owner: &str, repo: &str, token: &str, config: &EffectiveConfig) -> Result<Self> {
+    Ok(Self {
+      api_base: config.api_base_url()?,
+      project: project_id(owner, repo),
+      token: token.to_string(),
+      offline: config.offline,
+    })
+  }
+
+  /// Builds a request for `path` (relative to the project endpoint), failing
+  /// upfront in offline mode.
+  fn request(&self, method: &str, path: &str) -> Result<ureq::Request> {
+    if self.offline {
+      return Err(anyhow!("Cannot call the GitLab API in offline mode"));
+    }
+
+    let url = format!("{}/projects/{}{path}", self.api_base, self.project);
+
+    Ok(
+      gitlab_client()
+        .request(method, &url)
+        .set("PRIVATE-TOKEN", &self.token)
+        .set("User-Agent", "cambi"),
+    )
+  }
+}
+
+impl ReleaseProvider for GitlabProvider {
+  fn list_releases(&self) -> Result<Vec<ExistingRelease>> {
+    let response = self
+      .request("GET", "/releases?per_page=100")?
+      .call()
+      .map_err(|error| anyhow!("GitLab API error while listing releases: {error}"))?;
+
+    let releases = response
+      .into_json::<Vec<GitlabRelease>>()
+      .context("Failed to parse GitLab release list")?;
+
+    Ok(
+      releases
+        .into_iter()
+        .map(|release| {
+          ExistingRelease {
+            id: 0,
+            tag_name: release.tag_name,
+            name: release.name,
+            body: release.description,
+            prerelease: false,
+            draft: false,
+            upload_url: None,
+          }
+        })
+        .collect(),
+    )
+  }
+
+  fn create_release(&self, payload: &ReleasePayload) -> Result<PublishedRelease> {
+    self
+      .request("POST", "/releases")?
+      .send_json(serde_json::to_value(GitlabReleasePayload::from(payload)).context("Cannot serialize release payload")?)
+      .map_err(|error| {
+        anyhow!(
+          "GitLab API error while creating release '{}': {error}",
+          payload.tag_name
+        )
+      })?;
+
+    Ok(PublishedRelease::default())
+  }
+
+  fn update_release(&self, release: &ExistingRelease, payload: &ReleasePayload) -> Result<PublishedRelease> {
+    self
+      .request("PUT", &format!("/releases/{}", percent_encode(&release.tag_name)))?
+      .send_json(serde_json::to_value(GitlabReleasePayload::from(payload)).context("Cannot serialize release payload")?)
+      .map_err(|error| {
+        anyhow!(
+          "GitLab API error while updating release '{}': {error}",
+          payload.tag_name
+        )
+      })?;
+
+    Ok(PublishedRelease::default())
+  }
+
+  fn delete_release(&self, release: &ExistingRelease) -> Result<()> {
+    let result = self
+      .request("DELETE", &format!("/releases/{}", percent_encode(&release.tag_name)))?
+      .call();
+
+    match result {
+      Ok(_) | Err(ureq::Error::Status(404, _)) => Ok(()),
+      Err(error) => {
+        Err(anyhow!(
+          "GitLab API error while deleting release '{}': {error}",
+          release.tag_name
+        ))
+      }
+    }
+  }
+
+  fn delete_tag(&self, tag: &str) -> Result<()> {
+    self
+      .request("DELETE", &format!("/repository/tags/{}", percent_encode(tag)))?
+      .call()
+      .map_err(|error| anyhow!("GitLab API error while deleting tag '{tag}': {error}"))?;
+
+    Ok(())
+  }
+
+  fn tracks_release_flags(&self) -> bool {
+    false
+  }
+}
@@ -1,8 +1,15 @@
+pub mod api;
+pub mod batch;
 pub mod changelog;
 pub mod cli;
 pub mod config;
 pub mod conventional;
+pub mod files;
 pub mod filters;
 pub mod git;
+pub mod gitlab;
 pub mod release;
+pub mod tags;
 pub mod version;
+
+pub use api::Cambi;
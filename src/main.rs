@@ -3,16 +3,20 @@ use std::collections::HashMap;
 use anyhow::Result;
 use cambi::{
   changelog::execute_changelog_command,
-  cli::{Args, Command},
-  config::{ConfigOverrides, EffectiveConfig, load_file},
+  cli::{Args, Command, write_completions},
+  config::{ConfigOverrides, EffectiveConfig, config_schema, load_file},
+  git::fetch_tags,
   release::execute_release_command,
+  tags::execute_tags,
   version::{execute_semver, execute_update, execute_version},
 };
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 
 fn main() -> Result<()> {
-  let args = match Args::try_parse() {
-    Ok(opts) => opts,
+  // The subcommand is only optional for --config-schema, so it is enforced
+  // manually after parsing.
+  let matches = match Args::command().subcommand_required(false).try_get_matches() {
+    Ok(matches) => matches,
     Err(e) => {
       match e.kind() {
         clap::error::ErrorKind::DisplayVersion => {
@@ -26,40 +30,110 @@ fn main() -> Result<()> {
     }
   };
 
+  if matches.get_flag("config_schema") {
+    println!("{}", config_schema());
+    return Ok(());
+  }
+
+  if matches.subcommand_name().is_none() {
+    Args::command()
+      .error(
+        clap::error::ErrorKind::MissingSubcommand,
+        "a subcommand is required unless --config-schema is given",
+      )
+      .exit();
+  }
+
+  let args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+  match &args.command {
+    // Completions need no configuration, so a broken config file cannot
+    // prevent installing them.
+    Command::Completions(completions) => write_completions(completions.shell, &mut std::io::stdout()),
+    Command::Version(version_args) => execute_version(version_args, &load_config(&args)?)?,
+    Command::Semver(semver_args) => execute_semver(semver_args, &load_config(&args)?)?,
+    Command::Update(update_args) => execute_update(update_args, &load_config(&args)?)?,
+    Command::Changelog(changelog_args) => execute_changelog_command(changelog_args, &load_config(&args)?)?,
+    Command::Release(release_args) => execute_release_command(release_args, &load_config(&args)?)?,
+    Command::Tags(tags_args) => execute_tags(tags_args, &load_config(&args)?)?,
+  }
+
+  Ok(())
+}
+
+/// Merges the config files, the environment and the global flags into the
+/// configuration of the command, fetching tags first when asked to.
+fn load_config(args: &Args) -> Result<EffectiveConfig> {
   let file_cfg = load_file(args.config.as_deref())?;
 
   let overrides = match &args.command {
     Command::Release(release) => {
       ConfigOverrides {
+        provider: args.provider,
+        api_base: args.api_base.clone(),
+        web_host: args.web_host.clone(),
         token: release.token.clone(),
         owner: release.owner.clone(),
         repo: release.repo.clone(),
         tag_pattern: args.tag_pattern.clone(),
+        tag_prefix: args.prefix.clone(),
+        initial_version: args.initial_version.clone(),
+        max_commits: args.max_commits,
+        paths: (!args.paths.is_empty()).then(|| args.paths.clone()),
+        authors: (!args.authors.is_empty()).then(|| args.authors.clone()),
+        exclude_authors: (!args.exclude_authors.is_empty()).then(|| args.exclude_authors.clone()),
+        include_merge_prs: args.include_merge_prs.then_some(true),
+        continue_on_error: args.continue_on_error.then_some(true),
+        fetch: args.fetch.then_some(true),
+        max_retries: args.max_retries,
+        format: Some(args.format),
+        offline: args.offline.then_some(true),
         verbose: Some(args.verbose),
       }
     }
-    Command::Version(_) | Command::Semver(_) | Command::Update(_) | Command::Changelog(_) => {
+    Command::Version(_)
+    | Command::Semver(_)
+    | Command::Update(_)
+    | Command::Changelog(_)
+    | Command::Tags(_)
+    | Command::Completions(_) => {
       ConfigOverrides {
+        provider: args.provider,
+        api_base: args.api_base.clone(),
+        web_host: args.web_host.clone(),
         tag_pattern: args.tag_pattern.clone(),
+        tag_prefix: args.prefix.clone(),
+        initial_version: args.initial_version.clone(),
+        max_commits: args.max_commits,
+        paths: (!args.paths.is_empty()).then(|| args.paths.clone()),
+        authors: (!args.authors.is_empty()).then(|| args.authors.clone()),
+        exclude_authors: (!args.exclude_authors.is_empty()).then(|| args.exclude_authors.clone()),
+        include_merge_prs: args.include_merge_prs.then_some(true),
+        continue_on_error: args.continue_on_error.then_some(true),
+        fetch: args.fetch.then_some(true),
+        max_retries: args.max_retries,
+        format: Some(args.format),
+        offline: args.offline.then_some(true),
         verbose: Some(args.verbose),
         ..ConfigOverrides::default()
       }
     }
   };
 
-  let config = EffectiveConfig::from_sources(file_cfg, &HashMap::from_iter(std::env::vars()), overrides);
+  let config = EffectiveConfig::from_sources(
+    file_cfg,
+    args.command.name(),
+    &HashMap::from_iter(std::env::vars()),
+    overrides,
+  )?;
 
   if config.verbose {
     eprintln!("Configuration loaded for command '{}'.", args.command.name());
   }
 
-  match &args.command {
-    Command::Version(version_args) => execute_version(version_args, &config)?,
-    Command::Semver(semver_args) => execute_semver(semver_args, &config)?,
-    Command::Update(update_args) => execute_update(update_args, &config)?,
-    Command::Changelog(changelog_args) => execute_changelog_command(changelog_args, &config)?,
-    Command::Release(release_args) => execute_release_command(release_args, &config)?,
+  if config.fetch {
+    fetch_tags(&config);
   }
 
-  Ok(())
+  Ok(config)
 }
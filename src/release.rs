@@ -1,69 +1,137 @@
-use std::{collections::HashSet, fs, path::Path};
+use std::{
+  collections::{HashMap, HashSet},
+  fs,
+  io::{self, BufRead, Write},
+  path::{Path, PathBuf},
+  thread,
+  time::Duration,
+};
 
 use anyhow::{Context, Result, anyhow};
+use chrono::DateTime;
 use regex::Regex;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-  changelog::{apply_default_sorting, collect_releasable_commits},
+  batch::{BatchErrors, retry_backoff},
+  changelog::{apply_default_sorting, collect_releasable_commits, display_subjects, extract_configured_section},
   cli::ReleaseArgs,
-  config::EffectiveConfig,
+  config::{EffectiveConfig, OutputFormat, Provider},
+  conventional::commit_type,
   filters::CommitFilter,
-  git::{GitTag, read_commits_between_tags, read_tags},
+  git::{
+    GitTag, delete_local_tag, describe_tag_range, exclude_anchor_tags, read_commits_between_tags_in,
+    read_origin_url_in, read_tags_in, retain_reachable_tags_in,
+  },
+  gitlab::GitlabProvider,
 };
 
+/// A release to publish for a tag.
 #[derive(Debug, Clone)]
-struct ReleaseCandidate {
-  tag_name: String,
-  title: String,
-  body: String,
+pub struct ReleaseCandidate {
+  pub tag_name: String,
+  pub title: String,
+  pub body: String,
+  /// Tag the notes start from, linked when the body is too long.
+  pub previous_tag: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExistingRelease {
+  /// Numeric id, left at zero by providers addressing releases by tag.
+  #[serde(default)]
+  pub id: u64,
+  pub tag_name: String,
+  pub name: Option<String>,
+  pub body: Option<String>,
+  #[serde(default)]
+  pub prerelease: bool,
+  #[serde(default)]
+  pub draft: bool,
+  #[serde(default)]
+  pub upload_url: Option<String>,
+}
+
+/// Release returned by the provider after creating or updating it.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PublishedRelease {
+  #[serde(default)]
+  pub id: u64,
+  #[serde(default)]
+  pub upload_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct ExistingRelease {
+struct ReleaseAsset {
   id: u64,
-  tag_name: String,
-  name: Option<String>,
-  body: Option<String>,
+  name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct PullRequestLabel {
+  name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct PullRequest {
   #[serde(default)]
-  prerelease: bool,
+  labels: Vec<PullRequestLabel>,
+}
+
+/// Tags of the releases acted on by a release run, printed as a JSON summary.
+#[derive(Debug, Default, Serialize)]
+struct ReleaseSummary {
+  created: Vec<String>,
+  updated: Vec<String>,
+  skipped: Vec<String>,
+  deleted: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
-struct ReleasePayload {
-  tag_name: String,
-  name: String,
-  body: String,
-  draft: bool,
-  prerelease: bool,
+pub struct ReleasePayload {
+  pub tag_name: String,
+  pub name: String,
+  pub body: String,
+  pub draft: bool,
+  pub prerelease: bool,
 }
 
-pub fn parse_github_repo_from_url(url: &str) -> Option<(String, String)> {
-  let re = Regex::new(r"github\.com[:/](?P<owner>[^/]+)/(?P<repo>[^/.]+)(?:\.git)?/?$")
-    .expect("github repository regex must compile");
+/// Parses the owner (including GitLab subgroups) and repository from a
+/// repository URL on `host`, in either the HTTPS or the SSH form.
+pub fn parse_repo_from_url(url: &str, host: &str) -> Option<(String, String)> {
+  let re = Regex::new(&format!(
+    r"(?:^|[/@]){}[:/](?P<owner>[^.]+?)/(?P<repo>[^/.]+)(?:\.git)?/?$",
+    regex::escape(host)
+  ))
+  .expect("repository regex must compile");
   let captures = re.captures(url)?;
   let owner = captures.name("owner")?.as_str().to_string();
   let repo = captures.name("repo")?.as_str().to_string();
   Some((owner, repo))
 }
 
-fn detect_owner_repo_from_files() -> Option<(String, String)> {
-  if Path::new("Cargo.toml").exists() {
-    let cargo = fs::read_to_string("Cargo.toml").ok()?;
+fn detect_owner_repo_from_files(
+  repo_path: &Path,
+  parse_url: &dyn Fn(&str) -> Option<(String, String)>,
+) -> Option<(String, String)> {
+  let cargo_path = repo_path.join("Cargo.toml");
+  if cargo_path.exists() {
+    let cargo = fs::read_to_string(cargo_path).ok()?;
     let re =
       Regex::new(r#"(?m)^\s*repository\s*=\s*"(?P<url>[^"]+)"\s*$"#).expect("cargo repository regex must compile");
 
     if let Some(captures) = re.captures(&cargo) {
       let url = captures.name("url")?.as_str();
-      if let Some(parsed) = parse_github_repo_from_url(url) {
+      if let Some(parsed) = parse_url(url) {
         return Some(parsed);
       }
     }
   }
 
-  if Path::new("package.json").exists() {
-    let package = fs::read_to_string("package.json").ok()?;
+  let package_path = repo_path.join("package.json");
+  if package_path.exists() {
+    let package = fs::read_to_string(package_path).ok()?;
     let json: serde_json::Value = serde_json::from_str(&package).ok()?;
 
     if let Some(repository) = json.get("repository") {
@@ -75,7 +143,7 @@ fn detect_owner_repo_from_files() -> Option<(String, String)> {
       }
 
       if let Some(url) = repository_url
-        && let Some(parsed) = parse_github_repo_from_url(url)
+        && let Some(parsed) = parse_url(url)
       {
         return Some(parsed);
       }
@@ -85,6 +153,13 @@ fn detect_owner_repo_from_files() -> Option<(String, String)> {
   None
 }
 
+pub fn pull_request_number(subject: &str) -> Option<u64> {
+  let re = Regex::new(r"\(#(?P<number>\d+)\)").expect("pull request reference regex must compile");
+  re.captures_iter(subject)
+    .last()
+    .and_then(|captures| captures.name("number")?.as_str().parse().ok())
+}
+
 pub fn normalize_release_version(version: &str) -> String {
   version.trim_start_matches('v').to_string()
 }
@@ -93,7 +168,13 @@ pub fn release_tag(tag: &str) -> String {
   format!("v{}", normalize_release_version(tag))
 }
 
-pub fn release_title(tag: &str) -> String {
+/// Title of the release for `tag`: the version without the `v` prefix, or the
+/// tag name as is when `keep_prefix` is set.
+pub fn release_title(tag: &str, keep_prefix: bool) -> String {
+  if keep_prefix {
+    return tag.to_string();
+  }
+
   normalize_release_version(tag)
 }
 
@@ -109,23 +190,224 @@ pub fn render_release_body(commits: &[String]) -> String {
     .join("\n")
 }
 
-fn build_release_candidates(tags: &[GitTag], filter: &CommitFilter) -> Result<Vec<ReleaseCandidate>> {
+fn default_group_label(kind: &str) -> Option<&'static str> {
+  match kind {
+    "feat" => Some("Features"),
+    "fix" => Some("Bug Fixes"),
+    "perf" => Some("Performance Improvements"),
+    _ => None,
+  }
+}
+
+/// Renders the release body with a `### <label>` section per commit type, in
+/// the order the sections first appear. `labels` overrides the default labels,
+/// and unlabeled types end up under `Other Changes`.
+///
+/// A non-empty `type_order` sorts the sections by the position of their types
+/// in it instead, with `other` placing `Other Changes` (last when missing).
+/// Types not listed end up under `Other Changes`.
+pub fn render_grouped_release_body(
+  commits: &[String],
+  labels: &HashMap<String, String>,
+  type_order: &[String],
+) -> String {
+  if commits.is_empty() {
+    return render_release_body(commits);
+  }
+
+  let rank = |kind: &str| type_order.iter().position(|entry| entry == kind);
+  let other_rank = rank("other").unwrap_or(type_order.len());
+  let mut groups: Vec<(String, usize, Vec<String>)> = Vec::new();
+  let mut other = Vec::new();
+
+  for subject in commits {
+    let kind = commit_type(subject).filter(|kind| type_order.is_empty() || rank(kind).is_some());
+    let label = kind.and_then(|kind| {
+      labels
+        .get(kind)
+        .map(String::as_str)
+        .or_else(|| default_group_label(kind))
+    });
+
+    match (kind, label) {
+      (Some(kind), Some(label)) => {
+        let kind_rank = rank(kind).unwrap_or(usize::MAX);
+
+        match groups.iter_mut().find(|(heading, ..)| heading == label) {
+          Some((_, group_rank, subjects)) => {
+            *group_rank = (*group_rank).min(kind_rank);
+            subjects.push(subject.clone());
+          }
+          None => groups.push((label.to_string(), kind_rank, vec![subject.clone()])),
+        }
+      }
+      _ => other.push(subject.clone()),
+    }
+  }
+
+  if !other.is_empty() {
+    groups.push(("Other Changes".to_string(), other_rank, other));
+  }
+
+  if !type_order.is_empty() {
+    groups.sort_by_key(|(_, group_rank, _)| *group_rank);
+  }
+
+  groups
+    .iter()
+    .map(|(heading, _, subjects)| format!("### {heading}\n\n{}", render_release_body(subjects)))
+    .collect::<Vec<_>>()
+    .join("\n\n")
+}
+
+/// Renders the release body of `commits`, grouped by type when configured.
+pub fn render_release_notes(commits: &[String], config: &EffectiveConfig) -> String {
+  if config.release_grouped {
+    render_grouped_release_body(commits, &config.release_group_labels, &config.changelog_type_order)
+  } else {
+    render_release_body(commits)
+  }
+}
+
+/// Replaces a body longer than `max_bytes` with a link to the full
+/// changelog, since providers reject oversized release bodies.
+pub fn fit_release_body(body: String, max_bytes: usize, changelog_url: impl FnOnce() -> Option<String>) -> String {
+  if body.len() <= max_bytes {
+    return body;
+  }
+
+  match changelog_url() {
+    Some(url) => format!("See the full changelog: {url}"),
+    None => "See the full changelog in CHANGELOG.md.".to_string(),
+  }
+}
+
+/// Links the changes of `tag`: the comparison with the previous tag, or the
+/// CHANGELOG.md at the tag for the first release.
+fn full_changelog_url(
+  repo_path: &Path,
+  config: &EffectiveConfig,
+  previous_tag: Option<&str>,
+  tag: &str,
+) -> Option<String> {
+  let (owner, repo) = resolve_owner_repo_in(repo_path, config).ok()?;
+
+  Some(match previous_tag {
+    Some(previous_tag) => {
+      config
+        .provider
+        .compare_url(config.web_host(), &owner, &repo, previous_tag, tag)
+    }
+    None => {
+      config
+        .provider
+        .file_url(config.web_host(), &owner, &repo, tag, "CHANGELOG.md")
+    }
+  })
+}
+
+/// Whether a release titled `title` is a prerelease: its version carries a
+/// semver prerelease suffix, or the title matches `title_pattern`.
+pub fn is_prerelease_title(title: &str, title_pattern: Option<&Regex>) -> bool {
+  let semver_prerelease =
+    Version::parse(&normalize_release_version(title)).is_ok_and(|version| !version.pre.is_empty());
+  semver_prerelease || title_pattern.is_some_and(|pattern| pattern.is_match(title))
+}
+
+/// Normalizes a release body for comparison, ignoring line endings and
+/// surrounding whitespace that GitHub may alter.
+fn normalize_release_body(body: &str) -> String {
+  body.replace("\r\n", "\n").trim().to_string()
+}
+
+pub fn append_notes_footer(body: &str, footer: &str) -> String {
+  let footer = footer.trim();
+  if footer.is_empty() {
+    return body.to_string();
+  }
+
+  format!("{body}\n\n{footer}")
+}
+
+/// Replaces the body of each candidate with its CHANGELOG.md section, keeping
+/// the git-derived body of versions the changelog does not have.
+fn apply_changelog_bodies(candidates: &mut [ReleaseCandidate], config: &EffectiveConfig) -> Result<()> {
+  let markdown = fs::read_to_string("CHANGELOG.md").unwrap_or_default();
+
+  for candidate in candidates {
+    let version = normalize_release_version(&candidate.tag_name);
+
+    match extract_configured_section(&markdown, &version, config)? {
+      Some(section) => {
+        candidate.body = fit_release_body(section, config.release_max_body_bytes, || {
+          full_changelog_url(
+            Path::new("."),
+            config,
+            candidate.previous_tag.as_deref(),
+            &candidate.tag_name,
+          )
+        })
+      }
+      None => {
+        if config.verbose {
+          eprintln!("Version {version} not found in CHANGELOG.md, using the notes derived from git.");
+        }
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// Keeps the tags that get a release: anchor tags are dropped, and so are the
+/// tags not reachable from HEAD with `reachable_only`.
+fn release_tags_in(repo_path: &Path, tags: Vec<GitTag>, config: &EffectiveConfig) -> Result<Vec<GitTag>> {
+  // Orphaned tags would produce releases for commits that never landed on the
+  // current branch.
+  let tags = if config.reachable_only {
+    retain_reachable_tags_in(repo_path, tags)?
+  } else {
+    tags
+  };
+
+  Ok(exclude_anchor_tags(tags, &config.anchor_tags))
+}
+
+fn build_release_candidates(
+  tags: Vec<GitTag>,
+  filter: &CommitFilter,
+  config: &EffectiveConfig,
+) -> Result<Vec<ReleaseCandidate>> {
+  let repo_path = Path::new(".");
+  let tags = release_tags_in(repo_path, tags, config)?;
+
   let mut previous_tag_name: Option<String> = None;
   let mut candidates = Vec::new();
 
   for tag in tags.iter().rev() {
     let mut commits = collect_releasable_commits(
-      read_commits_between_tags(previous_tag_name.as_deref(), &tag.name)?,
+      read_commits_between_tags_in(
+        repo_path,
+        previous_tag_name.as_deref(),
+        &tag.name,
+        config.max_commits,
+        &config.paths,
+      )?,
       filter,
     );
     apply_default_sorting(&mut commits);
 
-    let subjects = commits.into_iter().map(|commit| commit.subject).collect::<Vec<_>>();
+    let subjects = display_subjects(commits, config);
 
     candidates.push(ReleaseCandidate {
       tag_name: release_tag(&tag.name),
-      title: release_title(&tag.name),
-      body: render_release_body(&subjects),
+      title: release_title(&tag.name, config.release_title_keep_prefix),
+      body: fit_release_body(
+        render_release_notes(&subjects, config),
+        config.release_max_body_bytes,
+        || full_changelog_url(repo_path, config, previous_tag_name.as_deref(), &tag.name),
+      ),
+      previous_tag: previous_tag_name.clone(),
     });
 
     previous_tag_name = Some(tag.name.clone());
@@ -134,21 +416,43 @@ fn build_release_candidates(tags: &[GitTag], filter: &CommitFilter) -> Result<Ve
   Ok(candidates)
 }
 
-fn build_latest_release_candidate(tags: &[GitTag], filter: &CommitFilter) -> Result<ReleaseCandidate> {
+/// Builds the release of the latest of `tags`, read from the repository at
+/// `repo_path`.
+pub fn build_latest_release_candidate_in(
+  repo_path: &Path,
+  tags: Vec<GitTag>,
+  filter: &CommitFilter,
+  config: &EffectiveConfig,
+) -> Result<ReleaseCandidate> {
+  let tags = release_tags_in(repo_path, tags, config)?;
   let latest_tag = tags
     .first()
     .ok_or(anyhow!("No release candidates produced from git tags"))?;
   let previous_tag = tags.get(1).map(|tag| tag.name.as_str());
 
-  let mut commits = collect_releasable_commits(read_commits_between_tags(previous_tag, &latest_tag.name)?, filter);
+  let mut commits = collect_releasable_commits(
+    read_commits_between_tags_in(
+      repo_path,
+      previous_tag,
+      &latest_tag.name,
+      config.max_commits,
+      &config.paths,
+    )?,
+    filter,
+  );
   apply_default_sorting(&mut commits);
 
-  let subjects = commits.into_iter().map(|commit| commit.subject).collect::<Vec<_>>();
+  let subjects = display_subjects(commits, config);
 
   Ok(ReleaseCandidate {
     tag_name: release_tag(&latest_tag.name),
-    title: release_title(&latest_tag.name),
-    body: render_release_body(&subjects),
+    title: release_title(&latest_tag.name, config.release_title_keep_prefix),
+    body: fit_release_body(
+      render_release_notes(&subjects, config),
+      config.release_max_body_bytes,
+      || full_changelog_url(repo_path, config, previous_tag, &latest_tag.name),
+    ),
+    previous_tag: previous_tag.map(ToOwned::to_owned),
   })
 }
 
@@ -191,100 +495,375 @@ fn resolve_release_target(current: &str, target: Option<&str>) -> Result<String>
   }
 }
 
-fn github_api_base() -> String {
-  std::env::var("CAMBI_GITHUB_API_BASE").unwrap_or_else(|_| "https://api.github.com".to_string())
-}
-
 fn github_client() -> ureq::Agent {
   ureq::AgentBuilder::new().build()
 }
 
-fn list_releases(owner: &str, repo: &str, token: &str) -> Result<Vec<ExistingRelease>> {
-  let url = format!("{}/repos/{owner}/{repo}/releases?per_page=100", github_api_base());
-  let response = github_client()
-    .get(&url)
-    .set("Accept", "application/vnd.github+json")
-    .set("Authorization", &format!("Bearer {token}"))
-    .set("X-GitHub-Api-Version", "2022-11-28")
-    .set("User-Agent", "cambi")
-    .call()
-    .map_err(|error| anyhow!("GitHub API error while listing releases: {error}"))?;
-
-  response
-    .into_json::<Vec<ExistingRelease>>()
-    .context("Failed to parse GitHub release list")
-}
-
-fn delete_release(owner: &str, repo: &str, token: &str, release_id: u64) -> Result<()> {
-  let url = format!("{}/repos/{owner}/{repo}/releases/{release_id}", github_api_base());
-  github_client()
-    .delete(&url)
-    .set("Accept", "application/vnd.github+json")
-    .set("Authorization", &format!("Bearer {token}"))
-    .set("X-GitHub-Api-Version", "2022-11-28")
-    .set("User-Agent", "cambi")
-    .call()
-    .map_err(|error| anyhow!("GitHub API error while deleting release {release_id}: {error}"))?;
+/// Release API of a hosting service, bound to a single repository.
+pub trait ReleaseProvider {
+  fn list_releases(&self) -> Result<Vec<ExistingRelease>>;
+  fn create_release(&self, payload: &ReleasePayload) -> Result<PublishedRelease>;
+  fn update_release(&self, release: &ExistingRelease, payload: &ReleasePayload) -> Result<PublishedRelease>;
+  fn delete_release(&self, release: &ExistingRelease) -> Result<()>;
+  /// Deletes the tag `tag` from the remote repository.
+  fn delete_tag(&self, tag: &str) -> Result<()>;
+
+  /// Attaches the file at `path` to `release`, replacing an asset of the same
+  /// name.
+  fn upload_asset(&self, _release: &PublishedRelease, path: &Path) -> Result<()> {
+    Err(anyhow!(
+      "Cannot upload {}: release assets are not supported by this provider",
+      path.display()
+    ))
+  }
 
-  Ok(())
+  /// Whether `upload_asset` is implemented, checked before any release is
+  /// published.
+  fn supports_assets(&self) -> bool {
+    false
+  }
+
+  /// Whether existing releases report their prerelease and draft flags, so
+  /// they can be compared when deciding if a release is up to date.
+  fn tracks_release_flags(&self) -> bool {
+    true
+  }
 }
 
-fn create_release(owner: &str, repo: &str, token: &str, payload: &ReleasePayload) -> Result<()> {
-  let url = format!("{}/repos/{owner}/{repo}/releases", github_api_base());
-  github_client()
-    .post(&url)
-    .set("Accept", "application/vnd.github+json")
-    .set("Authorization", &format!("Bearer {token}"))
-    .set("X-GitHub-Api-Version", "2022-11-28")
-    .set("User-Agent", "cambi")
-    .send_json(serde_json::to_value(payload).context("Cannot serialize release payload")?)
-    .map_err(|error| {
-      anyhow!(
-        "GitHub API error while creating release '{}': {error}",
-        payload.tag_name
-      )
-    })?;
+/// GitHub release provider.
+pub struct GithubProvider {
+  api_base: String,
+  owner: String,
+  repo: String,
+  token: String,
+  offline: bool,
+  max_retries: u32,
+}
 
-  Ok(())
+impl GithubProvider {
+  pub fn new(owner: &str, repo: &str, token: &str, config: &EffectiveConfig) -> Result<Self> {
+    Ok(Self {
+      api_base: config.api_base_url()?,
+      owner: owner.to_string(),
+      repo: repo.to_string(),
+      token: token.to_string(),
+      offline: config.offline,
+      max_retries: config.max_retries,
+    })
+  }
+
+  /// Builds a request for `path` (relative to the repository endpoint), failing
+  /// upfront in offline mode.
+  fn request(&self, method: &str, path: &str) -> Result<ureq::Request> {
+    self.request_url(
+      method,
+      &format!("{}/repos/{}/{}{path}", self.api_base, self.owner, self.repo),
+    )
+  }
+
+  fn request_url(&self, method: &str, url: &str) -> Result<ureq::Request> {
+    if self.offline {
+      return Err(anyhow!("Cannot call the GitHub API in offline mode"));
+    }
+
+    Ok(
+      github_client()
+        .request(method, url)
+        .set("Accept", "application/vnd.github+json")
+        .set("Authorization", &format!("Bearer {}", self.token))
+        .set("X-GitHub-Api-Version", "2022-11-28")
+        .set("User-Agent", "cambi"),
+    )
+  }
+
+  /// Sends `request`, retrying connection errors with exponential backoff (or
+  /// the `Retry-After` delay). Server errors are not retried for POST, so a
+  /// retried creation cannot publish twice.
+  fn call_with_retry(&self, request: ureq::Request, body: RequestBody) -> Result<ureq::Response, Box<ureq::Error>> {
+    let idempotent = request.method() != "POST";
+    let mut attempt = 0;
+
+    loop {
+      let result = match body {
+        RequestBody::Empty => request.clone().call(),
+        RequestBody::Json(value) => request.clone().send_json(value),
+        RequestBody::Bytes(bytes) => request.clone().send_bytes(bytes),
+      };
+
+      let delay = match result {
+        Err(ureq::Error::Status(status, response)) if status >= 500 && idempotent && attempt < self.max_retries => {
+          response
+            .header("Retry-After")
+            .and_then(|seconds| seconds.trim().parse().ok())
+            .map_or_else(|| retry_backoff(attempt), Duration::from_secs)
+        }
+        Err(ureq::Error::Transport(_)) if attempt < self.max_retries => retry_backoff(attempt),
+        result => return result.map_err(Box::new),
+      };
+
+      thread::sleep(delay);
+      attempt += 1;
+    }
+  }
+
+  pub fn fetch_pull_request_labels(&self, number: u64) -> Result<Vec<String>> {
+    let response = self
+      .call_with_retry(self.request("GET", &format!("/pulls/{number}"))?, RequestBody::Empty)
+      .map_err(|error| github_error(*error, &format!("reading pull request #{number}")))?;
+
+    let pull_request = response
+      .into_json::<PullRequest>()
+      .context(format!("Failed to parse GitHub pull request #{number}"))?;
+
+    Ok(pull_request.labels.into_iter().map(|label| label.name).collect())
+  }
 }
 
-fn update_release(owner: &str, repo: &str, token: &str, release_id: u64, payload: &ReleasePayload) -> Result<()> {
-  let url = format!("{}/repos/{owner}/{repo}/releases/{release_id}", github_api_base());
-  github_client()
-    .patch(&url)
-    .set("Accept", "application/vnd.github+json")
-    .set("Authorization", &format!("Bearer {token}"))
-    .set("X-GitHub-Api-Version", "2022-11-28")
-    .set("User-Agent", "cambi")
-    .send_json(serde_json::to_value(payload).context("Cannot serialize release payload")?)
-    .map_err(|error| {
-      anyhow!(
-        "GitHub API error while updating release '{}': {error}",
-        payload.tag_name
+impl ReleaseProvider for GithubProvider {
+  fn list_releases(&self) -> Result<Vec<ExistingRelease>> {
+    let response = self
+      .call_with_retry(self.request("GET", "/releases?per_page=100")?, RequestBody::Empty)
+      .map_err(|error| github_error(*error, "listing releases"))?;
+
+    response
+      .into_json::<Vec<ExistingRelease>>()
+      .context("Failed to parse GitHub release list")
+  }
+
+  fn create_release(&self, payload: &ReleasePayload) -> Result<PublishedRelease> {
+    let body = serde_json::to_value(payload).context("Cannot serialize release payload")?;
+    let response = self
+      .call_with_retry(self.request("POST", "/releases")?, RequestBody::Json(&body))
+      .map_err(|error| github_error(*error, &format!("creating release '{}'", payload.tag_name)))?;
+
+    response
+      .into_json::<PublishedRelease>()
+      .context(format!("Failed to parse GitHub release '{}'", payload.tag_name))
+  }
+
+  fn update_release(&self, release: &ExistingRelease, payload: &ReleasePayload) -> Result<PublishedRelease> {
+    let body = serde_json::to_value(payload).context("Cannot serialize release payload")?;
+    let response = self
+      .call_with_retry(
+        self.request("PATCH", &format!("/releases/{}", release.id))?,
+        RequestBody::Json(&body),
       )
-    })?;
+      .map_err(|error| github_error(*error, &format!("updating release '{}'", payload.tag_name)))?;
 
-  Ok(())
+    response
+      .into_json::<PublishedRelease>()
+      .context(format!("Failed to parse GitHub release '{}'", payload.tag_name))
+  }
+
+  fn delete_release(&self, release: &ExistingRelease) -> Result<()> {
+    let result = self.call_with_retry(
+      self.request("DELETE", &format!("/releases/{}", release.id))?,
+      RequestBody::Empty,
+    );
+
+    match result.map_err(|error| *error) {
+      // A release deleted by an earlier, interrupted run is already gone.
+      Ok(_) | Err(ureq::Error::Status(404, _)) => Ok(()),
+      Err(error) => Err(github_error(error, &format!("deleting release {}", release.id))),
+    }
+  }
+
+  fn delete_tag(&self, tag: &str) -> Result<()> {
+    self
+      .call_with_retry(
+        self.request("DELETE", &format!("/git/refs/tags/{tag}"))?,
+        RequestBody::Empty,
+      )
+      .map_err(|error| github_error(*error, &format!("deleting tag '{tag}'")))?;
+
+    Ok(())
+  }
+
+  fn supports_assets(&self) -> bool {
+    true
+  }
+
+  fn upload_asset(&self, release: &PublishedRelease, path: &Path) -> Result<()> {
+    let name = path
+      .file_name()
+      .and_then(|name| name.to_str())
+      .ok_or(anyhow!("Invalid asset path {}", path.display()))?;
+    let content = fs::read(path).context(format!("Cannot read asset {}", path.display()))?;
+
+    // The upload URL is a URI template like `.../assets{?name,label}` on the
+    // uploads host.
+    let upload_url = release
+      .upload_url
+      .as_deref()
+      .and_then(|url| url.split('{').next())
+      .ok_or(anyhow!("GitHub returned no upload URL for release {}", release.id))?;
+
+    let assets = self
+      .call_with_retry(
+        self.request("GET", &format!("/releases/{}/assets?per_page=100", release.id))?,
+        RequestBody::Empty,
+      )
+      .map_err(|error| github_error(*error, &format!("listing assets of release {}", release.id)))?
+      .into_json::<Vec<ReleaseAsset>>()
+      .context(format!("Failed to parse assets of GitHub release {}", release.id))?;
+
+    // GitHub rejects uploads whose name is already taken.
+    if let Some(asset) = assets.iter().find(|asset| asset.name == name) {
+      self
+        .call_with_retry(
+          self.request("DELETE", &format!("/releases/assets/{}", asset.id))?,
+          RequestBody::Empty,
+        )
+        .map_err(|error| github_error(*error, &format!("deleting asset '{name}'")))?;
+    }
+
+    let upload = self
+      .request_url("POST", &format!("{upload_url}?name={}", percent_encode(name)))?
+      .set("Content-Type", asset_content_type(path));
+
+    self
+      .call_with_retry(upload, RequestBody::Bytes(&content))
+      .map_err(|error| github_error(*error, &format!("uploading asset '{name}'")))?;
+
+    Ok(())
+  }
+}
+
+/// Describes a failed GitHub call, calling out rate limiting (a 403 or 429
+/// with no remaining quota or a `Retry-After` delay) with its reset time.
+fn github_error(error: ureq::Error, action: &str) -> anyhow::Error {
+  if let ureq::Error::Status(403 | 429, response) = &error {
+    let exhausted = response.header("X-RateLimit-Remaining").map(str::trim) == Some("0");
+
+    if exhausted || response.header("Retry-After").is_some() {
+      let reset = response
+        .header("X-RateLimit-Reset")
+        .and_then(|seconds| seconds.trim().parse().ok())
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0));
+
+      return match reset {
+        Some(reset) => {
+          anyhow!(
+            "GitHub rate limit exceeded, resets at {} (while {action})",
+            reset.format("%Y-%m-%d %H:%M:%S UTC")
+          )
+        }
+        None => anyhow!("GitHub rate limit exceeded (while {action})"),
+      };
+    }
+  }
+
+  anyhow!("GitHub API error while {action}: {error}")
+}
+
+/// Body sent by [`GithubProvider::call_with_retry`] on every attempt.
+#[derive(Clone, Copy)]
+enum RequestBody<'a> {
+  Empty,
+  Json(&'a serde_json::Value),
+  Bytes(&'a [u8]),
 }
 
-fn resolve_owner_repo(config: &EffectiveConfig) -> Result<(String, String)> {
+/// Percent-encodes `value` for a URL query value or path segment, keeping
+/// only the unreserved characters.
+pub fn percent_encode(value: &str) -> String {
+  value
+    .bytes()
+    .map(|byte| {
+      match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+        _ => format!("%{byte:02X}"),
+      }
+    })
+    .collect()
+}
+
+fn asset_content_type(path: &Path) -> &'static str {
+  let name = path.to_string_lossy().to_ascii_lowercase();
+  let extension = name.rsplit('.').next().unwrap_or_default();
+
+  match extension {
+    "zip" => "application/zip",
+    "gz" | "tgz" => "application/gzip",
+    "tar" => "application/x-tar",
+    "xz" => "application/x-xz",
+    "json" => "application/json",
+    "txt" | "sha256" | "sha512" | "asc" | "sig" => "text/plain",
+    _ => "application/octet-stream",
+  }
+}
+
+/// Uploads every asset to `release`, attempting all of them before reporting
+/// the ones that failed.
+fn upload_assets(
+  provider: &dyn ReleaseProvider,
+  release: &PublishedRelease,
+  assets: &[PathBuf],
+  report: bool,
+) -> Result<()> {
+  let mut errors = BatchErrors::new(true);
+
+  for asset in assets {
+    let result = provider.upload_asset(release, asset);
+    if report && result.is_ok() {
+      eprintln!("uploaded {}", asset.display());
+    }
+
+    errors.record(result)?;
+  }
+
+  errors.finish()
+}
+
+/// Builds the release provider selected in the configuration.
+fn release_provider(config: &EffectiveConfig, owner: &str, repo: &str) -> Result<Box<dyn ReleaseProvider>> {
+  let token = resolve_token(config)?;
+
+  Ok(match config.provider {
+    Provider::Github => Box::new(GithubProvider::new(owner, repo, &token, config)?),
+    Provider::Gitlab => Box::new(GitlabProvider::new(owner, repo, &token, config)?),
+    Provider::Gitea => return Err(anyhow!("Releases are not supported for Gitea")),
+  })
+}
+
+pub fn resolve_owner_repo(config: &EffectiveConfig) -> Result<(String, String)> {
+  resolve_owner_repo_in(Path::new("."), config)
+}
+
+/// Resolves the owner and the name of the repository at `repo_path`, from the
+/// configuration, its manifests or its origin remote.
+pub fn resolve_owner_repo_in(repo_path: &Path, config: &EffectiveConfig) -> Result<(String, String)> {
   if let (Some(owner), Some(repo)) = (config.owner.clone(), config.repo.clone()) {
     return Ok((owner, repo));
   }
 
-  detect_owner_repo_from_files().ok_or(anyhow!(
-    "Cannot determine GitHub owner/repo. Set CAMBI_OWNER and CAMBI_REPO, or use --owner/--repo."
-  ))
+  let host = config.provider.host();
+  let parse_url = |url: &str| parse_repo_from_url(url, config.web_host());
+
+  if let Some(url) = config.repository_url.as_deref() {
+    return parse_url(url).ok_or(anyhow!("Cannot parse {host} owner/repo from repository_url '{url}'"));
+  }
+
+  detect_owner_repo_from_files(repo_path, &parse_url)
+    .or_else(|| read_origin_url_in(repo_path).as_deref().and_then(parse_url))
+    .ok_or(anyhow!(
+      "Cannot determine {host} owner/repo. Set CAMBI_OWNER and CAMBI_REPO, or use --owner/--repo."
+    ))
 }
 
-fn resolve_token(config: &EffectiveConfig) -> Result<String> {
+pub fn resolve_token(config: &EffectiveConfig) -> Result<String> {
   config.token.clone().ok_or(anyhow!(
-    "Missing GitHub token. Set GH_RELEASE_TOKEN/CAMBI_TOKEN or pass --token."
+    "Missing {} token. Set GH_RELEASE_TOKEN/CAMBI_TOKEN or pass --token.",
+    config.provider.host()
   ))
 }
 
 fn read_required_tags(tag_pattern: &str) -> Result<Vec<GitTag>> {
-  let tags = read_tags(tag_pattern)?;
+  read_required_tags_in(Path::new("."), tag_pattern)
+}
+
+pub fn read_required_tags_in(repo_path: &Path, tag_pattern: &str) -> Result<Vec<GitTag>> {
+  let tags = read_tags_in(repo_path, tag_pattern)?;
   if tags.is_empty() {
     return Err(anyhow!("No matching git tags found for pattern '{}'", tag_pattern));
   }
@@ -295,8 +874,8 @@ fn read_required_tags(tag_pattern: &str) -> Result<Vec<GitTag>> {
 fn resolve_target_candidates(args: &ReleaseArgs, config: &EffectiveConfig) -> Result<Vec<ReleaseCandidate>> {
   if args.rebuild {
     let tags = read_required_tags(&config.tag_pattern)?;
-    let filter = CommitFilter::new(&config.ignore_patterns)?;
-    return build_release_candidates(&tags, &filter);
+    let filter = CommitFilter::from_config(config)?;
+    return build_release_candidates(tags, &filter, config);
   }
 
   if let Some(target) = args.target.as_deref() {
@@ -306,11 +885,16 @@ fn resolve_target_candidates(args: &ReleaseArgs, config: &EffectiveConfig) -> Re
     // version; exact targets intentionally create an empty release body.
     if matches!(normalized_target.as_str(), "major" | "minor" | "patch" | "path") {
       let tags = read_required_tags(&config.tag_pattern)?;
-      let filter = CommitFilter::new(&config.ignore_patterns)?;
-      let mut candidate = build_latest_release_candidate(&tags, &filter)?;
-      let target_version = resolve_release_target(&candidate.title, Some(target))?;
-      candidate.title = target_version.clone();
+      // Anchor tags get no release but still hold the version to bump from.
+      let base = tags
+        .first()
+        .map(|tag| normalize_release_version(&tag.name))
+        .unwrap_or_default();
+      let filter = CommitFilter::from_config(config)?;
+      let mut candidate = build_latest_release_candidate_in(Path::new("."), tags, &filter, config)?;
+      let target_version = resolve_release_target(&base, Some(target))?;
       candidate.tag_name = release_tag(&target_version);
+      candidate.title = release_title(&candidate.tag_name, config.release_title_keep_prefix);
 
       return Ok(vec![candidate]);
     }
@@ -320,17 +904,88 @@ fn resolve_target_candidates(args: &ReleaseArgs, config: &EffectiveConfig) -> Re
 
     return Ok(vec![ReleaseCandidate {
       tag_name: release_tag(&version),
-      title: version,
+      title: release_title(&release_tag(&version), config.release_title_keep_prefix),
       body: render_release_body(&[]),
+      previous_tag: None,
     }]);
   }
 
   let tags = read_required_tags(&config.tag_pattern)?;
-  let filter = CommitFilter::new(&config.ignore_patterns)?;
-  Ok(vec![build_latest_release_candidate(&tags, &filter)?])
+  let filter = CommitFilter::from_config(config)?;
+  Ok(vec![build_latest_release_candidate_in(
+    Path::new("."),
+    tags,
+    &filter,
+    config,
+  )?])
+}
+
+fn confirm(question: &str) -> Result<bool> {
+  eprint!("{question} [y/N] ");
+  io::stderr().flush().context("Cannot write confirmation prompt")?;
+
+  let mut answer = String::new();
+  io::stdin()
+    .lock()
+    .read_line(&mut answer)
+    .context("Cannot read confirmation answer")?;
+
+  Ok(matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes"))
+}
+
+fn execute_release_delete(tag: &str, args: &ReleaseArgs, config: &EffectiveConfig) -> Result<()> {
+  let (owner, repo) = resolve_owner_repo(config)?;
+  let host = config.provider.host();
+  let tag_name = release_tag(tag);
+
+  if args.dry_run {
+    println!("dry-run: would delete {host} release tag={tag_name} for {owner}/{repo}");
+
+    if args.delete_tag {
+      println!("dry-run: would delete tag {tag_name}");
+    }
+
+    return Ok(());
+  }
+
+  let subject = if args.delete_tag {
+    format!("release and tag {tag_name}")
+  } else {
+    format!("release {tag_name}")
+  };
+
+  if !args.yes && !confirm(&format!("Delete the {subject} of {owner}/{repo}?"))? {
+    return Err(anyhow!(
+      "Deletion of the {subject} not confirmed (pass --yes to skip the prompt)"
+    ));
+  }
+
+  let provider = release_provider(config, &owner, &repo)?;
+  let release = provider
+    .list_releases()?
+    .into_iter()
+    .find(|release| release.tag_name == tag_name)
+    .ok_or(anyhow!("No {host} release found for tag '{tag_name}'"))?;
+
+  provider.delete_release(&release)?;
+
+  if args.delete_tag {
+    provider.delete_tag(&tag_name)?;
+    delete_local_tag(&tag_name)?;
+  }
+
+  if config.verbose {
+    eprintln!("Deleted {subject}.");
+  }
+
+  Ok(())
 }
 
 pub fn execute_release_command(args: &ReleaseArgs, config: &EffectiveConfig) -> Result<()> {
+  if let Some(tag) = args.delete.as_deref() {
+    return execute_release_delete(tag, args, config);
+  }
+
   if args.rebuild && args.target.is_some() {
     return Err(anyhow!("Cannot combine --rebuild with an explicit release target"));
   }
@@ -339,7 +994,45 @@ pub fn execute_release_command(args: &ReleaseArgs, config: &EffectiveConfig) ->
     return Err(anyhow!("--prerelease requires an explicit positional release target"));
   }
 
-  let target_candidates = resolve_target_candidates(args, config)?;
+  // Checked before the dry-run, which would otherwise list drafts that cannot
+  // be created.
+  if args.draft && !config.provider.supports_drafts() {
+    return Err(anyhow!(
+      "Draft releases are not supported by {}",
+      config.provider.host()
+    ));
+  }
+
+  if args.print_range {
+    let tags = exclude_anchor_tags(read_required_tags(&config.tag_pattern)?, &config.anchor_tags);
+
+    if let Some(latest_tag) = tags.first() {
+      eprintln!(
+        "{}",
+        describe_tag_range(tags.get(1).map(|tag| tag.name.as_str()), &latest_tag.name)?
+      );
+    }
+  }
+
+  let mut target_candidates = resolve_target_candidates(args, config)?;
+
+  if args.from_changelog {
+    apply_changelog_bodies(&mut target_candidates, config)?;
+  }
+
+  let prerelease_title_pattern = config
+    .prerelease_title_pattern
+    .as_deref()
+    .map(|pattern| Regex::new(pattern).context(format!("Invalid prerelease title regex pattern: {pattern}")))
+    .transpose()?;
+
+  if let Some(footer_path) = args.notes_footer_file.as_deref() {
+    let footer = fs::read_to_string(footer_path).context(format!("Cannot read {}", footer_path.display()))?;
+
+    for candidate in &mut target_candidates {
+      candidate.body = append_notes_footer(&candidate.body, &footer);
+    }
+  }
 
   if args.notes_only {
     println!("{}", target_candidates[0].body);
@@ -347,21 +1040,28 @@ pub fn execute_release_command(args: &ReleaseArgs, config: &EffectiveConfig) ->
   }
 
   let (owner, repo) = resolve_owner_repo(config)?;
+  let host = config.provider.host();
 
   if args.dry_run {
     if args.rebuild {
-      println!("dry-run: would rebuild GitHub releases for {owner}/{repo}");
+      println!("dry-run: would rebuild {host} releases for {owner}/{repo}");
     } else {
-      println!("dry-run: would publish latest GitHub release for {owner}/{repo}");
+      println!("dry-run: would publish latest {host} release for {owner}/{repo}");
     }
 
     for candidate in &target_candidates {
       println!(
-        "dry-run: would upsert release tag={} title={}",
-        candidate.tag_name, candidate.title
+        "dry-run: would upsert release tag={} title={}{}",
+        candidate.tag_name,
+        candidate.title,
+        if args.draft { " draft=true" } else { "" }
       );
     }
 
+    for asset in &args.assets {
+      println!("dry-run: would upload asset {}", asset.display());
+    }
+
     if args.rebuild {
       println!("dry-run: rebuild would delete existing releases not matching git tags");
     }
@@ -369,8 +1069,15 @@ pub fn execute_release_command(args: &ReleaseArgs, config: &EffectiveConfig) ->
     return Ok(());
   }
 
-  let token = resolve_token(config)?;
-  let mut existing = list_releases(&owner, &repo, &token)?;
+  let provider = release_provider(config, &owner, &repo)?;
+  if !args.assets.is_empty() && !provider.supports_assets() {
+    return Err(anyhow!("Release assets are not supported by {host}"));
+  }
+
+  let mut existing = provider.list_releases()?;
+  let mut errors = BatchErrors::new(config.continue_on_error);
+  let report = args.resume || config.verbose;
+  let mut summary = ReleaseSummary::default();
 
   if args.rebuild {
     let target_tags = target_candidates
@@ -380,11 +1087,19 @@ pub fn execute_release_command(args: &ReleaseArgs, config: &EffectiveConfig) ->
 
     for release in &existing {
       if !target_tags.contains(&release.tag_name) {
-        delete_release(&owner, &repo, &token, release.id)?;
+        let result = provider.delete_release(release);
+        if result.is_ok() {
+          if report {
+            eprintln!("deleted {}", release.tag_name);
+          }
+          summary.deleted.push(release.tag_name.clone());
+        }
+
+        errors.record(result)?;
       }
     }
 
-    existing = list_releases(&owner, &repo, &token)?;
+    existing = provider.list_releases()?;
   }
 
   for candidate in &target_candidates {
@@ -392,24 +1107,67 @@ pub fn execute_release_command(args: &ReleaseArgs, config: &EffectiveConfig) ->
       tag_name: candidate.tag_name.clone(),
       name: candidate.title.clone(),
       body: candidate.body.clone(),
-      draft: false,
-      prerelease: args.prerelease,
+      draft: args.draft,
+      prerelease: args.prerelease || is_prerelease_title(&candidate.title, prerelease_title_pattern.as_ref()),
     };
 
-    if let Some(found) = existing.iter().find(|release| release.tag_name == candidate.tag_name) {
+    let result = if let Some(found) = existing.iter().find(|release| release.tag_name == candidate.tag_name) {
       let same_name = found.name.as_deref() == Some(payload.name.as_str());
-      let same_body = found.body.as_deref() == Some(payload.body.as_str());
-      let same_prerelease = found.prerelease == payload.prerelease;
-
-      if same_name && same_body && same_prerelease {
-        continue;
+      let same_body = found
+        .body
+        .as_deref()
+        .is_some_and(|body| normalize_release_body(body) == normalize_release_body(&payload.body));
+      let same_prerelease = !provider.tracks_release_flags() || found.prerelease == payload.prerelease;
+      // A draft is published by a later run without --draft.
+      let same_draft = !provider.tracks_release_flags() || found.draft == payload.draft;
+
+      if same_name && same_body && same_prerelease && same_draft {
+        if report {
+          eprintln!("skipped {} (up to date)", payload.tag_name);
+        }
+        summary.skipped.push(payload.tag_name.clone());
+
+        Ok(PublishedRelease {
+          id: found.id,
+          upload_url: found.upload_url.clone(),
+        })
+      } else {
+        let result = provider.update_release(found, &payload);
+        if result.is_ok() {
+          if report {
+            eprintln!("updated {}", payload.tag_name);
+          }
+          summary.updated.push(payload.tag_name.clone());
+        }
+
+        result
       }
-
-      update_release(&owner, &repo, &token, found.id, &payload)?;
     } else {
-      create_release(&owner, &repo, &token, &payload)?;
+      let result = provider.create_release(&payload);
+      if result.is_ok() {
+        if report {
+          eprintln!("created {}", payload.tag_name);
+        }
+        summary.created.push(payload.tag_name.clone());
+      }
+
+      result
+    };
+
+    match result {
+      Ok(release) => errors.record(upload_assets(provider.as_ref(), &release, &args.assets, report))?,
+      Err(error) => errors.record(Err(error))?,
     }
   }
 
-  Ok(())
+  // With continue_on_error, the summary still records the actions that
+  // succeeded before the failures are reported.
+  if args.json || config.format == OutputFormat::Json {
+    println!(
+      "{}",
+      serde_json::to_string(&summary).context("Cannot serialize the release summary")?
+    );
+  }
+
+  errors.finish()
 }
@@ -0,0 +1,40 @@
+use anyhow::Result;
+
+use crate::{
+  cli::TagsArgs,
+  config::EffectiveConfig,
+  git::{delete_local_tag, read_tags, read_unmatched_tag_names},
+};
+
+/// Lists the local tags matching the tag pattern, latest first. With `--prune`,
+/// lists the ones not matching it instead, deleting them with `--yes`.
+pub fn execute_tags(tags_args: &TagsArgs, config: &EffectiveConfig) -> Result<()> {
+  if !tags_args.prune {
+    for tag in read_tags(&config.tag_pattern)? {
+      println!("{}", tag.name);
+    }
+
+    return Ok(());
+  }
+
+  let unmatched = read_unmatched_tag_names(&config.tag_pattern)?;
+
+  if !tags_args.yes {
+    for name in &unmatched {
+      println!("{name}");
+    }
+
+    if !unmatched.is_empty() {
+      eprintln!("Pass --yes to delete these tags.");
+    }
+
+    return Ok(());
+  }
+
+  for name in &unmatched {
+    delete_local_tag(name)?;
+    println!("Deleted tag {name}.");
+  }
+
+  Ok(())
+}
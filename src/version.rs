@@ -6,19 +6,30 @@ use std::{
 use anyhow::{Context, Result, anyhow};
 use git2::{Repository, Signature, Status, StatusOptions};
 use regex::Regex;
-use semver::Version;
+use semver::{BuildMetadata, Prerelease, Version};
 
 use crate::{
-  changelog::execute_changelog_command,
+  batch::BatchErrors,
+  changelog::{
+    apply_default_sorting, collect_releasable_commits, display_subjects, execute_changelog_command, render_template,
+  },
   cli::{ChangelogArgs, SemverArgs, UpdateArgs, VersionArgs},
-  config::EffectiveConfig,
-  conventional::{BumpLevel, infer_bump},
+  config::{CommitScope, EffectiveConfig, FlutterBuild, OutputFormat},
+  conventional::{BumpLevel, bump_from_labels, commit_type, infer_commit_bump},
+  files::write_atomic,
   filters::CommitFilter,
-  git::{read_commits, read_tags},
+  git::{
+    create_annotated_tag, create_commit, describe_commit_range, push_head, read_commits, read_commits_in, read_tags_in,
+    retain_reachable_tags_in, signing_key, verify_clean_worktree,
+  },
+  release::{GithubProvider, pull_request_number, render_release_body, resolve_owner_repo_in, resolve_token},
 };
 
+const DEFAULT_TAG_MESSAGE_TEMPLATE: &str = "Release $VERSION";
+
 fn bump_semver(current: Version, bump: BumpLevel) -> Version {
   let mut next = current;
+  next.pre = Prerelease::EMPTY;
 
   match bump {
     BumpLevel::Major => {
@@ -38,61 +49,300 @@ fn bump_semver(current: Version, bump: BumpLevel) -> Version {
   next
 }
 
+/// Starts a `<identifier>.1` prerelease of the bumped version, or advances the
+/// prerelease of `current` (`rc.1` to `rc.2`, `beta.2` to `rc.1`) when its
+/// version already carries the bump.
+fn bump_prerelease(current: &Version, bump: BumpLevel, identifier: &str) -> Result<Version> {
+  // A prerelease stands for its upcoming version, which carries some bumps
+  // already: `1.3.0-rc.1` carries a minor bump, but not a major one.
+  let carries_bump = !current.pre.is_empty()
+    && match bump {
+      BumpLevel::Major => current.minor == 0 && current.patch == 0,
+      BumpLevel::Minor => current.patch == 0,
+      BumpLevel::Patch => true,
+    };
+
+  let (mut next, number) = if carries_bump {
+    let number = current
+      .pre
+      .as_str()
+      .strip_prefix(identifier)
+      .and_then(|rest| rest.strip_prefix('.'))
+      .and_then(|rest| rest.parse::<u64>().ok())
+      .map_or(1, |number| number + 1);
+
+    (
+      Version {
+        pre: Prerelease::EMPTY,
+        ..current.clone()
+      },
+      number,
+    )
+  } else {
+    (bump_semver(current.clone(), bump), 1)
+  };
+
+  next.pre = Prerelease::new(&format!("{identifier}.{number}"))
+    .context(format!("Invalid prerelease identifier '{identifier}'"))?;
+
+  if next <= *current {
+    return Err(anyhow!(
+      "Refusing to move prerelease {current} back to {next} (identifiers are compared alphabetically)"
+    ));
+  }
+
+  Ok(next)
+}
+
 pub fn normalize_semver(raw: &str) -> Result<Version> {
   let normalized = raw.trim().trim_start_matches('v').to_string();
   Version::parse(&normalized).context(format!("Invalid semver '{}'", raw.trim()))
 }
 
-pub fn latest_tag_version(tag_pattern: &str) -> Result<Version> {
-  let tags = read_tags(tag_pattern)?;
+/// Returns the version of the latest matching tag, only considering the tags
+/// reachable from HEAD with `reachable_only`.
+fn find_latest_tag_version_in(repo_path: &Path, tag_pattern: &str, reachable_only: bool) -> Result<Option<Version>> {
+  let mut tags = read_tags_in(repo_path, tag_pattern)?;
+  if reachable_only {
+    tags = retain_reachable_tags_in(repo_path, tags)?;
+  }
 
-  for tag in tags {
-    if let Ok(version) = normalize_semver(&tag.name) {
-      return Ok(version);
-    }
+  Ok(tags.iter().find_map(|tag| normalize_semver(&tag.name).ok()))
+}
+
+pub fn latest_tag_version(tag_pattern: &str, reachable_only: bool) -> Result<Version> {
+  Ok(find_latest_tag_version_in(Path::new("."), tag_pattern, reachable_only)?.unwrap_or(Version::new(0, 0, 0)))
+}
+
+/// Returns the version of the latest matching tag, falling back to the
+/// configured current version (`CAMBI_CURRENT_VERSION`) and then `0.0.0`.
+pub fn current_base_version(config: &EffectiveConfig, reachable_only: bool) -> Result<Version> {
+  current_base_version_in(Path::new("."), config, reachable_only)
+}
+
+pub fn current_base_version_in(repo_path: &Path, config: &EffectiveConfig, reachable_only: bool) -> Result<Version> {
+  if let Some(version) = find_latest_tag_version_in(repo_path, &config.tag_pattern, reachable_only)? {
+    return Ok(version);
+  }
+
+  match config.current_version.as_deref() {
+    Some(current_version) => normalize_semver(current_version),
+    None => Ok(Version::new(0, 0, 0)),
+  }
+}
+
+/// Returns the configured initial version when no tag matches yet, so the
+/// first release uses it instead of a bump from `0.0.0`.
+pub fn initial_release_version(config: &EffectiveConfig) -> Result<Option<Version>> {
+  initial_release_version_in(Path::new("."), config)
+}
+
+pub fn initial_release_version_in(repo_path: &Path, config: &EffectiveConfig) -> Result<Option<Version>> {
+  let Some(initial_version) = config.initial_version.as_deref() else {
+    return Ok(None);
+  };
+
+  if find_latest_tag_version_in(repo_path, &config.tag_pattern, false)?.is_some() {
+    return Ok(None);
   }
 
-  Ok(Version::new(0, 0, 0))
+  normalize_semver(initial_version)
+    .map(Some)
+    .context("Invalid initial version")
 }
 
 #[derive(Debug, Clone)]
 pub enum UpdateTarget {
   Bump(BumpLevel),
+  /// An explicit version, rejected unless greater than the current one.
   Exact(Version),
+  /// An explicit version applied even when lower than the current one.
+  Downgrade(Version),
+  /// The current version itself, used to read it without changes.
+  Current,
+  /// A prerelease with this identifier, of the bumped version or advancing the
+  /// current prerelease.
+  Prerelease(BumpLevel, String),
+  /// The current prerelease without its prerelease identifiers.
+  Finalize,
+  /// Another target, with the build metadata of the result replaced.
+  WithBuild(Box<UpdateTarget>, BuildMetadata),
 }
 
-fn resolve_target_version(current: Version, target: &UpdateTarget) -> Version {
+fn resolve_target_version(current: Version, target: &UpdateTarget) -> Result<Version> {
   match target {
-    UpdateTarget::Bump(bump) => bump_semver(current, *bump),
-    UpdateTarget::Exact(version) => version.clone(),
+    UpdateTarget::Bump(bump) => Ok(bump_semver(current, *bump)),
+    UpdateTarget::Exact(version) => {
+      if *version <= current {
+        return Err(anyhow!(
+          "Refusing to set version {version}, which is not greater than current {current} (use --allow-downgrade to \
+           force it)"
+        ));
+      }
+
+      Ok(version.clone())
+    }
+    UpdateTarget::Downgrade(version) => Ok(version.clone()),
+    UpdateTarget::Current => Ok(current),
+    UpdateTarget::Prerelease(bump, identifier) => bump_prerelease(&current, *bump, identifier),
+    UpdateTarget::Finalize => {
+      if current.pre.is_empty() {
+        return Err(anyhow!("Cannot finalize {current}, which is not a prerelease"));
+      }
+
+      Ok(Version {
+        pre: Prerelease::EMPTY,
+        ..current
+      })
+    }
+    UpdateTarget::WithBuild(target, build) => {
+      let mut next = resolve_target_version(current, target)?;
+      next.build = build.clone();
+      Ok(next)
+    }
   }
 }
 
-fn parse_update_target(target: Option<&str>, commits_bump: BumpLevel) -> Result<UpdateTarget> {
+fn parse_update_target(
+  target: Option<&str>,
+  pre: Option<&str>,
+  commits_bump: BumpLevel,
+  allow_downgrade: bool,
+) -> Result<UpdateTarget> {
+  let bump = |level: BumpLevel| {
+    match pre {
+      Some(identifier) => UpdateTarget::Prerelease(level, identifier.to_string()),
+      None => UpdateTarget::Bump(level),
+    }
+  };
+
   let Some(raw_target) = target else {
-    return Ok(UpdateTarget::Bump(commits_bump));
+    return Ok(bump(commits_bump));
   };
 
   match raw_target.to_ascii_lowercase().as_str() {
-    "major" => Ok(UpdateTarget::Bump(BumpLevel::Major)),
-    "minor" => Ok(UpdateTarget::Bump(BumpLevel::Minor)),
-    "patch" => Ok(UpdateTarget::Bump(BumpLevel::Patch)),
+    "major" => Ok(bump(BumpLevel::Major)),
+    "minor" => Ok(bump(BumpLevel::Minor)),
+    "patch" => Ok(bump(BumpLevel::Patch)),
+    "prerelease" => Ok(UpdateTarget::Prerelease(commits_bump, pre.unwrap_or("rc").to_string())),
+    _ if pre.is_some() => {
+      Err(anyhow!(
+        "--pre can only be combined with major, minor, patch or prerelease targets"
+      ))
+    }
+    "release" | "finalize" => Ok(UpdateTarget::Finalize),
+    _ if allow_downgrade => Ok(UpdateTarget::Downgrade(normalize_semver(raw_target)?)),
     _ => Ok(UpdateTarget::Exact(normalize_semver(raw_target)?)),
   }
 }
 
-fn detect_bump(from_tag: Option<&str>, config: &EffectiveConfig) -> Result<BumpLevel> {
-  let commits = read_commits(from_tag, &config.tag_pattern)?;
-  let filter = CommitFilter::new(&config.ignore_patterns)?;
+/// Pending commits by kind, as counted by `semver --stats`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct CommitStats {
+  features: usize,
+  fixes: usize,
+  breaking: usize,
+  other: usize,
+}
 
-  let bump = commits
-    .into_iter()
-    .filter(|commit| !filter.is_ignored(&commit.subject))
-    .map(|commit| infer_bump(&commit.subject, &commit.body))
-    .max()
-    .unwrap_or(BumpLevel::Patch);
+impl CommitStats {
+  fn record(&mut self, subject: &str, level: BumpLevel) {
+    match (level, commit_type(subject)) {
+      (BumpLevel::Major, _) => self.breaking += 1,
+      (_, Some("feat")) => self.features += 1,
+      (_, Some("fix")) => self.fixes += 1,
+      _ => self.other += 1,
+    }
+  }
+
+  fn render(&self) -> String {
+    format!(
+      "features: {}, fixes: {}, breaking: {}, other: {}",
+      self.features, self.fixes, self.breaking, self.other
+    )
+  }
+
+  fn to_json(self) -> serde_json::Value {
+    serde_json::json!({
+      "features": self.features,
+      "fixes": self.fixes,
+      "breaking": self.breaking,
+      "other": self.other,
+    })
+  }
+}
+
+fn detect_bump(from_tag: Option<&str>, config: &EffectiveConfig, use_labels: bool) -> Result<BumpLevel> {
+  detect_bump_in(Path::new("."), from_tag, config, use_labels)
+}
+
+/// Computes the bump of the commits pending in the repository at `repo_path`.
+pub fn detect_bump_in(
+  repo_path: &Path,
+  from_tag: Option<&str>,
+  config: &EffectiveConfig,
+  use_labels: bool,
+) -> Result<BumpLevel> {
+  detect_bump_with_stats_in(repo_path, from_tag, config, use_labels).map(|(bump, _)| bump)
+}
+
+/// Computes the bump of the pending commits and tallies them by kind in the
+/// same pass.
+fn detect_bump_with_stats_in(
+  repo_path: &Path,
+  from_tag: Option<&str>,
+  config: &EffectiveConfig,
+  use_labels: bool,
+) -> Result<(BumpLevel, CommitStats)> {
+  let commits = read_commits_in(
+    repo_path,
+    from_tag,
+    &config.tag_pattern,
+    config.max_commits,
+    &config.paths,
+  )?;
+  let filter = CommitFilter::from_config(config)?;
+  let api = if use_labels {
+    let (owner, repo) = resolve_owner_repo_in(repo_path, config)?;
+    Some(GithubProvider::new(&owner, &repo, &resolve_token(config)?, config)?)
+  } else {
+    None
+  };
+  let mut bump = BumpLevel::Patch;
+  let mut stats = CommitStats::default();
+
+  for commit in commits.iter().filter(|commit| !filter.is_ignored(&commit.subject)) {
+    let mut level = infer_commit_bump(commit, config);
+
+    // Squash-merged subjects keep the PR reference, whose semver:* labels are
+    // more reliable than the conventional type picked at merge time.
+    if let Some(api) = &api
+      && let Some(number) = pull_request_number(&commit.subject)
+    {
+      let labels = api.fetch_pull_request_labels(number)?;
+
+      if let Some(label_level) = bump_from_labels(&labels) {
+        if level == BumpLevel::Major && label_level < level {
+          // A label must not hide a breaking change from the version.
+          eprintln!(
+            "Ignoring label bump {} from pull request #{number}: the commit is a breaking change.",
+            label_level.as_str()
+          );
+        } else {
+          if config.verbose {
+            eprintln!("Using label bump {} from pull request #{number}.", label_level.as_str());
+          }
+
+          level = label_level;
+        }
+      }
+    }
+
+    stats.record(&commit.subject, level);
+    bump = bump.max(level);
+  }
 
-  Ok(bump)
+  Ok((bump, stats))
 }
 
 pub fn update_cargo_toml_version(path: &Path, target: &UpdateTarget) -> Result<String> {
@@ -111,15 +361,185 @@ pub fn update_cargo_toml_version(path: &Path, target: &UpdateTarget) -> Result<S
     .and_then(toml_edit::Item::as_str)
     .ok_or(anyhow!("No package version found in {}", path.display()))?;
 
-  let next = resolve_target_version(normalize_semver(current)?, target);
+  let next = resolve_target_version(normalize_semver(current)?, target)?;
   let next_string = next.to_string();
   doc["package"]["version"] = toml_edit::value(next_string.clone());
 
-  fs::write(path, doc.to_string()).context(format!("Cannot write {}", path.display()))?;
+  write_atomic(path, doc.to_string()).context(format!("Cannot write {}", path.display()))?;
 
   Ok(next_string)
 }
 
+fn find_workspace_root(manifest: &Path) -> Result<Option<(PathBuf, toml_edit::DocumentMut)>> {
+  let manifest = manifest
+    .canonicalize()
+    .context(format!("Cannot resolve {}", manifest.display()))?;
+
+  for dir in manifest.ancestors().skip(1) {
+    let candidate = dir.join("Cargo.toml");
+    if !candidate.exists() {
+      continue;
+    }
+
+    let doc = fs::read_to_string(&candidate)
+      .context(format!("Cannot read {}", candidate.display()))?
+      .parse::<toml_edit::DocumentMut>()
+      .context(format!("Invalid TOML in {}", candidate.display()))?;
+
+    if doc.get("workspace").is_some() {
+      return Ok(Some((dir.to_path_buf(), doc)));
+    }
+  }
+
+  Ok(None)
+}
+
+fn workspace_member_manifests(root: &Path, workspace: &toml_edit::DocumentMut) -> Vec<PathBuf> {
+  let members = workspace
+    .get("workspace")
+    .and_then(|workspace| workspace.get("members"))
+    .and_then(toml_edit::Item::as_array)
+    .map(|members| members.iter().filter_map(|member| member.as_str()).collect::<Vec<_>>())
+    .unwrap_or_default();
+
+  let mut manifests = vec![root.join("Cargo.toml")];
+
+  for member in members {
+    // Only the common `dir/*` glob form is expanded.
+    if let Some(parent) = member.strip_suffix("/*") {
+      let mut entries = fs::read_dir(root.join(parent))
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect::<Vec<_>>())
+        .unwrap_or_default();
+      entries.sort();
+
+      manifests.extend(entries.into_iter().map(|entry| entry.join("Cargo.toml")));
+    } else {
+      manifests.push(root.join(member).join("Cargo.toml"));
+    }
+  }
+
+  manifests.retain(|manifest| manifest.exists());
+  manifests
+}
+
+fn pin_version(current: &str, version: &str) -> String {
+  let operator = current
+    .find(|c: char| c.is_ascii_digit())
+    .map(|index| &current[..index])
+    .unwrap_or_default();
+
+  format!("{operator}{version}")
+}
+
+/// Pins `crate_name` to `version` in a dependency table, keeping the
+/// requirement operator. Returns whether any pin changed.
+fn sync_dependency_table(table: &mut dyn toml_edit::TableLike, crate_name: &str, version: &str) -> bool {
+  let mut changed = false;
+
+  for (key, item) in table.iter_mut() {
+    // Plain `name = "x.y.z"` pins.
+    if let Some(value) = item.as_value_mut()
+      && let Some(current) = value.as_str()
+    {
+      if key.get() == crate_name {
+        let pinned = pin_version(current, version);
+        if pinned != current {
+          let decor = value.decor().clone();
+          *value = toml_edit::Value::from(pinned);
+          *value.decor_mut() = decor;
+          changed = true;
+        }
+      }
+
+      continue;
+    }
+
+    let Some(dependency) = item.as_table_like_mut() else {
+      continue;
+    };
+
+    let package = dependency
+      .get("package")
+      .and_then(toml_edit::Item::as_str)
+      .unwrap_or(key.get());
+    if package != crate_name {
+      continue;
+    }
+
+    if let Some(current) = dependency.get("version").and_then(toml_edit::Item::as_str) {
+      let pinned = pin_version(current, version);
+      if pinned != current {
+        dependency.insert("version", toml_edit::value(pinned));
+        changed = true;
+      }
+    }
+  }
+
+  changed
+}
+
+/// Updates the version pins of `manifest`'s crate in the dependency tables of
+/// the other workspace members and in the `[workspace.dependencies]` of the
+/// root manifest, returning the manifests that changed.
+pub fn sync_workspace_dependency_pins(manifest: &Path, version: &str) -> Result<Vec<PathBuf>> {
+  let content = fs::read_to_string(manifest).context(format!("Cannot read {}", manifest.display()))?;
+  let doc = content
+    .parse::<toml_edit::DocumentMut>()
+    .context(format!("Invalid TOML in {}", manifest.display()))?;
+  let crate_name = doc
+    .get("package")
+    .and_then(|package| package.get("name"))
+    .and_then(toml_edit::Item::as_str)
+    .ok_or(anyhow!("No package name found in {}", manifest.display()))?
+    .to_string();
+
+  let Some((root, workspace)) = find_workspace_root(manifest)? else {
+    return Ok(Vec::new());
+  };
+
+  let bumped_manifest = manifest
+    .canonicalize()
+    .context(format!("Cannot resolve {}", manifest.display()))?;
+  let mut updated = Vec::new();
+
+  for member in workspace_member_manifests(&root, &workspace) {
+    let is_bumped = member.canonicalize().ok().as_deref() == Some(bumped_manifest.as_path());
+
+    let content = fs::read_to_string(&member).context(format!("Cannot read {}", member.display()))?;
+    let mut doc = content
+      .parse::<toml_edit::DocumentMut>()
+      .context(format!("Invalid TOML in {}", member.display()))?;
+    let mut changed = false;
+
+    // A crate does not depend on itself, but the root manifest it may be can
+    // still pin it for the workspace.
+    if !is_bumped {
+      for table_name in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        if let Some(table) = doc.get_mut(table_name).and_then(toml_edit::Item::as_table_like_mut) {
+          changed |= sync_dependency_table(table, &crate_name, version);
+        }
+      }
+    }
+
+    if let Some(table) = doc
+      .get_mut("workspace")
+      .and_then(|workspace| workspace.get_mut("dependencies"))
+      .and_then(toml_edit::Item::as_table_like_mut)
+    {
+      changed |= sync_dependency_table(table, &crate_name, version);
+    }
+
+    if changed {
+      write_atomic(&member, doc.to_string()).context(format!("Cannot write {}", member.display()))?;
+      if !is_bumped {
+        updated.push(member);
+      }
+    }
+  }
+
+  Ok(updated)
+}
+
 fn read_cargo_toml_target_version(path: &Path, target: &UpdateTarget) -> Result<String> {
   let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
   let doc = content
@@ -136,7 +556,7 @@ fn read_cargo_toml_target_version(path: &Path, target: &UpdateTarget) -> Result<
     .and_then(toml_edit::Item::as_str)
     .ok_or(anyhow!("No package version found in {}", path.display()))?;
 
-  Ok(resolve_target_version(normalize_semver(current)?, target).to_string())
+  Ok(resolve_target_version(normalize_semver(current)?, target)?.to_string())
 }
 
 pub fn update_package_json_version(path: &Path, target: &UpdateTarget) -> Result<String> {
@@ -149,7 +569,7 @@ pub fn update_package_json_version(path: &Path, target: &UpdateTarget) -> Result
     .and_then(|value| value.as_str())
     .ok_or(anyhow!("No 'version' field found in {}", path.display()))?;
 
-  let next = resolve_target_version(normalize_semver(current)?, target);
+  let next = resolve_target_version(normalize_semver(current)?, target)?;
 
   let object = json
     .as_object_mut()
@@ -157,7 +577,7 @@ pub fn update_package_json_version(path: &Path, target: &UpdateTarget) -> Result
 
   object.insert("version".to_string(), serde_json::Value::String(next.to_string()));
 
-  fs::write(
+  write_atomic(
     path,
     serde_json::to_string_pretty(&json).context("Cannot serialize package.json")? + "\n",
   )
@@ -176,7 +596,7 @@ fn read_package_json_target_version(path: &Path, target: &UpdateTarget) -> Resul
     .and_then(|value| value.as_str())
     .ok_or(anyhow!("No 'version' field found in {}", path.display()))?;
 
-  Ok(resolve_target_version(normalize_semver(current)?, target).to_string())
+  Ok(resolve_target_version(normalize_semver(current)?, target)?.to_string())
 }
 
 pub fn update_pyproject_toml_version(path: &Path, target: &UpdateTarget) -> Result<String> {
@@ -188,11 +608,11 @@ pub fn update_pyproject_toml_version(path: &Path, target: &UpdateTarget) -> Resu
   if let Some(project_table) = doc.get("project").and_then(toml_edit::Item::as_table_like)
     && let Some(version) = project_table.get("version").and_then(toml_edit::Item::as_str)
   {
-    let next = resolve_target_version(normalize_semver(version)?, target);
+    let next = resolve_target_version(normalize_semver(version)?, target)?;
     let next_string = next.to_string();
     doc["project"]["version"] = toml_edit::value(next_string.clone());
 
-    fs::write(path, doc.to_string()).context(format!("Cannot write {}", path.display()))?;
+    write_atomic(path, doc.to_string()).context(format!("Cannot write {}", path.display()))?;
 
     return Ok(next_string);
   }
@@ -201,11 +621,11 @@ pub fn update_pyproject_toml_version(path: &Path, target: &UpdateTarget) -> Resu
     && let Some(poetry_table) = tool_table.get("poetry").and_then(toml_edit::Item::as_table_like)
     && let Some(version) = poetry_table.get("version").and_then(toml_edit::Item::as_str)
   {
-    let next = resolve_target_version(normalize_semver(version)?, target);
+    let next = resolve_target_version(normalize_semver(version)?, target)?;
     let next_string = next.to_string();
     doc["tool"]["poetry"]["version"] = toml_edit::value(next_string.clone());
 
-    fs::write(path, doc.to_string()).context(format!("Cannot write {}", path.display()))?;
+    write_atomic(path, doc.to_string()).context(format!("Cannot write {}", path.display()))?;
 
     return Ok(next_string);
   }
@@ -225,14 +645,14 @@ fn read_pyproject_toml_target_version(path: &Path, target: &UpdateTarget) -> Res
   if let Some(project_table) = doc.get("project").and_then(toml_edit::Item::as_table_like)
     && let Some(version) = project_table.get("version").and_then(toml_edit::Item::as_str)
   {
-    return Ok(resolve_target_version(normalize_semver(version)?, target).to_string());
+    return Ok(resolve_target_version(normalize_semver(version)?, target)?.to_string());
   }
 
   if let Some(tool_table) = doc.get("tool").and_then(toml_edit::Item::as_table_like)
     && let Some(poetry_table) = tool_table.get("poetry").and_then(toml_edit::Item::as_table_like)
     && let Some(version) = poetry_table.get("version").and_then(toml_edit::Item::as_str)
   {
-    return Ok(resolve_target_version(normalize_semver(version)?, target).to_string());
+    return Ok(resolve_target_version(normalize_semver(version)?, target)?.to_string());
   }
 
   Err(anyhow!(
@@ -241,17 +661,33 @@ fn read_pyproject_toml_target_version(path: &Path, target: &UpdateTarget) -> Res
   ))
 }
 
-pub fn find_gemspec_path() -> Result<PathBuf> {
-  let entries = fs::read_dir(".").context("Cannot read current directory")?;
+/// Finds the only file of the current directory with `extension`, refusing
+/// to guess when there are several.
+fn find_single_file_with_extension(extension: &str) -> Result<Option<PathBuf>> {
+  let mut paths = Vec::new();
 
-  for entry in entries {
+  for entry in fs::read_dir(".").context("Cannot read current directory")? {
     let path = entry.context("Cannot read directory entry")?.path();
-    if path.extension().and_then(|ext| ext.to_str()) == Some("gemspec") {
-      return Ok(path);
+    if file_extension(&path) == extension {
+      paths.push(path);
     }
   }
 
-  Err(anyhow!("No .gemspec file found in current directory"))
+  paths.sort();
+
+  if paths.len() > 1 {
+    let names = paths.iter().map(|path| file_name(path)).collect::<Vec<_>>();
+    return Err(anyhow!(
+      "Multiple .{extension} files found ({}); pass --file to choose one",
+      names.join(", ")
+    ));
+  }
+
+  Ok(paths.pop())
+}
+
+pub fn find_gemspec_path() -> Result<PathBuf> {
+  find_single_file_with_extension("gemspec")?.ok_or(anyhow!("No .gemspec file found in current directory"))
 }
 
 pub fn update_gemspec_version(path: &Path, target: &UpdateTarget) -> Result<String> {
@@ -270,7 +706,7 @@ pub fn update_gemspec_version(path: &Path, target: &UpdateTarget) -> Result<Stri
         .name("version")
         .map(|m| m.as_str())
         .ok_or(anyhow!("Cannot parse spec.version in {}", path.display()))?;
-      let next = resolve_target_version(normalize_semver(current)?, target);
+      let next = resolve_target_version(normalize_semver(current)?, target)?;
       let prefix = captures.name("indent").map(|m| m.as_str()).unwrap_or("");
       let suffix = captures.name("suffix").map(|m| m.as_str()).unwrap_or("");
 
@@ -283,7 +719,7 @@ pub fn update_gemspec_version(path: &Path, target: &UpdateTarget) -> Result<Stri
   }
 
   let updated = updated.ok_or(anyhow!("No spec.version assignment found in {}", path.display()))?;
-  fs::write(path, format!("{}\n", lines.join("\n"))).context(format!("Cannot write {}", path.display()))?;
+  write_atomic(path, format!("{}\n", lines.join("\n"))).context(format!("Cannot write {}", path.display()))?;
 
   Ok(updated)
 }
@@ -299,22 +735,39 @@ fn read_gemspec_target_version(path: &Path, target: &UpdateTarget) -> Result<Str
         .name("version")
         .map(|m| m.as_str())
         .ok_or(anyhow!("Cannot parse spec.version in {}", path.display()))?;
-      return Ok(resolve_target_version(normalize_semver(current)?, target).to_string());
+      return Ok(resolve_target_version(normalize_semver(current)?, target)?.to_string());
     }
   }
 
   Err(anyhow!("No spec.version assignment found in {}", path.display()))
 }
 
+pub fn find_podspec_path() -> Result<PathBuf> {
+  find_single_file_with_extension("podspec")?.ok_or(anyhow!("No .podspec file found in current directory"))
+}
+
+fn podspec_regex() -> Regex {
+  Regex::new(r#"^(?P<prefix>\s*\w+\.version\s*=\s*["'])(?P<version>[^"']+)(?P<suffix>["'].*)$"#)
+    .expect("podspec version regex must compile")
+}
+
+pub fn update_podspec_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  update_version_line(path, target, &podspec_regex(), "s.version = 'x.y.z'")
+}
+
+fn read_podspec_target_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  read_version_line_target_version(path, target, &podspec_regex(), "s.version = 'x.y.z'")
+}
+
 pub fn update_plain_version_file(path: &Path, target: &UpdateTarget, tag_pattern: &str) -> Result<String> {
   let current = if path.exists() {
     normalize_semver(&fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?)?
   } else {
-    latest_tag_version(tag_pattern)?
+    latest_tag_version(tag_pattern, false)?
   };
 
-  let next = resolve_target_version(current, target);
-  fs::write(path, format!("{}\n", next)).context(format!("Cannot write {}", path.display()))?;
+  let next = resolve_target_version(current, target)?;
+  write_atomic(path, format!("{}\n", next)).context(format!("Cannot write {}", path.display()))?;
 
   Ok(next.to_string())
 }
@@ -323,10 +776,10 @@ fn read_plain_version_file_target_version(path: &Path, target: &UpdateTarget, ta
   let current = if path.exists() {
     normalize_semver(&fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?)?
   } else {
-    latest_tag_version(tag_pattern)?
+    latest_tag_version(tag_pattern, false)?
   };
 
-  Ok(resolve_target_version(current, target).to_string())
+  Ok(resolve_target_version(current, target)?.to_string())
 }
 
 pub fn update_mix_exs_version(path: &Path, target: &UpdateTarget) -> Result<String> {
@@ -345,7 +798,7 @@ pub fn update_mix_exs_version(path: &Path, target: &UpdateTarget) -> Result<Stri
         .name("version")
         .map(|m| m.as_str())
         .ok_or(anyhow!("Cannot parse version in {}", path.display()))?;
-      let next = resolve_target_version(normalize_semver(current)?, target);
+      let next = resolve_target_version(normalize_semver(current)?, target)?;
       let prefix = captures.name("prefix").map(|m| m.as_str()).unwrap_or("");
       let suffix = captures.name("suffix").map(|m| m.as_str()).unwrap_or("");
 
@@ -358,7 +811,7 @@ pub fn update_mix_exs_version(path: &Path, target: &UpdateTarget) -> Result<Stri
   }
 
   let updated = updated.ok_or(anyhow!("No version: field found in {}", path.display()))?;
-  fs::write(path, format!("{}\n", lines.join("\n"))).context(format!("Cannot write {}", path.display()))?;
+  write_atomic(path, format!("{}\n", lines.join("\n"))).context(format!("Cannot write {}", path.display()))?;
 
   Ok(updated)
 }
@@ -374,14 +827,45 @@ fn read_mix_exs_target_version(path: &Path, target: &UpdateTarget) -> Result<Str
         .name("version")
         .map(|m| m.as_str())
         .ok_or(anyhow!("Cannot parse version in {}", path.display()))?;
-      return Ok(resolve_target_version(normalize_semver(current)?, target).to_string());
+      return Ok(resolve_target_version(normalize_semver(current)?, target)?.to_string());
     }
   }
 
   Err(anyhow!("No version: field found in {}", path.display()))
 }
 
-pub fn update_pubspec_yaml_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+/// Resolves the next Flutter version, bumping the semver part and updating the
+/// `+build` number according to `flutter_build`. Explicit targets carrying
+/// their own build number are used as is.
+fn resolve_flutter_target_version(current: &str, target: &UpdateTarget, flutter_build: FlutterBuild) -> Result<String> {
+  let mut current = normalize_semver(current)?;
+
+  if matches!(target, UpdateTarget::Current) {
+    return Ok(current.to_string());
+  }
+
+  let build = std::mem::replace(&mut current.build, BuildMetadata::EMPTY);
+  let mut next = resolve_target_version(current.clone(), target)?;
+
+  if build.is_empty() || !next.build.is_empty() {
+    return Ok(next.to_string());
+  }
+
+  let number = build.as_str().parse::<u64>().map_err(|_| {
+    anyhow!("Invalid Flutter build number '{build}' in {current}+{build} (expected an integer after '+')")
+  })?;
+
+  let number = match flutter_build {
+    FlutterBuild::Increment => number + 1,
+    FlutterBuild::Reset if next != current => 1,
+    FlutterBuild::Reset | FlutterBuild::Keep => number,
+  };
+
+  next.build = BuildMetadata::new(&number.to_string()).context("Invalid Flutter build number")?;
+  Ok(next.to_string())
+}
+
+pub fn update_pubspec_yaml_version(path: &Path, target: &UpdateTarget, flutter_build: FlutterBuild) -> Result<String> {
   let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
   let mut parsed: serde_yaml::Value =
     serde_yaml::from_str(&content).context(format!("Invalid YAML in {}", path.display()))?;
@@ -391,27 +875,31 @@ pub fn update_pubspec_yaml_version(path: &Path, target: &UpdateTarget) -> Result
     .and_then(serde_yaml::Value::as_str)
     .ok_or(anyhow!("No 'version' field found in {}", path.display()))?;
 
-  let next = resolve_target_version(normalize_semver(current)?, target);
+  let next = resolve_flutter_target_version(current, target, flutter_build)
+    .context(format!("Cannot bump the version of {}", path.display()))?;
 
-  let map = parsed
-    .as_mapping_mut()
-    .ok_or(anyhow!("{} must contain a top-level mapping", path.display()))?;
+  let updated = match replace_yaml_scalar(&content, "version", current, &next) {
+    Some(updated) => updated,
+    None => {
+      let map = parsed
+        .as_mapping_mut()
+        .ok_or(anyhow!("{} must contain a top-level mapping", path.display()))?;
 
-  map.insert(
-    serde_yaml::Value::String("version".to_string()),
-    serde_yaml::Value::String(next.to_string()),
-  );
+      map.insert(
+        serde_yaml::Value::String("version".to_string()),
+        serde_yaml::Value::String(next.clone()),
+      );
 
-  fs::write(
-    path,
-    serde_yaml::to_string(&parsed).context("Cannot serialize pubspec.yaml")?,
-  )
-  .context(format!("Cannot write {}", path.display()))?;
+      serde_yaml::to_string(&parsed).context("Cannot serialize pubspec.yaml")?
+    }
+  };
 
-  Ok(next.to_string())
+  write_atomic(path, updated).context(format!("Cannot write {}", path.display()))?;
+
+  Ok(next)
 }
 
-fn read_pubspec_yaml_target_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+fn read_pubspec_yaml_target_version(path: &Path, target: &UpdateTarget, flutter_build: FlutterBuild) -> Result<String> {
   let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
   let parsed: serde_yaml::Value =
     serde_yaml::from_str(&content).context(format!("Invalid YAML in {}", path.display()))?;
@@ -425,7 +913,219 @@ fn read_pubspec_yaml_target_version(path: &Path, target: &UpdateTarget) -> Resul
     return Err(anyhow!("{} must contain a top-level mapping", path.display()));
   }
 
-  Ok(resolve_target_version(normalize_semver(current)?, target).to_string())
+  resolve_flutter_target_version(current, target, flutter_build)
+    .context(format!("Cannot bump the version of {}", path.display()))
+}
+
+fn json_pointer(key_path: &str) -> String {
+  key_path
+    .split('.')
+    .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+    .collect()
+}
+
+pub fn update_nested_json_version(path: &Path, target: &UpdateTarget, key_path: &str) -> Result<String> {
+  let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
+  let mut json: serde_json::Value =
+    serde_json::from_str(&content).context(format!("Invalid JSON in {}", path.display()))?;
+
+  let field = json
+    .pointer_mut(&json_pointer(key_path))
+    .ok_or(anyhow!("No '{key_path}' field found in {}", path.display()))?;
+
+  let current = field
+    .as_str()
+    .ok_or(anyhow!("Field '{key_path}' in {} is not a string", path.display()))?;
+
+  let next = resolve_target_version(normalize_semver(current)?, target)?;
+  *field = serde_json::Value::String(next.to_string());
+
+  write_atomic(
+    path,
+    serde_json::to_string_pretty(&json).context(format!("Cannot serialize {}", path.display()))? + "\n",
+  )
+  .context(format!("Cannot write {}", path.display()))?;
+
+  Ok(next.to_string())
+}
+
+fn read_nested_json_target_version(path: &Path, target: &UpdateTarget, key_path: &str) -> Result<String> {
+  let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
+  let json: serde_json::Value =
+    serde_json::from_str(&content).context(format!("Invalid JSON in {}", path.display()))?;
+
+  let current = json
+    .pointer(&json_pointer(key_path))
+    .ok_or(anyhow!("No '{key_path}' field found in {}", path.display()))?
+    .as_str()
+    .ok_or(anyhow!("Field '{key_path}' in {} is not a string", path.display()))?;
+
+  Ok(resolve_target_version(normalize_semver(current)?, target)?.to_string())
+}
+
+/// Locates the string at the dotted `key_path` of a TOML document, returning
+/// its current value and the field to overwrite.
+fn toml_version_field<'a>(
+  doc: &'a mut toml_edit::DocumentMut,
+  key_path: &str,
+  path: &Path,
+) -> Result<(String, &'a mut toml_edit::Value)> {
+  let field = key_path
+    .split('.')
+    .try_fold(doc.as_item_mut(), |current, segment| current.get_mut(segment))
+    .and_then(toml_edit::Item::as_value_mut)
+    .ok_or(anyhow!("No '{key_path}' field found in {}", path.display()))?;
+
+  let current = field
+    .as_str()
+    .ok_or(anyhow!("Field '{key_path}' in {} is not a string", path.display()))?
+    .to_string();
+
+  Ok((current, field))
+}
+
+/// Updates the version at the dotted `key_path` of any TOML file, keeping
+/// the formatting and comments around it.
+pub fn update_toml_version(path: &Path, target: &UpdateTarget, key_path: &str) -> Result<String> {
+  let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
+  let mut doc = content
+    .parse::<toml_edit::DocumentMut>()
+    .context(format!("Invalid TOML in {}", path.display()))?;
+
+  let (current, field) = toml_version_field(&mut doc, key_path, path)?;
+  let next = resolve_target_version(normalize_semver(&current)?, target)?;
+  let decor = field.decor().clone();
+  *field = toml_edit::Value::from(next.to_string());
+  *field.decor_mut() = decor;
+
+  write_atomic(path, doc.to_string()).context(format!("Cannot write {}", path.display()))?;
+
+  Ok(next.to_string())
+}
+
+fn read_toml_target_version(path: &Path, target: &UpdateTarget, key_path: &str) -> Result<String> {
+  let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
+  let mut doc = content
+    .parse::<toml_edit::DocumentMut>()
+    .context(format!("Invalid TOML in {}", path.display()))?;
+
+  let (current, _) = toml_version_field(&mut doc, key_path, path)?;
+  Ok(resolve_target_version(normalize_semver(&current)?, target)?.to_string())
+}
+
+fn yaml_field_mut<'a>(value: &'a mut serde_yaml::Value, key_path: &str) -> Option<&'a mut serde_yaml::Value> {
+  key_path
+    .split('.')
+    .try_fold(value, |current, segment| current.get_mut(segment))
+}
+
+/// Finds the line holding the dotted `key_path` of a block-style YAML
+/// document, following the indentation of each nested mapping.
+fn find_yaml_field_line(lines: &[&str], key_path: &str) -> Option<usize> {
+  let segments = key_path.split('.').collect::<Vec<_>>();
+  let mut depth = 0;
+  let mut parent_indent: Option<usize> = None;
+  let mut child_indent: Option<usize> = None;
+
+  for (index, line) in lines.iter().enumerate() {
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("---") {
+      continue;
+    }
+
+    let indent = line.len() - trimmed.len();
+    if parent_indent.is_some_and(|parent| indent <= parent) {
+      return None;
+    }
+
+    // Deeper lines belong to a sibling mapping.
+    if *child_indent.get_or_insert(indent) != indent {
+      continue;
+    }
+
+    let Some(rest) = trimmed
+      .strip_prefix(segments[depth])
+      .and_then(|rest| rest.strip_prefix(':'))
+    else {
+      continue;
+    };
+
+    if depth + 1 == segments.len() {
+      return Some(index);
+    }
+
+    let rest = rest.trim_start();
+    if !rest.is_empty() && !rest.starts_with('#') {
+      return None;
+    }
+
+    depth += 1;
+    parent_indent = Some(indent);
+    child_indent = None;
+  }
+
+  None
+}
+
+/// Replaces the `current` scalar at `key_path` with `next` on its own line,
+/// keeping comments and formatting. Returns `None` for layouts it cannot
+/// edit in place, like flow mappings.
+fn replace_yaml_scalar(content: &str, key_path: &str, current: &str, next: &str) -> Option<String> {
+  let value = Regex::new(r#"^(?P<prefix>[^:]+:\s*["']?)(?P<version>[^"'\s#]+)(?P<suffix>.*)$"#)
+    .expect("YAML scalar regex must compile");
+  let mut lines = content.split_inclusive('\n').collect::<Vec<_>>();
+  let index = find_yaml_field_line(&lines, key_path)?;
+
+  let line = lines[index];
+  let (body, ending) = line.split_at(line.trim_end_matches(['\r', '\n']).len());
+  let captures = value
+    .captures(body)
+    .filter(|captures| &captures["version"] == current)?;
+  let replaced = format!("{}{next}{}{ending}", &captures["prefix"], &captures["suffix"]);
+  lines[index] = &replaced;
+
+  Some(lines.concat())
+}
+
+pub fn update_nested_yaml_version(path: &Path, target: &UpdateTarget, key_path: &str) -> Result<String> {
+  let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
+  let mut parsed: serde_yaml::Value =
+    serde_yaml::from_str(&content).context(format!("Invalid YAML in {}", path.display()))?;
+
+  let field =
+    yaml_field_mut(&mut parsed, key_path).ok_or(anyhow!("No '{key_path}' field found in {}", path.display()))?;
+
+  let current = field
+    .as_str()
+    .ok_or(anyhow!("Field '{key_path}' in {} is not a string", path.display()))?;
+
+  let next = resolve_target_version(normalize_semver(current)?, target)?;
+
+  // Reserializing drops comments, so it is only the fallback.
+  let updated = match replace_yaml_scalar(&content, key_path, current, &next.to_string()) {
+    Some(updated) => updated,
+    None => {
+      *field = serde_yaml::Value::String(next.to_string());
+      serde_yaml::to_string(&parsed).context(format!("Cannot serialize {}", path.display()))?
+    }
+  };
+
+  write_atomic(path, updated).context(format!("Cannot write {}", path.display()))?;
+
+  Ok(next.to_string())
+}
+
+fn read_nested_yaml_target_version(path: &Path, target: &UpdateTarget, key_path: &str) -> Result<String> {
+  let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
+  let mut parsed: serde_yaml::Value =
+    serde_yaml::from_str(&content).context(format!("Invalid YAML in {}", path.display()))?;
+
+  let current = yaml_field_mut(&mut parsed, key_path)
+    .ok_or(anyhow!("No '{key_path}' field found in {}", path.display()))?
+    .as_str()
+    .ok_or(anyhow!("Field '{key_path}' in {} is not a string", path.display()))?;
+
+  Ok(resolve_target_version(normalize_semver(current)?, target)?.to_string())
 }
 
 pub fn update_package_swift_version(path: &Path, target: &UpdateTarget) -> Result<String> {
@@ -447,7 +1147,7 @@ pub fn update_package_swift_version(path: &Path, target: &UpdateTarget) -> Resul
         .name("version")
         .map(|m| m.as_str())
         .ok_or(anyhow!("Cannot parse version in {}", path.display()))?;
-      let next = resolve_target_version(normalize_semver(current)?, target);
+      let next = resolve_target_version(normalize_semver(current)?, target)?;
       let prefix = captures.name("prefix").map(|m| m.as_str()).unwrap_or("");
       let suffix = captures.name("suffix").map(|m| m.as_str()).unwrap_or("");
 
@@ -466,7 +1166,7 @@ pub fn update_package_swift_version(path: &Path, target: &UpdateTarget) -> Resul
     )
   })?;
 
-  fs::write(path, format!("{}\n", lines.join("\n"))).context(format!("Cannot write {}", path.display()))?;
+  write_atomic(path, format!("{}\n", lines.join("\n"))).context(format!("Cannot write {}", path.display()))?;
 
   Ok(updated)
 }
@@ -484,7 +1184,7 @@ fn read_package_swift_target_version(path: &Path, target: &UpdateTarget) -> Resu
         .name("version")
         .map(|m| m.as_str())
         .ok_or(anyhow!("Cannot parse version in {}", path.display()))?;
-      return Ok(resolve_target_version(normalize_semver(current)?, target).to_string());
+      return Ok(resolve_target_version(normalize_semver(current)?, target)?.to_string());
     }
   }
 
@@ -494,7 +1194,348 @@ fn read_package_swift_target_version(path: &Path, target: &UpdateTarget) -> Resu
   ))
 }
 
-fn commit_updated_paths(paths: &[PathBuf], commit_message: &str) -> Result<()> {
+fn gradle_properties_regex() -> Regex {
+  Regex::new(r"^(?P<prefix>\s*version\s*[=:]\s*)(?P<version>\S+)(?P<suffix>\s*)$")
+    .expect("gradle.properties version regex must compile")
+}
+
+fn gradle_build_regex() -> Regex {
+  // Covers both the Groovy (version = '1.2.3') and the Kotlin DSL
+  // (version = "1.2.3") assignments.
+  Regex::new(r#"^(?P<prefix>\s*version\s*=\s*["'])(?P<version>[^"']+)(?P<suffix>["']\s*)$"#)
+    .expect("build.gradle version regex must compile")
+}
+
+fn update_version_line(path: &Path, target: &UpdateTarget, version_line: &Regex, expected: &str) -> Result<String> {
+  let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
+
+  let mut lines = Vec::new();
+  let mut updated: Option<String> = None;
+
+  for line in content.lines() {
+    if updated.is_none()
+      && let Some(captures) = version_line.captures(line)
+    {
+      let current = captures
+        .name("version")
+        .map(|m| m.as_str())
+        .ok_or(anyhow!("Cannot parse version in {}", path.display()))?;
+      let next = resolve_target_version(normalize_semver(current)?, target)?;
+      let prefix = captures.name("prefix").map(|m| m.as_str()).unwrap_or("");
+      let suffix = captures.name("suffix").map(|m| m.as_str()).unwrap_or("");
+
+      lines.push(format!("{prefix}{next}{suffix}"));
+      updated = Some(next.to_string());
+      continue;
+    }
+
+    lines.push(line.to_string());
+  }
+
+  let updated = updated.ok_or_else(|| {
+    anyhow!(
+      "No version assignment found in {} (expected {expected})",
+      path.display()
+    )
+  })?;
+
+  write_atomic(path, format!("{}\n", lines.join("\n"))).context(format!("Cannot write {}", path.display()))?;
+
+  Ok(updated)
+}
+
+fn read_version_line_target_version(
+  path: &Path,
+  target: &UpdateTarget,
+  version_line: &Regex,
+  expected: &str,
+) -> Result<String> {
+  let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
+
+  for line in content.lines() {
+    if let Some(captures) = version_line.captures(line) {
+      let current = captures
+        .name("version")
+        .map(|m| m.as_str())
+        .ok_or(anyhow!("Cannot parse version in {}", path.display()))?;
+      return Ok(resolve_target_version(normalize_semver(current)?, target)?.to_string());
+    }
+  }
+
+  Err(anyhow!(
+    "No version assignment found in {} (expected {expected})",
+    path.display()
+  ))
+}
+
+fn setup_py_regex() -> Regex {
+  Regex::new(r#"^(?P<prefix>.*\bversion\s*=\s*["'])(?P<version>[^"']+)(?P<suffix>["'].*)$"#)
+    .expect("setup.py version regex must compile")
+}
+
+fn setup_cfg_regex() -> Regex {
+  Regex::new(r"^(?P<prefix>\s*version\s*[=:]\s*)(?P<version>\S.*?)(?P<suffix>\s*)$")
+    .expect("setup.cfg version regex must compile")
+}
+
+/// Finds the `version` key of the `[metadata]` section of a `setup.cfg`,
+/// returning its line index, the text before and after the value, and the
+/// value itself.
+fn locate_setup_cfg_version(content: &str) -> Option<(usize, String, String, String)> {
+  let version_line = setup_cfg_regex();
+  let mut in_metadata = false;
+
+  for (index, line) in content.lines().enumerate() {
+    let trimmed = line.trim();
+    if trimmed.starts_with('[') {
+      in_metadata = trimmed == "[metadata]";
+      continue;
+    }
+
+    if !in_metadata {
+      continue;
+    }
+
+    if let Some(captures) = version_line.captures(line) {
+      let capture = |name: &str| captures.name(name).map(|m| m.as_str()).unwrap_or_default().to_string();
+      return Some((index, capture("prefix"), capture("version"), capture("suffix")));
+    }
+  }
+
+  None
+}
+
+fn find_setup_cfg_version(path: &Path, content: &str) -> Result<(usize, String, String, String)> {
+  let found = locate_setup_cfg_version(content).ok_or(anyhow!(
+    "No version found in the [metadata] section of {}",
+    path.display()
+  ))?;
+
+  if found.2.starts_with("attr:") {
+    return Err(anyhow!(
+      "{} reads the version with '{}'; edit it in the source file instead",
+      path.display(),
+      found.2
+    ));
+  }
+
+  Ok(found)
+}
+
+/// setup.cfg, when it declares a `[metadata]` version; projects keeping the
+/// version in setup.py only fall through to it.
+fn find_setup_cfg_path() -> Option<PathBuf> {
+  let path = PathBuf::from("setup.cfg");
+  let content = fs::read_to_string(&path).ok()?;
+  locate_setup_cfg_version(&content).map(|_| path)
+}
+
+pub fn update_setup_cfg_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
+  let (index, prefix, current, suffix) = find_setup_cfg_version(path, &content)?;
+  let next = resolve_target_version(normalize_semver(&current)?, target)?;
+
+  let mut lines = content.lines().map(ToOwned::to_owned).collect::<Vec<_>>();
+  lines[index] = format!("{prefix}{next}{suffix}");
+
+  write_atomic(path, format!("{}\n", lines.join("\n"))).context(format!("Cannot write {}", path.display()))?;
+
+  Ok(next.to_string())
+}
+
+fn read_setup_cfg_target_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
+  let (_, _, current, _) = find_setup_cfg_version(path, &content)?;
+
+  Ok(resolve_target_version(normalize_semver(&current)?, target)?.to_string())
+}
+
+fn sbt_regex() -> Regex {
+  Regex::new(r#"^(?P<prefix>\s*(?:ThisBuild\s*/\s*)?version\s*:=\s*")(?P<version>[^"]+)(?P<suffix>".*)$"#)
+    .expect("build.sbt version regex must compile")
+}
+
+fn makefile_regex() -> Regex {
+  Regex::new(r"^(?P<prefix>VERSION\s*[:?]?=\s*)(?P<version>\S+)(?P<suffix>\s*)$")
+    .expect("Makefile version regex must compile")
+}
+
+fn dunder_version_regex() -> Regex {
+  Regex::new(r#"^(?P<prefix>\s*__version__\s*=\s*["'])(?P<version>[^"']+)(?P<suffix>["'].*)$"#)
+    .expect("__version__ regex must compile")
+}
+
+pub fn update_setup_py_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  update_version_line(path, target, &setup_py_regex(), "version=\"x.y.z\"")
+}
+
+fn read_setup_py_target_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  read_version_line_target_version(path, target, &setup_py_regex(), "version=\"x.y.z\"")
+}
+
+pub fn update_sbt_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  update_version_line(path, target, &sbt_regex(), "version := \"x.y.z\"")
+}
+
+fn read_sbt_target_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  read_version_line_target_version(path, target, &sbt_regex(), "version := \"x.y.z\"")
+}
+
+/// Updates the `VERSION` variable of a Makefile, keeping its assignment
+/// operator (`=`, `:=` or `?=`).
+pub fn update_makefile_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  update_version_line(path, target, &makefile_regex(), "VERSION := x.y.z")
+}
+
+fn read_makefile_target_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  read_version_line_target_version(path, target, &makefile_regex(), "VERSION := x.y.z")
+}
+
+pub fn update_dunder_version_file(path: &Path, target: &UpdateTarget) -> Result<String> {
+  update_version_line(path, target, &dunder_version_regex(), "__version__ = \"x.y.z\"")
+}
+
+fn read_dunder_version_target_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  read_version_line_target_version(path, target, &dunder_version_regex(), "__version__ = \"x.y.z\"")
+}
+
+fn nimble_regex() -> Regex {
+  Regex::new(r#"^(?P<prefix>\s*version\s*=\s*")(?P<version>[^"]+)(?P<suffix>".*)$"#)
+    .expect(".nimble version regex must compile")
+}
+
+pub fn update_nimble_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  update_version_line(path, target, &nimble_regex(), "version = \"x.y.z\"")
+}
+
+fn read_nimble_target_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  read_version_line_target_version(path, target, &nimble_regex(), "version = \"x.y.z\"")
+}
+
+pub fn update_gradle_properties_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  update_version_line(path, target, &gradle_properties_regex(), "version=x.y.z")
+}
+
+fn read_gradle_properties_target_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  read_version_line_target_version(path, target, &gradle_properties_regex(), "version=x.y.z")
+}
+
+pub fn update_gradle_build_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  update_version_line(path, target, &gradle_build_regex(), "version = \"x.y.z\"")
+}
+
+fn read_gradle_build_target_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  read_version_line_target_version(path, target, &gradle_build_regex(), "version = \"x.y.z\"")
+}
+
+/// Finds the Gradle file holding the version: `gradle.properties` when it
+/// declares one, then `build.gradle.kts` and `build.gradle`.
+fn find_gradle_version_file() -> Option<PathBuf> {
+  let properties = Path::new("gradle.properties");
+  let declares_version = fs::read_to_string(properties)
+    .is_ok_and(|content| content.lines().any(|line| gradle_properties_regex().is_match(line)));
+
+  if declares_version {
+    return Some(properties.to_path_buf());
+  }
+
+  ["build.gradle.kts", "build.gradle", "gradle.properties"]
+    .into_iter()
+    .map(PathBuf::from)
+    .find(|path| path.exists())
+}
+
+struct DebianChangelogEntry {
+  package: String,
+  epoch: Option<String>,
+  upstream: Version,
+  revision: Option<String>,
+  trailer: String,
+  maintainer: String,
+}
+
+fn parse_debian_changelog_entry(path: &Path, content: &str) -> Result<DebianChangelogEntry> {
+  let header = Regex::new(r"^(?P<package>\S+)\s+\((?P<version>[^)]+)\)(?P<trailer>.*)$")
+    .expect("debian changelog header regex must compile");
+  let signature = Regex::new(r"^ -- (?P<maintainer>.+?)  \S").expect("debian changelog signature regex must compile");
+
+  let first_line = content
+    .lines()
+    .find(|line| !line.trim().is_empty())
+    .ok_or(anyhow!("No entries found in {}", path.display()))?;
+  let captures = header
+    .captures(first_line)
+    .ok_or(anyhow!("Cannot parse the top entry header in {}", path.display()))?;
+
+  let raw_version = &captures["version"];
+
+  // Debian versions are [epoch:]upstream[-revision]; only the upstream part is
+  // semver and gets bumped.
+  let (epoch, rest) = match raw_version.split_once(':') {
+    Some((epoch, rest)) if epoch.chars().all(|c| c.is_ascii_digit()) => (Some(epoch.to_string()), rest),
+    _ => (None, raw_version),
+  };
+  let (upstream, revision) = match rest.rsplit_once('-') {
+    Some((upstream, revision)) => (upstream, Some(revision.to_string())),
+    None => (rest, None),
+  };
+
+  let maintainer = content
+    .lines()
+    .find_map(|line| signature.captures(line))
+    .map(|captures| captures["maintainer"].to_string())
+    .ok_or(anyhow!("No maintainer signature found in {}", path.display()))?;
+
+  Ok(DebianChangelogEntry {
+    package: captures["package"].to_string(),
+    epoch,
+    upstream: normalize_semver(upstream)?,
+    revision,
+    trailer: captures["trailer"].to_string(),
+    maintainer,
+  })
+}
+
+pub fn update_debian_changelog_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
+  let entry = parse_debian_changelog_entry(path, &content)?;
+  let next = resolve_target_version(entry.upstream, target)?;
+
+  let mut version = next.to_string();
+  if let Some(epoch) = entry.epoch {
+    version = format!("{epoch}:{version}");
+  }
+  if let Some(revision) = entry.revision {
+    version = format!("{version}-{revision}");
+  }
+
+  let stanza = format!(
+    "{} ({version}){}\n\n  * New upstream release {next}.\n\n -- {}  {}\n",
+    entry.package,
+    entry.trailer,
+    entry.maintainer,
+    chrono::Utc::now().to_rfc2822()
+  );
+
+  write_atomic(path, format!("{stanza}\n{}", content.trim_start()))
+    .context(format!("Cannot write {}", path.display()))?;
+
+  Ok(next.to_string())
+}
+
+fn read_debian_changelog_target_version(path: &Path, target: &UpdateTarget) -> Result<String> {
+  let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
+  let entry = parse_debian_changelog_entry(path, &content)?;
+
+  Ok(resolve_target_version(entry.upstream, target)?.to_string())
+}
+
+fn commit_updated_paths(
+  paths: &[PathBuf],
+  commit_message: &str,
+  scope: CommitScope,
+  signing_key: Option<&str>,
+) -> Result<()> {
   let repo = Repository::discover(".").context("Failed to discover git repository")?;
 
   let workdir = repo.workdir().ok_or(anyhow!("Repository has no working directory"))?;
@@ -517,28 +1558,30 @@ fn commit_updated_paths(paths: &[PathBuf], commit_message: &str) -> Result<()> {
 
   let mut index = repo.index().context("Cannot open git index")?;
 
-  let mut options = StatusOptions::new();
-  options.include_untracked(false).recurse_untracked_dirs(false);
-  let statuses = repo.statuses(Some(&mut options)).context("Failed to read git status")?;
-
-  // Preserve the existing auto-commit behavior: include all tracked dirty files,
-  // not only the version files explicitly touched by this command.
-  for entry in statuses.iter() {
-    let Some(path) = entry.path() else {
-      continue;
-    };
-
-    let status = entry.status();
-    let path = Path::new(path);
-
-    if status.contains(Status::WT_DELETED) || status.contains(Status::INDEX_DELETED) {
-      index
-        .remove_path(path)
-        .context(format!("Cannot stage removal of {}", path.display()))?;
-    } else {
-      index
-        .add_path(path)
-        .context(format!("Cannot stage {}", path.display()))?;
+  // The `all` scope keeps the historical auto-commit behavior: include all
+  // tracked dirty files, not only the version files touched by this command.
+  if scope == CommitScope::All {
+    let mut options = StatusOptions::new();
+    options.include_untracked(false).recurse_untracked_dirs(false);
+    let statuses = repo.statuses(Some(&mut options)).context("Failed to read git status")?;
+
+    for entry in statuses.iter() {
+      let Some(path) = entry.path() else {
+        continue;
+      };
+
+      let status = entry.status();
+      let path = Path::new(path);
+
+      if status.contains(Status::WT_DELETED) || status.contains(Status::INDEX_DELETED) {
+        index
+          .remove_path(path)
+          .context(format!("Cannot stage removal of {}", path.display()))?;
+      } else {
+        index
+          .add_path(path)
+          .context(format!("Cannot stage {}", path.display()))?;
+      }
     }
   }
 
@@ -563,21 +1606,12 @@ fn commit_updated_paths(paths: &[PathBuf], commit_message: &str) -> Result<()> {
   }
 
   let parent_refs = parents.iter().collect::<Vec<_>>();
-  repo
-    .commit(
-      Some("HEAD"),
-      &signature,
-      &signature,
-      commit_message,
-      &tree,
-      &parent_refs,
-    )
-    .context("Cannot create git commit")?;
+  create_commit(&repo, &signature, commit_message, &tree, &parent_refs, signing_key)?;
 
   Ok(())
 }
 
-fn tag_name_for_version(version: &str, tag_pattern: &str) -> Result<String> {
+pub fn tag_name_for_version(version: &str, tag_pattern: &str) -> Result<String> {
   let regex = Regex::new(tag_pattern).context(format!("Invalid tag regex pattern: {tag_pattern}"))?;
 
   let mut generated = Vec::new();
@@ -618,20 +1652,113 @@ fn tag_name_for_version(version: &str, tag_pattern: &str) -> Result<String> {
     .cloned()
     .ok_or_else(|| {
       anyhow!(
-        "Cannot derive tag from pattern '{}' for version {}",
+        "Cannot derive tag from pattern '{}' for version {} (tried {}); use a pattern containing \\d+\\.\\d+\\.\\d+ \
+         or set tag_format",
         tag_pattern,
-        version
+        version,
+        generated.join(", ")
       )
     })
 }
 
-fn tag_current_commit(version: &str, tag_pattern: &str) -> Result<()> {
-  let tag_name = tag_name_for_version(version, tag_pattern)?;
+/// Returns the name of the tag for `version`, from `tag_format` when set or
+/// derived from `tag_pattern` otherwise.
+pub fn tag_name(version: &str, config: &EffectiveConfig) -> Result<String> {
+  let Some(format) = config.tag_format.as_deref() else {
+    return tag_name_for_version(version, &config.tag_pattern);
+  };
+
+  let regex = Regex::new(&config.tag_pattern).context(format!("Invalid tag regex pattern: {}", config.tag_pattern))?;
+  let name = format.replace("{version}", version);
+
+  if !regex.is_match(&name) {
+    return Err(anyhow!(
+      "Tag format '{}' produces '{}', which does not match tag pattern '{}'",
+      format,
+      name,
+      config.tag_pattern
+    ));
+  }
+
+  Ok(name)
+}
+
+/// Returns the tag derived for `version`, failing when the tag pattern cannot
+/// produce one or the derived tag does not read back as `version`.
+pub fn validate_tag_pattern(version: &Version, config: &EffectiveConfig) -> Result<String> {
+  let name = tag_name(&version.to_string(), config)?;
+
+  if normalize_semver(&name).ok().as_ref() != Some(version) {
+    return Err(anyhow!(
+      "Tag '{}' derived from pattern '{}' does not read back as version {}; only v-prefixed or plain semver tags are \
+       recognized",
+      name,
+      config.tag_pattern,
+      version
+    ));
+  }
+
+  Ok(name)
+}
+
+/// Renders the annotated tag message from `message` (the `--tag-message`
+/// flag), falling back to the configured template.
+pub fn render_tag_message(version: &str, message: Option<&str>, config: &EffectiveConfig) -> Result<String> {
+  let template = message
+    .or(config.tag_message_template.as_deref())
+    .unwrap_or(DEFAULT_TAG_MESSAGE_TEMPLATE);
+
+  if !template.contains("$NOTES") {
+    return Ok(render_template(template, &[("VERSION", version)]).trim().to_string());
+  }
+
+  // The new tag does not exist yet, so the pending range since the latest
+  // matching tag is exactly the range this release covers.
+  let filter = CommitFilter::from_config(config)?;
+  let mut commits = collect_releasable_commits(
+    read_commits(None, &config.tag_pattern, config.max_commits, &config.paths)?,
+    &filter,
+  );
+  apply_default_sorting(&mut commits);
+
+  let subjects = display_subjects(commits, config);
+
+  Ok(
+    render_template(
+      template,
+      &[("VERSION", version), ("NOTES", &render_release_body(&subjects))],
+    )
+    .trim()
+    .to_string(),
+  )
+}
+
+fn tag_current_commit(
+  version: &str,
+  config: &EffectiveConfig,
+  annotate: bool,
+  tag_message: Option<&str>,
+  signing_key: Option<&str>,
+) -> Result<()> {
+  let tag_name = tag_name(version, config)?;
 
   let repo = Repository::discover(".").context("Failed to discover git repository")?;
   let head = repo.head().context("Cannot resolve HEAD")?;
   let target = head.peel_to_commit().context("Cannot resolve HEAD commit")?;
 
+  // Only annotated tags can carry a signature.
+  if annotate || signing_key.is_some() {
+    let message = render_tag_message(version, tag_message, config)?;
+    let signature = repo
+      .signature()
+      .or_else(|_| Signature::now("cambi", "cambi@localhost"))
+      .context("Cannot build git signature")?;
+
+    create_annotated_tag(&repo, &tag_name, target.as_object(), &signature, &message, signing_key)?;
+
+    return Ok(());
+  }
+
   repo
     .tag_lightweight(&tag_name, target.as_object(), false)
     .context(format!("Cannot create git tag '{}'", tag_name))?;
@@ -639,182 +1766,654 @@ fn tag_current_commit(version: &str, tag_pattern: &str) -> Result<()> {
   Ok(())
 }
 
-fn apply_update_target(target: &UpdateTarget, config: &EffectiveConfig) -> Result<(String, PathBuf)> {
-  let cargo_toml = Path::new("Cargo.toml");
-  if cargo_toml.exists() {
-    return Ok((update_cargo_toml_version(cargo_toml, target)?, cargo_toml.to_path_buf()));
+fn file_extension(path: &Path) -> &str {
+  path.extension().and_then(|ext| ext.to_str()).unwrap_or_default()
+}
+
+fn file_name(path: &Path) -> &str {
+  path.file_name().and_then(|name| name.to_str()).unwrap_or_default()
+}
+
+fn unsupported_file_error(path: &Path) -> anyhow::Error {
+  anyhow!(
+    "Unsupported version file {} (expected a known manifest like Cargo.toml or mix.exs, a version/VERSION file, or a \
+     .json, .yaml, .yml, .py, .gemspec, .podspec or .nimble file)",
+    path.display()
+  )
+}
+
+/// Format of a version file recognized by its content.
+enum SniffedFormat {
+  Json,
+  Toml,
+  Yaml,
+  Line,
+}
+
+fn sniffed_line_regex() -> Regex {
+  Regex::new(r#"^(?P<prefix>\s*version\s*[=:]\s*["']?)(?P<version>v?\d+\.\d+\.\d+[^"'\s]*)(?P<suffix>["']?.*)$"#)
+    .expect("sniffed version regex must compile")
+}
+
+/// Recognizes an unconventionally named version file by trying, in order,
+/// JSON, TOML, YAML and a `version = x.y.z` line, picking the first that holds
+/// a version.
+fn sniff_version_format(path: &Path, config: &EffectiveConfig) -> Result<SniffedFormat> {
+  let content = fs::read_to_string(path).context(format!("Cannot read {}", path.display()))?;
+  let key_path = config.version_json_path.as_deref().unwrap_or("version");
+  let toml_path = config.toml_version_path.as_deref().unwrap_or("version");
+
+  if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content)
+    && json
+      .pointer(&json_pointer(key_path))
+      .is_some_and(serde_json::Value::is_string)
+  {
+    return Ok(SniffedFormat::Json);
   }
 
-  let package_json = Path::new("package.json");
-  if package_json.exists() {
-    return Ok((
-      update_package_json_version(package_json, target)?,
-      package_json.to_path_buf(),
-    ));
+  if let Ok(mut doc) = content.parse::<toml_edit::DocumentMut>()
+    && toml_version_field(&mut doc, toml_path, path).is_ok()
+  {
+    return Ok(SniffedFormat::Toml);
   }
 
-  let pyproject_toml = Path::new("pyproject.toml");
-  if pyproject_toml.exists() {
-    return Ok((
-      update_pyproject_toml_version(pyproject_toml, target)?,
-      pyproject_toml.to_path_buf(),
-    ));
+  if let Ok(mut yaml) = serde_yaml::from_str::<serde_yaml::Value>(&content)
+    && yaml_field_mut(&mut yaml, key_path).is_some_and(|field| field.is_string())
+  {
+    return Ok(SniffedFormat::Yaml);
   }
 
-  if let Ok(gemspec_path) = find_gemspec_path() {
-    return Ok((update_gemspec_version(&gemspec_path, target)?, gemspec_path));
+  let line_regex = sniffed_line_regex();
+  if content.lines().any(|line| line_regex.is_match(line)) {
+    return Ok(SniffedFormat::Line);
   }
 
-  let mix_exs = Path::new("mix.exs");
-  if mix_exs.exists() {
-    return Ok((update_mix_exs_version(mix_exs, target)?, mix_exs.to_path_buf()));
+  Err(unsupported_file_error(path))
+}
+
+fn apply_sniffed_file(path: &Path, target: &UpdateTarget, config: &EffectiveConfig) -> Result<String> {
+  let key_path = config.version_json_path.as_deref().unwrap_or("version");
+  let toml_path = config.toml_version_path.as_deref().unwrap_or("version");
+
+  match sniff_version_format(path, config)? {
+    SniffedFormat::Json => update_nested_json_version(path, target, key_path),
+    SniffedFormat::Toml => update_toml_version(path, target, toml_path),
+    SniffedFormat::Yaml => update_nested_yaml_version(path, target, key_path),
+    SniffedFormat::Line => update_version_line(path, target, &sniffed_line_regex(), "version = x.y.z"),
   }
+}
 
-  let pubspec_yaml = Path::new("pubspec.yaml");
-  if pubspec_yaml.exists() {
-    return Ok((
-      update_pubspec_yaml_version(pubspec_yaml, target)?,
-      pubspec_yaml.to_path_buf(),
-    ));
+fn read_sniffed_file(path: &Path, target: &UpdateTarget, config: &EffectiveConfig) -> Result<String> {
+  let key_path = config.version_json_path.as_deref().unwrap_or("version");
+  let toml_path = config.toml_version_path.as_deref().unwrap_or("version");
+
+  match sniff_version_format(path, config)? {
+    SniffedFormat::Json => read_nested_json_target_version(path, target, key_path),
+    SniffedFormat::Toml => read_toml_target_version(path, target, toml_path),
+    SniffedFormat::Yaml => read_nested_yaml_target_version(path, target, key_path),
+    SniffedFormat::Line => read_version_line_target_version(path, target, &sniffed_line_regex(), "version = x.y.z"),
   }
+}
 
-  let package_swift = Path::new("Package.swift");
-  if package_swift.exists() {
-    return Ok((
-      update_package_swift_version(package_swift, target)?,
-      package_swift.to_path_buf(),
-    ));
+fn apply_explicit_file(path: &Path, target: &UpdateTarget, config: &EffectiveConfig) -> Result<String> {
+  let key_path = config.version_json_path.as_deref();
+
+  if let Some(toml_path) = config.toml_version_path.as_deref()
+    && file_extension(path) == "toml"
+  {
+    return update_toml_version(path, target, toml_path);
   }
 
-  let version_lower = Path::new("version");
-  if version_lower.exists() {
-    return Ok((
-      update_plain_version_file(version_lower, target, &config.tag_pattern)?,
-      version_lower.to_path_buf(),
-    ));
+  match file_name(path) {
+    "Cargo.toml" => return update_cargo_toml_version(path, target),
+    "pyproject.toml" => return update_pyproject_toml_version(path, target),
+    "setup.cfg" => return update_setup_cfg_version(path, target),
+    "mix.exs" => return update_mix_exs_version(path, target),
+    "build.sbt" => return update_sbt_version(path, target),
+    "Makefile" | "makefile" | "GNUmakefile" => return update_makefile_version(path, target),
+    "Package.swift" => return update_package_swift_version(path, target),
+    "gradle.properties" => return update_gradle_properties_version(path, target),
+    "build.gradle" | "build.gradle.kts" => return update_gradle_build_version(path, target),
+    "version" | "VERSION" => return update_plain_version_file(path, target, &config.tag_pattern),
+    "changelog" if path.parent().is_some_and(|parent| parent.ends_with("debian")) => {
+      return update_debian_changelog_version(path, target);
+    }
+    _ => {}
   }
 
-  let version_upper = Path::new("VERSION");
-  if version_upper.exists() {
-    return Ok((
-      update_plain_version_file(version_upper, target, &config.tag_pattern)?,
-      version_upper.to_path_buf(),
-    ));
+  match (file_extension(path), key_path) {
+    ("gemspec", _) => update_gemspec_version(path, target),
+    ("podspec", _) => update_podspec_version(path, target),
+    ("nimble", _) => update_nimble_version(path, target),
+    ("json", Some(key_path)) => update_nested_json_version(path, target, key_path),
+    ("json", None) => update_package_json_version(path, target),
+    ("yaml" | "yml", Some(key_path)) => update_nested_yaml_version(path, target, key_path),
+    ("yaml" | "yml", None) => update_pubspec_yaml_version(path, target, config.flutter_build),
+    ("py", _) if path.ends_with("setup.py") => update_setup_py_version(path, target),
+    ("py", _) => update_dunder_version_file(path, target),
+    _ => apply_sniffed_file(path, target, config),
   }
+}
 
-  Err(anyhow!(
-    "No supported package file found (Cargo.toml, package.json, pyproject.toml, *.gemspec, mix.exs, pubspec.yaml, \
-     Package.swift, or version/VERSION)"
-  ))
+fn read_explicit_file(path: &Path, target: &UpdateTarget, config: &EffectiveConfig) -> Result<String> {
+  let key_path = config.version_json_path.as_deref();
+
+  if let Some(toml_path) = config.toml_version_path.as_deref()
+    && file_extension(path) == "toml"
+  {
+    return read_toml_target_version(path, target, toml_path);
+  }
+
+  match file_name(path) {
+    "Cargo.toml" => return read_cargo_toml_target_version(path, target),
+    "pyproject.toml" => return read_pyproject_toml_target_version(path, target),
+    "setup.cfg" => return read_setup_cfg_target_version(path, target),
+    "mix.exs" => return read_mix_exs_target_version(path, target),
+    "build.sbt" => return read_sbt_target_version(path, target),
+    "Makefile" | "makefile" | "GNUmakefile" => return read_makefile_target_version(path, target),
+    "Package.swift" => return read_package_swift_target_version(path, target),
+    "gradle.properties" => return read_gradle_properties_target_version(path, target),
+    "build.gradle" | "build.gradle.kts" => return read_gradle_build_target_version(path, target),
+    "version" | "VERSION" => return read_plain_version_file_target_version(path, target, &config.tag_pattern),
+    "changelog" if path.parent().is_some_and(|parent| parent.ends_with("debian")) => {
+      return read_debian_changelog_target_version(path, target);
+    }
+    _ => {}
+  }
+
+  match (file_extension(path), key_path) {
+    ("gemspec", _) => read_gemspec_target_version(path, target),
+    ("podspec", _) => read_podspec_target_version(path, target),
+    ("nimble", _) => read_nimble_target_version(path, target),
+    ("json", Some(key_path)) => read_nested_json_target_version(path, target, key_path),
+    ("json", None) => read_package_json_target_version(path, target),
+    ("yaml" | "yml", Some(key_path)) => read_nested_yaml_target_version(path, target, key_path),
+    ("yaml" | "yml", None) => read_pubspec_yaml_target_version(path, target, config.flutter_build),
+    ("py", _) if path.ends_with("setup.py") => read_setup_py_target_version(path, target),
+    ("py", _) => read_dunder_version_target_version(path, target),
+    _ => read_sniffed_file(path, target, config),
+  }
 }
 
-fn read_update_target(target: &UpdateTarget, config: &EffectiveConfig) -> Result<(String, PathBuf)> {
-  let cargo_toml = Path::new("Cargo.toml");
-  if cargo_toml.exists() {
-    return Ok((
-      read_cargo_toml_target_version(cargo_toml, target)?,
-      cargo_toml.to_path_buf(),
-    ));
+fn existing_path(name: &str) -> Option<PathBuf> {
+  let path = PathBuf::from(name);
+  path.exists().then_some(path)
+}
+
+/// Lists the supported version files of the current directory in priority
+/// order: only the first one, unless `all` is set. With `all`, ambiguous
+/// detectors are skipped with a warning instead of failing.
+fn detect_version_files(config: &EffectiveConfig, all: bool) -> Result<Vec<PathBuf>> {
+  if config.debian_changelog {
+    return Ok(vec![PathBuf::from("debian/changelog")]);
   }
 
-  let package_json = Path::new("package.json");
-  if package_json.exists() {
-    return Ok((
-      read_package_json_target_version(package_json, target)?,
-      package_json.to_path_buf(),
-    ));
+  let detectors: [fn() -> Result<Option<PathBuf>>; 15] = [
+    || Ok(existing_path("Cargo.toml")),
+    || Ok(existing_path("package.json")),
+    || Ok(existing_path("pyproject.toml")),
+    || Ok(find_setup_cfg_path()),
+    || Ok(existing_path("setup.py")),
+    || find_single_file_with_extension("gemspec"),
+    || find_single_file_with_extension("podspec"),
+    || Ok(existing_path("mix.exs")),
+    || Ok(existing_path("pubspec.yaml")),
+    || Ok(existing_path("Package.swift")),
+    || Ok(existing_path("build.sbt")),
+    || Ok(find_gradle_version_file()),
+    || find_single_file_with_extension("nimble"),
+    || Ok(existing_path("version")),
+    || Ok(existing_path("VERSION")),
+  ];
+
+  let mut files = Vec::new();
+
+  for detect in detectors {
+    match detect() {
+      Ok(Some(path)) => {
+        files.push(path);
+
+        if !all {
+          break;
+        }
+      }
+      Ok(None) => {}
+      Err(error) if all => eprintln!("{error:#}. Skipping it."),
+      Err(error) => return Err(error),
+    }
   }
 
-  let pyproject_toml = Path::new("pyproject.toml");
-  if pyproject_toml.exists() {
-    return Ok((
-      read_pyproject_toml_target_version(pyproject_toml, target)?,
-      pyproject_toml.to_path_buf(),
-    ));
+  Ok(files)
+}
+
+fn apply_detected_file(path: &Path, target: &UpdateTarget, config: &EffectiveConfig) -> Result<String> {
+  // Detected manifests always use their own version field, regardless of
+  // version_json_path and toml_version_path.
+  match file_name(path) {
+    "Cargo.toml" => update_cargo_toml_version(path, target),
+    "pyproject.toml" => update_pyproject_toml_version(path, target),
+    "package.json" => update_package_json_version(path, target),
+    "pubspec.yaml" => update_pubspec_yaml_version(path, target, config.flutter_build),
+    _ => apply_explicit_file(path, target, config),
+  }
+}
+
+fn read_detected_file(path: &Path, target: &UpdateTarget, config: &EffectiveConfig) -> Result<String> {
+  match file_name(path) {
+    "Cargo.toml" => read_cargo_toml_target_version(path, target),
+    "pyproject.toml" => read_pyproject_toml_target_version(path, target),
+    "package.json" => read_package_json_target_version(path, target),
+    "pubspec.yaml" => read_pubspec_yaml_target_version(path, target, config.flutter_build),
+    _ => read_explicit_file(path, target, config),
   }
+}
 
-  if let Ok(gemspec_path) = find_gemspec_path() {
-    return Ok((read_gemspec_target_version(&gemspec_path, target)?, gemspec_path));
+fn no_version_file_error() -> anyhow::Error {
+  anyhow!(
+    "No supported package file found (Cargo.toml, package.json, pyproject.toml, setup.cfg, setup.py, *.gemspec, \
+     *.podspec, mix.exs, pubspec.yaml, Package.swift, build.sbt, gradle.properties, build.gradle(.kts), *.nimble, or \
+     version/VERSION)"
+  )
+}
+
+/// Unifies the target of every detected file on the highest next version, so
+/// files that were out of sync end up on the same version.
+fn resolve_all_files_target(
+  files: &[PathBuf],
+  target: &UpdateTarget,
+  config: &EffectiveConfig,
+) -> Result<UpdateTarget> {
+  let mut versions = Vec::new();
+
+  for file in files {
+    let mut version = normalize_semver(&read_detected_file(file, target, config)?)?;
+    // Flutter build numbers are handled per file.
+    version.build = BuildMetadata::EMPTY;
+    versions.push((file, version));
   }
 
-  let mix_exs = Path::new("mix.exs");
-  if mix_exs.exists() {
-    return Ok((read_mix_exs_target_version(mix_exs, target)?, mix_exs.to_path_buf()));
+  let highest = versions
+    .iter()
+    .map(|(_, version)| version.clone())
+    .max()
+    .ok_or_else(no_version_file_error)?;
+
+  if config.verbose && versions.iter().any(|(_, version)| *version != highest) {
+    let found = versions
+      .iter()
+      .map(|(file, version)| format!("{}: {version}", file.display()))
+      .collect::<Vec<_>>();
+    eprintln!(
+      "Version files are out of sync ({}); updating all of them to {highest}.",
+      found.join(", ")
+    );
   }
 
-  let pubspec_yaml = Path::new("pubspec.yaml");
-  if pubspec_yaml.exists() {
-    return Ok((
-      read_pubspec_yaml_target_version(pubspec_yaml, target)?,
-      pubspec_yaml.to_path_buf(),
-    ));
+  Ok(match target {
+    UpdateTarget::Downgrade(_) => UpdateTarget::Downgrade(highest),
+    UpdateTarget::WithBuild(_, build) => UpdateTarget::WithBuild(Box::new(UpdateTarget::Exact(highest)), build.clone()),
+    _ => UpdateTarget::Exact(highest),
+  })
+}
+
+/// Resolves the files to update: the explicit `file`, every detected file with
+/// `all`, or the first detected one. Also returns the target to apply to them.
+fn resolve_update_files(
+  target: &UpdateTarget,
+  file: Option<&Path>,
+  all: bool,
+  config: &EffectiveConfig,
+) -> Result<(Vec<PathBuf>, UpdateTarget)> {
+  if let Some(file) = file {
+    return Ok((vec![file.to_path_buf()], target.clone()));
   }
 
-  let package_swift = Path::new("Package.swift");
-  if package_swift.exists() {
-    return Ok((
-      read_package_swift_target_version(package_swift, target)?,
-      package_swift.to_path_buf(),
-    ));
+  let mut files = detect_version_files(config, all)?;
+
+  if all && files.len() > 1 {
+    let unified = resolve_all_files_target(&files, target, config)?;
+    return Ok((files, unified));
   }
 
-  let version_lower = Path::new("version");
-  if version_lower.exists() {
-    return Ok((
-      read_plain_version_file_target_version(version_lower, target, &config.tag_pattern)?,
-      version_lower.to_path_buf(),
-    ));
+  files.truncate(1);
+  Ok((files, target.clone()))
+}
+
+fn apply_update_target(
+  target: &UpdateTarget,
+  file: Option<&Path>,
+  all: bool,
+  config: &EffectiveConfig,
+) -> Result<Vec<(String, Option<PathBuf>)>> {
+  let (files, target) = resolve_update_files(target, file, all, config)?;
+
+  if files.is_empty() {
+    // Without any version file, a base version from the environment still lets
+    // the next version be computed.
+    let Some(current_version) = config.current_version.as_deref() else {
+      return Err(no_version_file_error());
+    };
+
+    let version = resolve_target_version(normalize_semver(current_version)?, &target)?;
+    return Ok(vec![(version.to_string(), None)]);
+  }
+
+  // Every file is checked before the first one is written, so a file that
+  // cannot be bumped leaves the others untouched.
+  if files.len() > 1 {
+    for path in &files {
+      read_detected_file(path, &target, config)?;
+    }
+  }
+
+  let mut updated = Vec::new();
+  let mut errors = BatchErrors::new(config.continue_on_error);
+
+  for path in files {
+    let result = if file.is_some() {
+      apply_explicit_file(&path, &target, config)
+    } else {
+      apply_detected_file(&path, &target, config)
+    };
+
+    errors.record(result.map(|version| updated.push((version, Some(path)))))?;
+  }
+
+  errors.finish()?;
+  Ok(updated)
+}
+
+fn read_update_target(
+  target: &UpdateTarget,
+  file: Option<&Path>,
+  all: bool,
+  config: &EffectiveConfig,
+) -> Result<Vec<(String, Option<PathBuf>)>> {
+  let (files, target) = resolve_update_files(target, file, all, config)?;
+
+  if files.is_empty() {
+    let Some(current_version) = config.current_version.as_deref() else {
+      return Err(no_version_file_error());
+    };
+
+    let version = resolve_target_version(normalize_semver(current_version)?, &target)?;
+    return Ok(vec![(version.to_string(), None)]);
+  }
+
+  let mut resolved = Vec::new();
+
+  for path in files {
+    let version = if file.is_some() {
+      read_explicit_file(&path, &target, config)?
+    } else {
+      read_detected_file(&path, &target, config)?
+    };
+
+    resolved.push((version, Some(path)));
+  }
+
+  Ok(resolved)
+}
+
+/// Writes the dot-env file of `version`, also listing the version it was
+/// bumped from as `CAMBI_PREVIOUS_VERSION` when there is one.
+pub fn write_env_file(path: &Path, version: &str, tag: &str, bump: BumpLevel, previous: Option<&str>) -> Result<()> {
+  if let Some(parent) = path.parent()
+    && !parent.as_os_str().is_empty()
+  {
+    fs::create_dir_all(parent).context(format!("Cannot create {}", parent.display()))?;
   }
 
-  let version_upper = Path::new("VERSION");
-  if version_upper.exists() {
-    return Ok((
-      read_plain_version_file_target_version(version_upper, target, &config.tag_pattern)?,
-      version_upper.to_path_buf(),
+  let previous = previous
+    .map(|previous| format!("CAMBI_PREVIOUS_VERSION={previous}\n"))
+    .unwrap_or_default();
+
+  write_atomic(
+    path,
+    format!(
+      "CAMBI_VERSION={version}\nCAMBI_TAG={tag}\nCAMBI_BUMP={}\n{previous}",
+      bump.as_str()
+    ),
+  )
+  .context(format!("Cannot write {}", path.display()))
+}
+
+/// Compares the version of the detected version file with the latest tag
+/// version, ignoring build metadata.
+fn check_version_consistency(config: &EffectiveConfig) -> Result<()> {
+  let (file_version, path) = read_update_target(&UpdateTarget::Current, None, false, config)?
+    .into_iter()
+    .next()
+    .ok_or_else(no_version_file_error)?;
+  let source = path.map_or("CAMBI_CURRENT_VERSION".to_string(), |path| path.display().to_string());
+  let file_version = normalize_semver(&file_version)?;
+  let tag_version = latest_tag_version(&config.tag_pattern, false)?;
+
+  if file_version.cmp_precedence(&tag_version).is_ne() {
+    return Err(anyhow!(
+      "Version mismatch: {source} is at {file_version}, but the latest tag is at {tag_version}"
     ));
   }
 
-  Err(anyhow!(
-    "No supported package file found (Cargo.toml, package.json, pyproject.toml, *.gemspec, mix.exs, pubspec.yaml, \
-     Package.swift, or version/VERSION)"
-  ))
+  println!(
+    "{}",
+    config.format.render(
+      format!("{source} matches the latest tag at {file_version}"),
+      serde_json::json!({ "file": source, "version": file_version.to_string(), "tag_version": tag_version.to_string() })
+    )
+  );
+  Ok(())
 }
 
 pub fn execute_version(version_args: &VersionArgs, config: &EffectiveConfig) -> Result<()> {
+  if version_args.check_consistency {
+    return check_version_consistency(config);
+  }
+
   let current = if let Some(from_tag) = version_args.from_tag.as_deref() {
     normalize_semver(from_tag)?
   } else {
-    latest_tag_version(&config.tag_pattern)?
+    current_base_version(config, version_args.reachable)?
   };
 
-  println!("{}", current);
+  if version_args.validate_tag_pattern {
+    let name = validate_tag_pattern(&current, config)?;
+    println!(
+      "{}",
+      config.format.render(
+        name.clone(),
+        serde_json::json!({ "version": current.to_string(), "tag": name })
+      )
+    );
+    return Ok(());
+  }
+
+  if let Some(env_path) = version_args.write_env.as_deref() {
+    let version = current.to_string();
+    let bump = detect_bump(version_args.from_tag.as_deref(), config, false)?;
+    write_env_file(env_path, &version, &tag_name(&version, config)?, bump, None)?;
+  }
+
+  if !version_args.next.is_empty() {
+    let previews = version_args
+      .next
+      .iter()
+      .map(|bump| (bump.as_str(), bump_semver(current.clone(), *bump).to_string()))
+      .collect::<Vec<_>>();
+
+    let text = previews
+      .iter()
+      .map(|(bump, version)| format!("{bump}={version}"))
+      .collect::<Vec<_>>()
+      .join(" ");
+    let json = previews
+      .into_iter()
+      .map(|(bump, version)| (bump.to_string(), serde_json::Value::String(version)))
+      .collect::<serde_json::Map<_, _>>();
+
+    println!("{}", config.format.render(text, json.into()));
+    return Ok(());
+  }
+
+  println!(
+    "{}",
+    config.format.render(
+      current.to_string(),
+      serde_json::json!({ "version": current.to_string() })
+    )
+  );
   Ok(())
 }
 
 pub fn execute_semver(semver_args: &SemverArgs, config: &EffectiveConfig) -> Result<()> {
-  let bump = detect_bump(semver_args.from_tag.as_deref(), config)?;
-  println!("{}", bump.as_str());
+  if semver_args.print_range {
+    eprintln!(
+      "{}",
+      describe_commit_range(semver_args.from_tag.as_deref(), &config.tag_pattern)?
+    );
+  }
+
+  let (bump, stats) = detect_bump_with_stats_in(
+    Path::new("."),
+    semver_args.from_tag.as_deref(),
+    config,
+    semver_args.bump_from_labels,
+  )?;
+
+  if semver_args.stats {
+    println!(
+      "{}",
+      config.format.render(
+        format!("{}\n{}", bump.as_str(), stats.render()),
+        serde_json::json!({ "bump": bump.as_str(), "stats": stats.to_json() })
+      )
+    );
+  } else {
+    println!(
+      "{}",
+      config
+        .format
+        .render(bump.as_str().to_string(), serde_json::json!({ "bump": bump.as_str() }))
+    );
+  }
 
   Ok(())
 }
 
+/// Machine-readable summary of an update run.
+fn update_summary(
+  previous: &str,
+  next: &str,
+  bump: BumpLevel,
+  file: Option<&PathBuf>,
+  committed: bool,
+  tagged: bool,
+) -> serde_json::Value {
+  serde_json::json!({
+    "previous": previous,
+    "next": next,
+    "bump": bump.as_str(),
+    "file": file.map(|path| path.display().to_string()),
+    "committed": committed,
+    "tagged": tagged,
+  })
+}
+
 pub fn execute_update(update_args: &UpdateArgs, config: &EffectiveConfig) -> Result<()> {
-  let detected_bump = detect_bump(update_args.from_tag.as_deref(), config)?;
-  let target = parse_update_target(update_args.target.as_deref(), detected_bump)?;
-  let (target_version, target_path) = read_update_target(&target, config)?;
+  let committing = update_args.commit || update_args.commit_all;
+  let commit_scope = if update_args.commit_all {
+    CommitScope::All
+  } else {
+    config.commit_scope
+  };
+  // A missing key must fail before any file is touched.
+  let signing_key = if committing && (update_args.sign || config.sign) {
+    Some(signing_key()?)
+  } else {
+    None
+  };
+  let detected_bump = detect_bump(update_args.from_tag.as_deref(), config, update_args.bump_from_labels)?;
+  // The initial version only applies when it is ahead of the version on disk,
+  // otherwise the usual bump keeps the no-downgrade guarantee.
+  let target = match (update_args.target.as_deref(), update_args.pre.as_deref()) {
+    (None, None) => {
+      match initial_release_version(config)? {
+        Some(initial) => {
+          let current = read_update_target(&UpdateTarget::Current, update_args.file.as_deref(), false, config)?[0]
+            .0
+            .clone();
+          normalize_semver(&current)
+            .is_ok_and(|current| initial > current)
+            .then_some(UpdateTarget::Exact(initial))
+        }
+        None => None,
+      }
+    }
+    _ => None,
+  };
+  let target = match target {
+    Some(target) => target,
+    None => {
+      parse_update_target(
+        update_args.target.as_deref(),
+        update_args.pre.as_deref(),
+        detected_bump,
+        update_args.allow_downgrade,
+      )?
+    }
+  };
+  let target = match update_args.build.as_deref() {
+    Some(build) => {
+      UpdateTarget::WithBuild(
+        Box::new(target),
+        BuildMetadata::new(build).context(format!("Invalid build metadata '{build}'"))?,
+      )
+    }
+    None => target,
+  };
+  let file = update_args.file.as_deref();
+  let resolved = read_update_target(&target, file, update_args.all, config)?;
+  let target_version = resolved[0].0.clone();
 
   if update_args.show {
     println!("{target_version}");
     return Ok(());
   }
 
+  // Explicit versions carry no bump level of their own, so report the one
+  // inferred from commits instead.
+  let inner_target = match &target {
+    UpdateTarget::WithBuild(target, _) => target,
+    target => target,
+  };
+  let bump = match inner_target {
+    UpdateTarget::Bump(bump) | UpdateTarget::Prerelease(bump, _) => *bump,
+    UpdateTarget::Exact(_)
+    | UpdateTarget::Downgrade(_)
+    | UpdateTarget::Current
+    | UpdateTarget::Finalize
+    | UpdateTarget::WithBuild(..) => detected_bump,
+  };
+
+  // With JSON output, stdout only carries the summary.
+  let json = update_args.json || config.format == OutputFormat::Json;
+  let report = |line: String| {
+    if json {
+      eprintln!("{line}");
+    } else {
+      println!("{line}");
+    }
+  };
+
+  let previous = read_update_target(&UpdateTarget::Current, file, false, config)?[0]
+    .0
+    .clone();
+
   if update_args.changelog {
     let changelog_args = ChangelogArgs {
       target: Some(target_version.clone()),
-      rebuild: false,
-      commit: false,
-      commit_message: None,
       dry_run: update_args.dry_run,
+      ..ChangelogArgs::default()
     };
 
     // CHANGELOG.md is generated before the version file is written so its
@@ -822,61 +2421,161 @@ pub fn execute_update(update_args: &UpdateArgs, config: &EffectiveConfig) -> Res
     execute_changelog_command(&changelog_args, config)?;
   }
 
-  let (updated, updated_path) = if update_args.dry_run {
-    (target_version, target_path)
+  let updated_files = if update_args.dry_run {
+    resolved
   } else {
-    apply_update_target(&target, config)?
+    apply_update_target(&target, file, update_args.all, config)?
   };
+  let updated = updated_files[0].0.clone();
+  let updated_paths = updated_files
+    .iter()
+    .filter_map(|(_, path)| path.clone())
+    .collect::<Vec<_>>();
+
+  let mut synced_paths = Vec::new();
+
+  if !update_args.dry_run && config.sync_workspace_deps {
+    for (version, path) in &updated_files {
+      if let Some(path) = path.as_deref()
+        && path.file_name().is_some_and(|name| name == "Cargo.toml")
+      {
+        synced_paths.extend(sync_workspace_dependency_pins(path, version)?);
+      }
+    }
+  }
 
   if update_args.dry_run {
-    println!("dry-run: would update {} to {}", updated_path.display(), updated);
+    for (version, path) in &updated_files {
+      if let Some(path) = path.as_deref() {
+        let current = if file.is_some() {
+          read_explicit_file(path, &UpdateTarget::Current, config)?
+        } else {
+          read_detected_file(path, &UpdateTarget::Current, config)?
+        };
+        report(format!(
+          "dry-run: would update {} from {} to {}",
+          path.display(),
+          current,
+          version
+        ));
+      }
+    }
 
-    if update_args.commit {
+    if committing {
       let commit_message = update_args
         .commit_message
         .as_deref()
         .unwrap_or("chore: Updated version.");
-      let mut updated_paths = vec![updated_path.display().to_string()];
+      let mut updated_paths = updated_paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>();
 
       if update_args.changelog {
         updated_paths.push("CHANGELOG.md".to_string());
       }
 
-      println!(
+      report(format!(
         "dry-run: would commit {} with message '{}'",
         updated_paths.join(", "),
         commit_message
-      );
+      ));
 
       if update_args.tag {
-        let tag_name = tag_name_for_version(&updated, &config.tag_pattern)?;
-        println!("dry-run: would create tag {tag_name}");
+        let tag_name = tag_name(&updated, config)?;
+
+        if update_args.annotate || signing_key.is_some() {
+          report(format!("dry-run: would create annotated tag {tag_name}"));
+        } else {
+          report(format!("dry-run: would create tag {tag_name}"));
+        }
+      }
+
+      if update_args.push {
+        report(format!("dry-run: would push to {}", update_args.remote));
       }
     }
 
-    println!("Updated version to {}.", updated);
+    if let Some(env_path) = update_args.write_env.as_deref() {
+      report(format!(
+        "dry-run: would write {} with CAMBI_VERSION={updated} and CAMBI_PREVIOUS_VERSION={previous}",
+        env_path.display()
+      ));
+    }
+
+    report(format!("Updated version to {}.", updated));
+
+    if json {
+      println!(
+        "{}",
+        update_summary(&previous, &updated, bump, updated_paths.first(), false, false)
+      );
+    }
+
     return Ok(());
   }
 
-  if update_args.commit {
+  if committing {
     let commit_message = update_args
       .commit_message
       .as_deref()
       .unwrap_or("chore: Updated version.");
-    let mut updated_paths = vec![updated_path];
+    let mut commit_paths = updated_paths.clone();
+    commit_paths.extend(synced_paths);
 
     if update_args.changelog {
-      updated_paths.push(PathBuf::from("CHANGELOG.md"));
+      commit_paths.push(PathBuf::from("CHANGELOG.md"));
     }
 
-    commit_updated_paths(&updated_paths, commit_message)?;
+    commit_updated_paths(&commit_paths, commit_message, commit_scope, signing_key.as_deref())?;
+
+    if update_args.verify_clean {
+      verify_clean_worktree()?;
+    }
 
     if update_args.tag {
-      tag_current_commit(&updated, &config.tag_pattern)?;
+      tag_current_commit(
+        &updated,
+        config,
+        update_args.annotate,
+        update_args.tag_message.as_deref(),
+        signing_key.as_deref(),
+      )?;
+    }
+
+    if update_args.push {
+      let tag = update_args.tag.then(|| tag_name(&updated, config)).transpose()?;
+      push_head(&update_args.remote, tag.as_deref())?;
     }
   }
 
-  println!("Updated version to {}.", updated);
+  if update_args.all {
+    for (version, path) in &updated_files {
+      if let Some(path) = path.as_deref() {
+        report(format!("Updated {} to {version}.", path.display()));
+      }
+    }
+  }
+
+  if let Some(env_path) = update_args.write_env.as_deref() {
+    write_env_file(env_path, &updated, &tag_name(&updated, config)?, bump, Some(&previous))?;
+  }
+
+  report(format!("Updated version to {}.", updated));
+
+  if json {
+    println!(
+      "{}",
+      update_summary(
+        &previous,
+        &updated,
+        bump,
+        updated_paths.first(),
+        committing,
+        update_args.tag
+      )
+    );
+  }
 
   Ok(())
 }
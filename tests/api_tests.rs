@@ -0,0 +1,43 @@
+mod common;
+
+use std::{collections::HashMap, fs};
+
+use cambi::{
+  Cambi,
+  config::{ConfigOverrides, EffectiveConfig},
+  conventional::BumpLevel,
+};
+
+use crate::common::{commit_with_date, create_repo, git};
+
+fn config() -> EffectiveConfig {
+  EffectiveConfig::from_sources(None, "release", &HashMap::new(), ConfigOverrides::default()).expect("config")
+}
+
+#[test]
+fn facade_computes_versions_and_notes_without_changing_cwd() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write");
+  commit_with_date(repo.path(), "fix: tweak output", "2026-02-21T10:00:00Z");
+  git(repo.path(), &["tag", "v0.1.1"]);
+
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"y\"); }\n").expect("write");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+
+  let cambi = Cambi::open(repo.path().join("src"), config()).expect("open");
+
+  assert_eq!(cambi.current_version().expect("current").to_string(), "0.1.1");
+  assert_eq!(cambi.next_bump().expect("bump"), BumpLevel::Minor);
+  assert_eq!(cambi.next_version().expect("next").to_string(), "0.2.0");
+  assert_eq!(
+    cambi.generate_changelog_section().expect("section").as_deref(),
+    Some("### 2026-02-22 / 0.2.0\n\n- feat: add output")
+  );
+  assert_eq!(cambi.release_notes().expect("notes"), "- fix: tweak output");
+}
+
+#[test]
+fn facade_open_fails_outside_a_repository() {
+  let temp = tempfile::TempDir::new().expect("temp dir");
+  assert!(Cambi::open(temp.path(), config()).is_err());
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use cambi::batch::{retry, retry_backoff};
+
+#[test]
+fn retry_backoff_doubles_up_to_a_cap() {
+  assert_eq!(retry_backoff(0), Duration::from_millis(250));
+  assert_eq!(retry_backoff(2), Duration::from_millis(1000));
+  assert_eq!(retry_backoff(6), retry_backoff(10));
+}
+
+#[test]
+fn retry_succeeds_after_a_fixed_number_of_failures() {
+  let mut attempts = 0;
+  let mut retried = Vec::new();
+
+  let result = retry(
+    2,
+    || {
+      attempts += 1;
+      if attempts <= 2 { Err(attempts) } else { Ok("fetched") }
+    },
+    |failure| retried.push(*failure),
+  );
+
+  assert_eq!(result, Ok("fetched"));
+  assert_eq!(attempts, 3);
+  assert_eq!(retried, vec![1, 2]);
+}
+
+#[test]
+fn retry_returns_the_last_failure_when_retries_run_out() {
+  let mut attempts = 0;
+
+  let result: Result<(), u32> = retry(
+    1,
+    || {
+      attempts += 1;
+      Err(attempts)
+    },
+    |_| {},
+  );
+
+  assert_eq!(result, Err(2));
+  assert_eq!(attempts, 2);
+}
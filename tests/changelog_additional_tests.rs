@@ -5,7 +5,7 @@ use std::fs;
 use assert_cmd::Command;
 use predicates::prelude::*;
 
-use crate::common::{commit_with_date, create_repo};
+use crate::common::{commit_with_date, create_repo, git};
 
 #[test]
 fn changelog_verbose_no_releasable_commits_prints_message() {
@@ -43,3 +43,441 @@ fn changelog_uses_custom_template_from_config() {
     .stdout(predicate::str::contains("### 2026-02-22 / 0.2.0"))
     .stdout(predicate::str::contains("- feat: add output"));
 }
+
+#[test]
+fn changelog_commit_verify_clean_succeeds_when_nothing_is_left() {
+  let repo = create_repo();
+  fs::write(repo.path().join("CHANGELOG.md"), "").expect("write changelog");
+  commit_with_date(repo.path(), "chore: add changelog", "2026-02-21T10:00:00Z");
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["changelog", "--commit", "--verify-clean"]);
+  cmd.assert().success();
+
+  let status = git(repo.path(), &["status", "--short"]);
+  assert_eq!(status.trim(), "");
+}
+
+#[test]
+fn changelog_commit_verify_clean_fails_when_modifications_remain() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() {}\n").expect("write stray change");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["changelog", "--commit", "--verify-clean"]);
+  cmd
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Working tree is not clean").and(predicate::str::contains("src/lib.rs")));
+}
+
+#[test]
+fn changelog_print_writes_file_and_echoes_section() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["changelog", "--print"]);
+  cmd
+    .assert()
+    .success()
+    .stdout("### 2026-02-22 / 0.2.0\n\n- feat: add output\n");
+
+  let changelog = fs::read_to_string(repo.path().join("CHANGELOG.md")).expect("read changelog");
+  assert_eq!(changelog, "### 2026-02-22 / 0.2.0\n\n- feat: add output\n");
+}
+
+#[test]
+fn changelog_json_format_reports_written_version_and_section() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["--format", "json", "changelog"]);
+  let output = cmd.assert().success().get_output().stdout.clone();
+
+  let summary: serde_json::Value = serde_json::from_slice(&output).expect("parse summary");
+  assert_eq!(summary["version"], "0.2.0");
+  assert_eq!(summary["written"], true);
+  assert_eq!(summary["path"], "CHANGELOG.md");
+  assert_eq!(summary["section"], "### 2026-02-22 / 0.2.0\n\n- feat: add output");
+
+  let changelog = fs::read_to_string(repo.path().join("CHANGELOG.md")).expect("read changelog");
+  assert_eq!(changelog, "### 2026-02-22 / 0.2.0\n\n- feat: add output\n");
+}
+
+#[test]
+fn changelog_unreleased_previews_pending_section_without_writing() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+  fs::write(
+    repo.path().join("cambi.yml"),
+    "changelog_template: \"## $VERSION\\n\\n$COMMITS\"\n",
+  )
+  .expect("write config");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["changelog", "--unreleased"]);
+  cmd.assert().success().stdout("## Unreleased\n\n- feat: add output\n");
+
+  assert!(!repo.path().join("CHANGELOG.md").exists());
+  assert!(!crate::common::git(repo.path(), &["tag", "--list"]).contains("v0.2.0"));
+}
+
+#[test]
+fn changelog_keepachangelog_style_keeps_unreleased_and_links() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+  git(repo.path(), &["tag", "v0.2.0"]);
+  fs::write(
+    repo.path().join("CHANGELOG.md"),
+    "# Changelog\n\n## [Unreleased]\n\n## [0.2.0] - 2026-02-22\n\n- feat: add output\n\n[0.2.0]: \
+     https://github.com/octo/repo/releases/tag/v0.2.0\n",
+  )
+  .expect("write changelog");
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"y\"); }\n").expect("write fix file");
+  commit_with_date(repo.path(), "fix: tweak output", "2026-02-23T10:00:00Z");
+
+  let expected = "# Changelog\n\n## [Unreleased]\n\n## [0.2.1] - 2026-02-23\n\n- fix: tweak output\n\n## [0.2.0] - \
+                  2026-02-22\n\n- feat: add output\n\n[Unreleased]: \
+                  https://github.com/octo/repo/compare/v0.2.1...HEAD\n[0.2.1]: \
+                  https://github.com/octo/repo/compare/v0.2.0...v0.2.1\n[0.2.0]: \
+                  https://github.com/octo/repo/releases/tag/v0.2.0\n";
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["changelog", "--style", "keepachangelog"]);
+  cmd.assert().success();
+  let changelog = fs::read_to_string(repo.path().join("CHANGELOG.md")).expect("read changelog");
+  assert_eq!(changelog, expected);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["changelog", "--style", "keepachangelog", "--rebuild"]);
+  cmd.assert().success();
+  let changelog = fs::read_to_string(repo.path().join("CHANGELOG.md")).expect("read changelog");
+  assert_eq!(changelog, expected);
+}
+
+#[test]
+fn changelog_since_starts_from_the_given_tag() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+  git(repo.path(), &["tag", "v0.2.0"]);
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"y\"); }\n").expect("write fix file");
+  commit_with_date(repo.path(), "fix: tweak output", "2026-02-23T10:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["changelog", "--since", "v0.1.0", "--dry-run"]);
+  cmd
+    .assert()
+    .success()
+    .stdout("### 2026-02-22 / 0.2.0\n\n- feat: add output\n- fix: tweak output\n\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["changelog", "--since", "v9.9.9", "--dry-run"]);
+  cmd
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Cannot resolve tag 'v9.9.9'"));
+}
+
+#[test]
+fn changelog_strips_scopes_when_configured() {
+  let repo = create_repo();
+  fs::write(repo.path().join("cambi.yml"), "changelog_strip_scope: true\n").expect("write config");
+
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat(api)!: redesign output", "2026-02-22T10:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["changelog", "--dry-run"]);
+
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("- feat!: redesign output"))
+    .stdout(predicate::str::contains("(api)").not());
+}
+
+#[test]
+fn changelog_strips_prefixes_when_enabled_by_env() {
+  let repo = create_repo();
+
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat(api)!: redesign output", "2026-02-22T10:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .env("CAMBI_STRIP_PREFIXES", "true")
+    .args(["changelog", "--dry-run"]);
+
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("- redesign output (breaking)"))
+    .stdout(predicate::str::contains("feat").not());
+}
+
+#[test]
+fn changelog_lists_merged_pull_request_titles_when_enabled() {
+  let repo = create_repo();
+  git(repo.path(), &["checkout", "-q", "-b", "feature"]);
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "chore: wip", "2026-02-22T10:00:00Z");
+  git(repo.path(), &["checkout", "-q", "-"]);
+  git(
+    repo.path(),
+    &[
+      "merge",
+      "-q",
+      "--no-ff",
+      "feature",
+      "-m",
+      "Merge pull request #12 from user/feature",
+      "-m",
+      "Add shiny output",
+    ],
+  );
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["changelog", "--dry-run"]);
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Add shiny output").not());
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--include-merge-prs", "changelog", "--dry-run"]);
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("- Add shiny output (#12)"))
+    .stdout(predicate::str::contains("Merge pull request").not());
+}
+
+#[test]
+fn changelog_links_commits_when_configured() {
+  let repo = create_repo();
+  fs::write(repo.path().join("cambi.yml"), "link_commits: true\n").expect("write config");
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+  let id = git(repo.path(), &["rev-parse", "--short=7", "HEAD"]);
+  let id = id.trim();
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["changelog", "--dry-run"]);
+  cmd.assert().success().stdout(predicate::str::contains(format!(
+    "- feat: add output ([{id}](https://github.com/octo/repo/commit/{id}))"
+  )));
+
+  fs::write(
+    repo.path().join("Cargo.toml"),
+    "[package]\nname = \"fixture\"\nversion = \"0.1.0\"\n",
+  )
+  .expect("write Cargo.toml");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["changelog", "--dry-run"]);
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(format!("- feat: add output ({id})\n")));
+}
+
+#[test]
+fn changelog_links_commits_to_the_origin_remote_on_a_self_hosted_gitlab() {
+  let repo = create_repo();
+  fs::write(
+    repo.path().join("Cargo.toml"),
+    "[package]\nname = \"fixture\"\nversion = \"0.1.0\"\n",
+  )
+  .expect("write Cargo.toml");
+  fs::write(
+    repo.path().join("cambi.yml"),
+    "link_commits: true\nprovider: gitlab\nweb_host: git.example.com\n",
+  )
+  .expect("write config");
+  git(
+    repo.path(),
+    &["remote", "add", "origin", "git@git.example.com:group/sub/widgets.git"],
+  );
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+  let id = git(repo.path(), &["rev-parse", "--short=7", "HEAD"]);
+  let id = id.trim();
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["changelog", "--dry-run"]);
+  cmd.assert().success().stdout(predicate::str::contains(format!(
+    "- feat: add output ([{id}](https://git.example.com/group/sub/widgets/-/commit/{id}))"
+  )));
+}
+
+#[test]
+fn changelog_custom_heading_format_is_rendered_and_detected() {
+  let repo = create_repo();
+  fs::write(repo.path().join("cambi.yml"), "heading_format: '## $VERSION ($DATE)'\n").expect("write config");
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+
+  for _ in 0..2 {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+    cmd.current_dir(repo.path()).args(["changelog"]);
+    cmd.assert().success();
+  }
+
+  let changelog = fs::read_to_string(repo.path().join("CHANGELOG.md")).expect("read changelog");
+  assert_eq!(changelog, "## 0.2.0 (2026-02-22)\n\n- feat: add output\n");
+}
+
+#[test]
+fn changelog_rebuild_commit_twice_creates_a_single_commit() {
+  let repo = create_repo();
+  fs::write(repo.path().join("CHANGELOG.md"), "").expect("write changelog");
+  commit_with_date(repo.path(), "chore: add changelog", "2026-02-21T10:00:00Z");
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+
+  for _ in 0..2 {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+    cmd
+      .current_dir(repo.path())
+      .args(["--verbose", "changelog", "--rebuild", "--commit"]);
+    cmd.assert().success();
+  }
+
+  let log = git(repo.path(), &["log", "--format=%s"]);
+  assert_eq!(log.matches("chore: Updated CHANGELOG.md.").count(), 1);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--verbose", "changelog", "--rebuild", "--commit"]);
+  cmd
+    .assert()
+    .success()
+    .stderr(predicate::str::contains("CHANGELOG.md is unchanged"));
+}
+
+#[test]
+fn changelog_anchor_tags_set_the_base_without_a_section() {
+  let repo = create_repo();
+  fs::write(repo.path().join("cambi.yml"), "anchor_tags:\n  - v0.2.1\n").expect("write config");
+
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-20T10:00:00Z");
+  git(repo.path(), &["tag", "v0.2.0"]);
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"y\"); }\n").expect("write fix file");
+  commit_with_date(repo.path(), "fix: retag output", "2026-02-21T10:00:00Z");
+  git(repo.path(), &["tag", "v0.2.1"]);
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"z\"); }\n").expect("write fix file");
+  commit_with_date(repo.path(), "fix: tweak output", "2026-02-22T10:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["changelog", "--rebuild", "--dry-run"]);
+
+  cmd.assert().success().stdout(
+    predicate::str::contains("### 2026-02-22 / 0.2.2")
+      .and(predicate::str::contains("### 2026-02-20 / 0.2.0"))
+      .and(predicate::str::contains("/ 0.2.1").not()),
+  );
+}
+
+#[test]
+fn changelog_rebuild_keeps_the_commits_of_a_trailing_anchor_tag() {
+  let repo = create_repo();
+  fs::write(repo.path().join("cambi.yml"), "anchor_tags:\n  - v0.2.1\n").expect("write config");
+
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-20T10:00:00Z");
+  git(repo.path(), &["tag", "v0.2.0"]);
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"y\"); }\n").expect("write fix file");
+  commit_with_date(repo.path(), "fix: retag output", "2026-02-21T10:00:00Z");
+  git(repo.path(), &["tag", "v0.2.1"]);
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"z\"); }\n").expect("write fix file");
+  commit_with_date(repo.path(), "fix: tweak output", "2026-02-22T10:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["changelog", "--rebuild", "--dry-run"]);
+
+  cmd.assert().success().stdout(
+    predicate::str::contains("### 2026-02-22 / 0.2.2\n\n- fix: tweak output\n- fix: retag output\n")
+      .and(predicate::str::contains("### 2026-02-20 / 0.2.0\n\n- feat: add output"))
+      .and(predicate::str::contains("/ 0.2.1").not()),
+  );
+}
+
+#[test]
+fn changelog_rebuild_can_date_sections_from_tags() {
+  let repo = create_repo();
+  fs::write(repo.path().join("cambi.yml"), "changelog_date_source: tag\n").expect("write config");
+
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-20T10:00:00Z");
+
+  let output = std::process::Command::new("git")
+    .current_dir(repo.path())
+    .env("GIT_COMMITTER_DATE", "2026-03-05T10:00:00Z")
+    .args(["tag", "-a", "v0.2.0", "-m", "Release 0.2.0"])
+    .output()
+    .expect("failed to run git tag");
+  assert!(output.status.success());
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["changelog", "--rebuild", "--dry-run"]);
+
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("### 2026-03-05 / 0.2.0").and(predicate::str::contains("2026-02-20").not()));
+}
+
+#[test]
+fn changelog_drops_reverted_commits_with_their_revert() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+  fs::write(repo.path().join("README.md"), "docs\n").expect("write fix file");
+  commit_with_date(repo.path(), "fix: keep readme", "2026-02-23T10:00:00Z");
+  git(repo.path(), &["revert", "--no-edit", "HEAD~1"]);
+
+  let body = git(repo.path(), &["log", "-1", "--format=%b"]);
+  assert!(body.starts_with("This reverts commit "));
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["changelog", "--dry-run"]);
+  cmd.assert().success().stdout(
+    predicate::str::contains("- fix: keep readme")
+      .and(predicate::str::contains("add output").not())
+      .and(predicate::str::contains("Revert").not()),
+  );
+}
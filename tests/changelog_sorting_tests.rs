@@ -1,7 +1,9 @@
 use cambi::{
   changelog::{
-    ChangelogSection, apply_default_sorting, extract_versions, format_date, render_section, with_prepended_section,
+    ChangelogSection, apply_default_sorting, display_body, extract_versions, format_date, render_section,
+    with_prepended_section,
   },
+  config::EffectiveConfig,
   git::GitCommit,
 };
 
@@ -9,34 +11,58 @@ use cambi::{
 fn apply_default_sorting_covers_priorities_and_time_tiebreak() {
   let mut commits = vec![
     GitCommit {
+      id: "".to_string(),
       subject: "fix: z".to_string(),
       body: "".to_string(),
       time: 2,
+      author_name: "".to_string(),
+      author_email: "".to_string(),
+      parent_count: 1,
     },
     GitCommit {
+      id: "".to_string(),
       subject: "feat: y".to_string(),
       body: "".to_string(),
       time: 1,
+      author_name: "".to_string(),
+      author_email: "".to_string(),
+      parent_count: 1,
     },
     GitCommit {
+      id: "".to_string(),
       subject: "chore: BREAKING CHANGE api".to_string(),
       body: "".to_string(),
       time: 3,
+      author_name: "".to_string(),
+      author_email: "".to_string(),
+      parent_count: 1,
     },
     GitCommit {
+      id: "".to_string(),
       subject: "refactor!: x".to_string(),
       body: "".to_string(),
       time: 3,
+      author_name: "".to_string(),
+      author_email: "".to_string(),
+      parent_count: 1,
     },
     GitCommit {
+      id: "".to_string(),
       subject: "docs: a".to_string(),
       body: "".to_string(),
       time: 4,
+      author_name: "".to_string(),
+      author_email: "".to_string(),
+      parent_count: 1,
     },
     GitCommit {
+      id: "".to_string(),
       subject: "fix: old".to_string(),
       body: "".to_string(),
       time: 1,
+      author_name: "".to_string(),
+      author_email: "".to_string(),
+      parent_count: 1,
     },
   ];
 
@@ -55,6 +81,7 @@ fn render_section_without_template_and_with_template() {
     date: "2026-02-22".to_string(),
     version: "1.2.3".to_string(),
     commits: vec!["feat: add".to_string()],
+    ..ChangelogSection::default()
   };
 
   let default_render = render_section(&section, None);
@@ -64,6 +91,139 @@ fn render_section_without_template_and_with_template() {
   assert_eq!(custom, "2026-02-22 1.2.3\n- feat: add");
 }
 
+#[test]
+fn render_section_credits_authors() {
+  let section = ChangelogSection {
+    date: "2026-02-22".to_string(),
+    version: "1.2.3".to_string(),
+    commits: vec!["feat: add".to_string(), "fix: typo".to_string()],
+    authors: vec!["Jane".to_string(), "Jane".to_string()],
+    show_authors: true,
+    ..ChangelogSection::default()
+  };
+
+  assert_eq!(
+    render_section(&section, None),
+    "### 2026-02-22 / 1.2.3\n\n- feat: add (@Jane)\n- fix: typo (@Jane)"
+  );
+  assert_eq!(
+    render_section(&section, Some("$VERSION by $AUTHOR\n$COMMITS")),
+    "1.2.3 by Jane\n- feat: add\n- fix: typo"
+  );
+}
+
+#[test]
+fn render_section_keeps_placeholders_inside_commit_subjects() {
+  let section = ChangelogSection {
+    date: "2026-02-22".to_string(),
+    version: "1.2.3".to_string(),
+    commits: vec!["docs: explain $URL and $HASH".to_string()],
+    authors: vec!["Jane".to_string()],
+    hash: "abc1234".to_string(),
+    url: "https://github.com/o/r/commit/abc1234".to_string(),
+    ..ChangelogSection::default()
+  };
+
+  assert_eq!(
+    render_section(&section, Some("$VERSION ($HASH) by $AUTHOR\n$COMMITS\n$URL")),
+    "1.2.3 (abc1234) by Jane\n- docs: explain $URL and $HASH\nhttps://github.com/o/r/commit/abc1234"
+  );
+}
+
+#[test]
+fn render_section_links_issue_references() {
+  let section = ChangelogSection {
+    date: "2026-02-22".to_string(),
+    version: "1.2.3".to_string(),
+    commits: vec!["fix: crash (#123)".to_string(), "fix: GH-7 and abc#123def".to_string()],
+    issues_url: Some("https://github.com/o/r/issues".to_string()),
+    ..ChangelogSection::default()
+  };
+
+  assert_eq!(
+    render_section(&section, None),
+    "### 2026-02-22 / 1.2.3\n\n- fix: crash ([#123](https://github.com/o/r/issues/123))\n- fix: \
+     [GH-7](https://github.com/o/r/issues/7) and abc#123def"
+  );
+
+  let section = ChangelogSection {
+    issues_url: None,
+    ..section
+  };
+  assert!(render_section(&section, None).contains("- fix: crash (#123)"));
+}
+
+#[test]
+fn render_section_annotates_bump_levels() {
+  let commit = |subject: &str, body: &str| {
+    GitCommit {
+      id: "abc1234".to_string(),
+      subject: subject.to_string(),
+      body: body.to_string(),
+      time: 1,
+      author_name: "".to_string(),
+      author_email: "".to_string(),
+      parent_count: 1,
+    }
+  };
+  let commits = vec![
+    commit("refactor: drop route", "BREAKING CHANGE: old route removed"),
+    commit("feat: add output", ""),
+    commit("fix: typo", ""),
+  ];
+  let config = EffectiveConfig {
+    changelog_annotate_bump: true,
+    ..EffectiveConfig::default()
+  };
+
+  let section = ChangelogSection::from_commits("2026-02-22".to_string(), "1.2.3".to_string(), commits, &config);
+  assert_eq!(
+    render_section(&section, None),
+    "### 2026-02-22 / 1.2.3\n\n- [major] refactor: drop route\n- [minor] feat: add output\n- [patch] fix: typo"
+  );
+
+  let section = ChangelogSection {
+    bumps: Vec::new(),
+    ..section
+  };
+  assert!(render_section(&section, None).contains("\n- feat: add output"));
+}
+
+#[test]
+fn render_section_indents_bodies_beneath_entries() {
+  let body = display_body(
+    "Adds the endpoint.\nIt supports paging.\n\nSee the docs.\n\nBREAKING CHANGE: old route removed\nCo-authored-by: \
+     Jane <jane@example.com>",
+  );
+  assert_eq!(body, "Adds the endpoint.\nIt supports paging.\n\nSee the docs.");
+
+  let section = ChangelogSection {
+    date: "2026-02-22".to_string(),
+    version: "1.2.3".to_string(),
+    commits: vec!["feat: add endpoint".to_string(), "fix: typo".to_string()],
+    bodies: vec![body, String::new()],
+    ..ChangelogSection::default()
+  };
+
+  assert_eq!(
+    render_section(&section, None),
+    "### 2026-02-22 / 1.2.3\n\n- feat: add endpoint\n  Adds the endpoint.\n  It supports paging.\n\n  See the \
+     docs.\n- fix: typo"
+  );
+}
+
+#[test]
+fn display_body_keeps_paragraphs_that_are_not_only_trailers() {
+  assert_eq!(display_body("Just text."), "Just text.");
+  assert_eq!(display_body("Signed-off-by: Jane <jane@example.com>"), "");
+  assert_eq!(
+    display_body("Intro.\n\nNote: this is prose\nand more."),
+    "Intro.\n\nNote: this is prose\nand more."
+  );
+  assert_eq!(display_body("Intro.\n\nNote: keep this"), "Intro.\n\nNote: keep this");
+  assert_eq!(display_body("Intro.\n\nRefs: #12\nReviewed-by: Jane"), "Intro.");
+}
+
 #[test]
 fn prepended_section_handles_empty_and_non_empty() {
   assert_eq!(with_prepended_section("", "A"), "A\n");
@@ -1,4 +1,16 @@
-use cambi::changelog::extract_versions;
+mod common;
+
+use std::fs;
+
+use cambi::{
+  changelog::{extract_versions, generate_changelog, is_version_recorded},
+  cli::ChangelogArgs,
+  config::EffectiveConfig,
+};
+use semver::Version;
+use serial_test::serial;
+
+use crate::common::{commit_with_date, create_repo};
 
 #[test]
 fn extracts_versions_from_default_headers() {
@@ -7,3 +19,85 @@ fn extracts_versions_from_default_headers() {
   assert!(versions.contains("1.2.3"));
   assert!(versions.contains("1.2.2"));
 }
+
+#[test]
+fn extracts_prerelease_versions() {
+  let markdown = "### 2026-02-22 / 1.2.3-rc.1\n\n- feat: x\n";
+  let versions = extract_versions(markdown);
+  assert!(versions.contains("1.2.3-rc.1"));
+}
+
+#[test]
+fn stable_version_is_not_covered_by_its_release_candidates() {
+  let versions =
+    extract_versions("### 2026-02-22 / 1.2.3-rc.2\n\n- feat: x\n\n### 2026-02-21 / 1.2.3-rc.1\n\n- fix: y\n");
+
+  assert!(!is_version_recorded(
+    &Version::parse("1.2.3").expect("parse"),
+    &versions
+  ));
+  assert!(is_version_recorded(
+    &Version::parse("1.2.3-rc.1").expect("parse"),
+    &versions
+  ));
+  assert!(is_version_recorded(
+    &Version::parse("1.2.3-rc.2").expect("parse"),
+    &versions
+  ));
+  assert!(!is_version_recorded(
+    &Version::parse("1.2.3-rc.3").expect("parse"),
+    &versions
+  ));
+}
+
+#[test]
+fn stable_version_covers_itself_and_its_release_candidates() {
+  let versions = extract_versions("### 2026-02-22 / 1.2.3\n\n- feat: x\n");
+
+  assert!(is_version_recorded(&Version::parse("1.2.3").expect("parse"), &versions));
+  assert!(is_version_recorded(
+    &Version::parse("1.2.3+build.5").expect("parse"),
+    &versions
+  ));
+  assert!(is_version_recorded(
+    &Version::parse("1.2.3-rc.1").expect("parse"),
+    &versions
+  ));
+  assert!(!is_version_recorded(
+    &Version::parse("1.2.4").expect("parse"),
+    &versions
+  ));
+  assert!(!is_version_recorded(
+    &Version::parse("1.2.2").expect("parse"),
+    &versions
+  ));
+}
+
+#[test]
+#[serial]
+fn generate_changelog_returns_markdown_without_writing() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+  fs::write(
+    repo.path().join("CHANGELOG.md"),
+    "### 2026-01-01 / 0.1.0\n\n- chore: init\n",
+  )
+  .expect("write changelog");
+
+  let config = EffectiveConfig {
+    tag_pattern: r"^v\d+\.\d+\.\d+$".to_string(),
+    ..EffectiveConfig::default()
+  };
+  let old = std::env::current_dir().expect("cwd");
+  std::env::set_current_dir(repo.path()).expect("set cwd");
+  let markdown = generate_changelog(&config, &ChangelogArgs::default());
+  std::env::set_current_dir(old).expect("restore cwd");
+
+  assert_eq!(
+    markdown.expect("generate changelog"),
+    "### 2026-02-22 / 0.2.0\n\n- feat: add output\n\n### 2026-01-01 / 0.1.0\n\n- chore: init\n"
+  );
+  let changelog = fs::read_to_string(repo.path().join("CHANGELOG.md")).expect("read changelog");
+  assert_eq!(changelog, "### 2026-01-01 / 0.1.0\n\n- chore: init\n");
+}
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
 use cambi::{
-  changelog::{bump_version, collect_releasable_commits, normalize_tag_version},
+  changelog::{bump_version, collect_releasable_commits, extract_section, normalize_tag_version},
+  config::EffectiveConfig,
   conventional::BumpLevel,
   filters::CommitFilter,
   git::GitCommit,
@@ -34,19 +37,31 @@ fn bump_version_covers_all_levels() {
 fn collect_releasable_commits_filters_chore_and_ignored() {
   let commits = vec![
     GitCommit {
+      id: "".to_string(),
       subject: "feat: add".to_string(),
       body: "".to_string(),
       time: 1,
+      author_name: "".to_string(),
+      author_email: "".to_string(),
+      parent_count: 1,
     },
     GitCommit {
+      id: "".to_string(),
       subject: "chore: clean".to_string(),
       body: "".to_string(),
       time: 2,
+      author_name: "".to_string(),
+      author_email: "".to_string(),
+      parent_count: 1,
     },
     GitCommit {
+      id: "".to_string(),
       subject: "wip: temp".to_string(),
       body: "".to_string(),
       time: 3,
+      author_name: "".to_string(),
+      author_email: "".to_string(),
+      parent_count: 1,
     },
   ];
 
@@ -55,3 +70,110 @@ fn collect_releasable_commits_filters_chore_and_ignored() {
   assert_eq!(kept.len(), 1);
   assert_eq!(kept[0].subject, "feat: add");
 }
+
+#[test]
+fn collect_releasable_commits_honors_excluded_types() {
+  let commit = |subject: &str, body: &str| {
+    GitCommit {
+      id: "".to_string(),
+      subject: subject.to_string(),
+      body: body.to_string(),
+      time: 1,
+      author_name: "".to_string(),
+      author_email: "".to_string(),
+      parent_count: 1,
+    }
+  };
+  let commits = vec![
+    commit("feat: add", ""),
+    commit("chore: clean", ""),
+    commit("CI: tweak workflow", ""),
+    commit("ci!: drop node 18", ""),
+    commit("build: bump deps", "BREAKING CHANGE: needs rust 1.85"),
+    commit("build: bump tools", ""),
+  ];
+
+  let filter = CommitFilter::new(&[])
+    .expect("regex")
+    .with_excluded_types(&["ci".to_string(), "build".to_string()]);
+  let kept: Vec<String> = collect_releasable_commits(commits, &filter)
+    .into_iter()
+    .map(|commit| commit.subject)
+    .collect();
+
+  assert_eq!(
+    kept,
+    vec!["feat: add", "chore: clean", "ci!: drop node 18", "build: bump deps"]
+  );
+}
+
+#[test]
+fn collect_releasable_commits_keeps_only_breaking_changes_of_excluded_types() {
+  let commit = |subject: &str| {
+    GitCommit {
+      id: "".to_string(),
+      subject: subject.to_string(),
+      body: "".to_string(),
+      time: 1,
+      author_name: "".to_string(),
+      author_email: "".to_string(),
+      parent_count: 1,
+    }
+  };
+  let commits = vec![
+    commit("ci(docs)!: reword workflow"),
+    commit("ci(api)!: drop node 18"),
+    commit("chore: rewrite config"),
+  ];
+
+  let filter = CommitFilter::new(&[])
+    .expect("regex")
+    .with_excluded_types(&["ci".to_string(), "chore".to_string()])
+    .with_breaking_scopes(Some(&["api".to_string()]));
+  let kept: Vec<String> = collect_releasable_commits(commits, &filter)
+    .into_iter()
+    .map(|commit| commit.subject)
+    .collect();
+
+  assert_eq!(kept, vec!["ci(api)!: drop node 18"]);
+}
+
+#[test]
+fn collect_releasable_commits_excludes_types_mapped_to_major() {
+  let commit = |subject: &str| {
+    GitCommit {
+      id: "".to_string(),
+      subject: subject.to_string(),
+      body: "".to_string(),
+      time: 1,
+      author_name: "".to_string(),
+      author_email: "".to_string(),
+      parent_count: 1,
+    }
+  };
+  let config = EffectiveConfig {
+    bump_map: HashMap::from([("chore".to_string(), BumpLevel::Major)]),
+    ..EffectiveConfig::default()
+  };
+
+  let filter = CommitFilter::from_config(&config).expect("filter");
+  let kept: Vec<String> =
+    collect_releasable_commits(vec![commit("chore: rewrite config"), commit("fix: typo")], &filter)
+      .into_iter()
+      .map(|commit| commit.subject)
+      .collect();
+
+  assert_eq!(kept, vec!["fix: typo"]);
+}
+
+#[test]
+fn extract_section_returns_the_text_between_headings() {
+  let markdown = "### 2026-02-22 / 1.1.0\n\n- feat: edited by hand\n\n### 2026-01-01 / 1.0.0\n\n- first\n";
+
+  assert_eq!(
+    extract_section(markdown, "1.1.0").as_deref(),
+    Some("- feat: edited by hand")
+  );
+  assert_eq!(extract_section(markdown, "1.0.0").as_deref(), Some("- first"));
+  assert_eq!(extract_section(markdown, "0.9.0"), None);
+}
@@ -18,6 +18,55 @@ fn semver_outputs_minor_for_feat_since_latest_tag() {
   cmd.assert().success().stdout("minor\n");
 }
 
+#[test]
+fn semver_honors_bump_map_from_config() {
+  let repo = create_repo();
+  fs::write(repo.path().join("cambi.yml"), "bump_map:\n  perf: minor\n").expect("write config");
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write file");
+  commit_with_date(repo.path(), "perf: faster output", "2026-02-22T10:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).arg("semver");
+  cmd.assert().success().stdout("minor\n");
+}
+
+#[test]
+fn semver_stats_tallies_pending_commits_by_kind() {
+  let repo = create_repo();
+  for (index, subject) in [
+    "feat: add output",
+    "fix: tweak output",
+    "feat(api)!: redesign",
+    "docs: explain",
+  ]
+  .iter()
+  .enumerate()
+  {
+    fs::write(
+      repo.path().join("src/lib.rs"),
+      format!("pub fn a() {{ println!(\"{index}\"); }}\n"),
+    )
+    .expect("write file");
+    commit_with_date(repo.path(), subject, &format!("2026-02-22T1{index}:00:00Z"));
+  }
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["semver", "--stats"]);
+  cmd
+    .assert()
+    .success()
+    .stdout("major\nfeatures: 1, fixes: 1, breaking: 1, other: 1\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--format", "json", "semver", "--stats"]);
+  cmd
+    .assert()
+    .success()
+    .stdout("{\"bump\":\"major\",\"stats\":{\"features\":1,\"fixes\":1,\"breaking\":1,\"other\":1}}\n");
+}
+
 #[test]
 fn changelog_dry_run_generates_expected_changelog_section() {
   let repo = create_repo();
@@ -54,6 +103,63 @@ fn release_notes_only_prints_generated_notes() {
     .stdout(predicate::str::contains("- feat: add output"));
 }
 
+#[test]
+fn release_from_changelog_uses_edited_section_or_falls_back_to_git() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+  git(repo.path(), &["tag", "v0.2.0"]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--verbose", "release", "--notes-only", "--from-changelog"]);
+  cmd
+    .assert()
+    .success()
+    .stdout("- feat: add output\n")
+    .stderr(predicate::str::contains("Version 0.2.0 not found in CHANGELOG.md"));
+
+  fs::write(
+    repo.path().join("CHANGELOG.md"),
+    "### 2026-02-22 / 0.2.0\n\n- feat: add output, now with colors\n\n### 2026-01-01 / 0.1.0\n\n- first\n",
+  )
+  .expect("write changelog");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["release", "--notes-only", "--from-changelog"]);
+  cmd.assert().success().stdout("- feat: add output, now with colors\n");
+}
+
+#[test]
+fn release_from_changelog_links_the_full_changelog_when_the_section_is_too_long() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+  git(repo.path(), &["tag", "v0.2.0"]);
+  fs::write(
+    repo.path().join("cambi.yml"),
+    "owner: o\nrepo: r\nrelease_max_body_bytes: 40\n",
+  )
+  .expect("write config");
+  fs::write(
+    repo.path().join("CHANGELOG.md"),
+    "### 2026-02-22 / 0.2.0\n\n- feat: add output, now with colors and a much longer description\n",
+  )
+  .expect("write changelog");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["release", "--notes-only", "--from-changelog"]);
+  cmd
+    .assert()
+    .success()
+    .stdout("See the full changelog: https://github.com/o/r/compare/v0.1.0...v0.2.0\n");
+}
+
 #[test]
 fn release_notes_only_filters_non_releasable_commits() {
   let repo = create_repo();
@@ -106,3 +212,288 @@ fn release_fails_without_matching_tags() {
     .failure()
     .stderr(predicate::str::contains("No matching git tags found"));
 }
+
+#[test]
+fn config_schema_prints_json_schema_without_a_command() {
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.arg("--config-schema");
+  let output = cmd.assert().success().get_output().stdout.clone();
+
+  let schema: serde_json::Value = serde_json::from_slice(&output).expect("json schema");
+  assert!(schema["properties"]["tag_pattern"].is_object());
+  assert!(schema["properties"]["release"].is_object());
+  assert!(schema["$defs"]["CommandConfig"]["properties"]["tag_pattern"].is_object());
+  assert!(schema["$defs"]["CommandConfig"]["properties"]["changelog"].is_null());
+}
+
+#[test]
+fn missing_command_is_rejected() {
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.arg("--verbose");
+  cmd.assert().failure();
+}
+
+#[test]
+fn semver_max_commits_caps_history_traversal() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"y\"); }\n").expect("write fix file");
+  commit_with_date(repo.path(), "fix: tweak output", "2026-02-22T11:00:00Z");
+
+  let mut uncapped = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  uncapped.current_dir(repo.path()).arg("semver");
+  uncapped.assert().success().stdout("minor\n");
+
+  let mut capped = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  capped.current_dir(repo.path()).args(["--max-commits", "1", "semver"]);
+  capped.assert().success().stdout("patch\n");
+}
+
+#[test]
+fn semver_paths_only_count_commits_under_any_listed_prefix() {
+  let repo = create_repo();
+  fs::create_dir_all(repo.path().join("packages/a")).expect("create package a");
+  fs::create_dir_all(repo.path().join("packages/b")).expect("create package b");
+  fs::create_dir_all(repo.path().join("packages/ab")).expect("create package ab");
+
+  fs::write(repo.path().join("packages/a/lib.rs"), "fn a() {}\n").expect("write package a");
+  commit_with_date(repo.path(), "fix: tweak a", "2026-02-22T10:00:00Z");
+  fs::write(repo.path().join("packages/ab/lib.rs"), "fn ab() {}\n").expect("write package ab");
+  commit_with_date(repo.path(), "feat!: redesign ab", "2026-02-22T11:00:00Z");
+
+  let mut only_a = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  only_a.current_dir(repo.path()).args(["--path", "packages/a", "semver"]);
+  only_a.assert().success().stdout("patch\n");
+
+  fs::write(repo.path().join("packages/b/lib.rs"), "fn b() {}\n").expect("write package b");
+  commit_with_date(repo.path(), "feat: add b", "2026-02-22T12:00:00Z");
+
+  let mut a_or_b = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  a_or_b
+    .current_dir(repo.path())
+    .args(["--path", "./packages/a", "--path", "packages/b/", "semver"]);
+  a_or_b.assert().success().stdout("minor\n");
+}
+
+#[test]
+fn completions_prints_shell_script() {
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .args(["completions", "bash"])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("_cambi()").and(predicate::str::contains("changelog")));
+}
+
+#[test]
+fn semver_print_range_names_start_tag_and_head() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+
+  let tag_sha = git(repo.path(), &["rev-parse", "--short=7", "v0.1.0^{commit}"]);
+  let head_sha = git(repo.path(), &["rev-parse", "--short=7", "HEAD"]);
+  let expected = format!("Commit range: v0.1.0 ({})..HEAD ({})", tag_sha.trim(), head_sha.trim());
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["semver", "--print-range"]);
+  cmd
+    .assert()
+    .success()
+    .stdout("minor\n")
+    .stderr(predicate::str::contains(expected));
+}
+
+#[test]
+fn version_and_semver_support_json_format() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+
+  let mut version = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  version.current_dir(repo.path()).args(["--format", "json", "version"]);
+  version.assert().success().stdout("{\"version\":\"0.1.0\"}\n");
+
+  let mut semver = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  semver.current_dir(repo.path()).args(["semver", "--format", "json"]);
+  semver.assert().success().stdout("{\"bump\":\"minor\"}\n");
+
+  let mut next = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  next
+    .current_dir(repo.path())
+    .args(["--format", "json", "version", "--next", "patch,major"]);
+  next
+    .assert()
+    .success()
+    .stdout("{\"patch\":\"0.1.1\",\"major\":\"1.0.0\"}\n");
+}
+
+#[test]
+fn fetch_reads_the_tags_of_the_origin_remote() {
+  let upstream = create_repo();
+  fs::write(upstream.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write file");
+  commit_with_date(upstream.path(), "feat: add output", "2030-01-01T00:00:00Z");
+  git(upstream.path(), &["tag", "v0.3.0"]);
+
+  let repo = create_repo();
+  let remotes = tempfile::tempdir().expect("create remotes dir");
+  let remote = remotes.path().join("upstream.git");
+  git(
+    upstream.path(),
+    &["clone", "-q", "--bare", ".", remote.to_str().expect("remote path")],
+  );
+  git(
+    repo.path(),
+    &["remote", "add", "origin", remote.to_str().expect("remote path")],
+  );
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["--fetch", "version"]);
+  cmd.assert().success().stdout("0.3.0\n");
+}
+
+#[test]
+fn fetch_retries_the_configured_number_of_times() {
+  let repo = create_repo();
+  git(
+    repo.path(),
+    &["remote", "add", "origin", "/nonexistent/cambi-remote.git"],
+  );
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--fetch", "--max-retries", "2", "--verbose", "version"]);
+  let output = cmd.assert().success().stdout("0.1.0\n").get_output().clone();
+  let stderr = String::from_utf8(output.stderr).expect("utf8 stderr");
+
+  assert_eq!(stderr.matches("Fetching tags failed").count(), 2);
+  assert!(stderr.contains("Using local tags."));
+}
+
+#[test]
+fn fetch_failure_falls_back_to_local_tags() {
+  let repo = create_repo();
+  git(
+    repo.path(),
+    &["remote", "add", "origin", "/nonexistent/cambi-remote.git"],
+  );
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--fetch", "--max-retries", "0", "version"]);
+  cmd
+    .assert()
+    .success()
+    .stdout("0.1.0\n")
+    .stderr(predicate::str::contains("Cannot fetch tags from origin"))
+    .stderr(predicate::str::contains("Using local tags."));
+}
+
+#[test]
+fn invalid_format_is_rejected() {
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .args(["--format", "xml", "version"])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("invalid value 'xml'"));
+}
+
+#[test]
+fn version_reachable_ignores_tags_outside_the_branch() {
+  let repo = create_repo();
+  git(repo.path(), &["checkout", "-q", "-b", "side"]);
+  fs::write(repo.path().join("side.txt"), "x").expect("write side file");
+  commit_with_date(repo.path(), "feat: side", "2026-02-01T00:00:00Z");
+
+  git(repo.path(), &["checkout", "-q", "-"]);
+  fs::write(repo.path().join("main.txt"), "x").expect("write main file");
+  commit_with_date(repo.path(), "feat: main", "2026-02-02T00:00:00Z");
+  git(repo.path(), &["tag", "v0.2.0"]);
+  git(repo.path(), &["checkout", "-q", "side"]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["version"]);
+  cmd.assert().success().stdout("0.2.0\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["version", "--reachable"]);
+  cmd.assert().success().stdout("0.1.0\n");
+}
+
+#[test]
+fn version_validate_tag_pattern_reports_derived_tag_or_error() {
+  let repo = create_repo();
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["version", "--validate-tag-pattern"]);
+  cmd.assert().success().stdout("v0.1.0\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--tag-pattern", r"^release/\d+$", "version", "--validate-tag-pattern"]);
+  cmd.assert().failure().stderr(predicate::str::contains(
+    r"Cannot derive tag from pattern '^release/\d+$' for version 0.0.0 (tried v0.0.0, 0.0.0)",
+  ));
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args([
+    "--tag-pattern",
+    r"^release-\d+\.\d+\.\d+$",
+    "version",
+    "--validate-tag-pattern",
+  ]);
+  cmd.assert().failure().stderr(predicate::str::contains(
+    "Tag 'release-0.0.0' derived from pattern '^release-\\d+\\.\\d+\\.\\d+$' does not read back as version 0.0.0",
+  ));
+}
+
+#[test]
+fn config_none_ignores_local_config_file() {
+  let repo = create_repo();
+  fs::write(
+    repo.path().join("cambi.yml"),
+    "tag_pattern: '^release-\\d+\\.\\d+\\.\\d+$'\n",
+  )
+  .expect("write config");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["version"]);
+  cmd.assert().success().stdout("0.0.0\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["--config", "none", "version"]);
+  cmd.assert().success().stdout("0.1.0\n");
+}
+
+#[test]
+fn version_check_consistency_passes_when_file_matches_tag() {
+  let repo = create_repo();
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["version", "--check-consistency"]);
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Cargo.toml matches the latest tag at 0.1.0"));
+}
+
+#[test]
+fn version_check_consistency_fails_on_mismatch() {
+  let repo = create_repo();
+  fs::write(
+    repo.path().join("Cargo.toml"),
+    "[package]\nname = \"fixture\"\nversion = \"0.2.0\"\n",
+  )
+  .expect("write Cargo.toml");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["version", "--check-consistency"]);
+  cmd.assert().failure().stderr(predicate::str::contains(
+    "Version mismatch: Cargo.toml is at 0.2.0, but the latest tag is at 0.1.0",
+  ));
+}
@@ -85,6 +85,12 @@ fn version_from_tag_is_parsed() {
   assert!(parsed.is_ok());
 }
 
+#[test]
+fn version_next_rejects_unknown_levels() {
+  let parsed = Args::try_parse_from(["cambi", "version", "--next", "patch,huge"]);
+  assert!(parsed.is_err());
+}
+
 #[test]
 fn semver_rejects_current_flag() {
   let parsed = Args::try_parse_from(["cambi", "semver", "--current"]);
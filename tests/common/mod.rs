@@ -1,8 +1,94 @@
 #![allow(dead_code)]
 
-use std::{fs, path::Path, process::Command};
+use std::{
+  fs,
+  path::Path,
+  process::Command,
+  sync::{Arc, Mutex},
+  thread,
+  time::Duration,
+};
 
 use tempfile::TempDir;
+use tiny_http::{Header, Method, Response, Server};
+
+pub struct RecordedRequest {
+  pub method: Method,
+  pub url: String,
+  pub headers: Vec<(String, String)>,
+  pub body: String,
+}
+
+impl RecordedRequest {
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self
+      .headers
+      .iter()
+      .find(|(field, _)| field.eq_ignore_ascii_case(name))
+      .map(|(_, value)| value.as_str())
+  }
+}
+
+/// Serves the given `(method, path, status, body)` responses in order and
+/// records every request received.
+pub fn spawn_mock_server(responses: Vec<(Method, String, u16, String)>) -> (String, Arc<Mutex<Vec<RecordedRequest>>>) {
+  spawn_mock_server_with_headers(
+    responses
+      .into_iter()
+      .map(|(method, path, status, body)| (method, path, status, body, Vec::new()))
+      .collect(),
+  )
+}
+
+/// Expected request method and path, then the status, body and headers of the
+/// response.
+pub type MockResponse = (Method, String, u16, String, Vec<(&'static str, String)>);
+
+/// Like [`spawn_mock_server`], also sending the given headers with each
+/// response.
+pub fn spawn_mock_server_with_headers(responses: Vec<MockResponse>) -> (String, Arc<Mutex<Vec<RecordedRequest>>>) {
+  let server = Server::http("127.0.0.1:0").expect("start server");
+  let addr = format!("http://{}", server.server_addr());
+  let seen = Arc::new(Mutex::new(Vec::new()));
+  let seen_clone = Arc::clone(&seen);
+
+  thread::spawn(move || {
+    for (method, path, status, body, headers) in responses {
+      let mut request = server
+        .recv_timeout(Duration::from_secs(10))
+        .expect("receive request")
+        .expect("some request");
+      assert_eq!(request.method(), &method);
+      assert_eq!(request.url(), path);
+
+      let mut request_body = String::new();
+      request
+        .as_reader()
+        .read_to_string(&mut request_body)
+        .expect("read request body");
+
+      seen_clone.lock().expect("lock").push(RecordedRequest {
+        method: request.method().clone(),
+        url: request.url().to_string(),
+        headers: request
+          .headers()
+          .iter()
+          .map(|header| (header.field.to_string(), header.value.to_string()))
+          .collect(),
+        body: request_body,
+      });
+
+      let mut response = Response::from_string(body).with_status_code(status);
+      for (name, value) in headers {
+        response.add_header(Header::from_bytes(name, value).expect("valid header"));
+      }
+
+      request.respond(response).expect("respond");
+    }
+  });
+
+  (addr, seen)
+}
 
 pub fn git(dir: &Path, args: &[&str]) -> String {
   let output = Command::new("git")
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, env, fs, path::Path};
 
-use cambi::config::{ConfigOverrides, EffectiveConfig, FileConfig, load_file};
+use cambi::config::{CommandConfig, ConfigOverrides, EffectiveConfig, FileConfig, load_file};
 use serial_test::serial;
 use tempfile::TempDir;
 
@@ -15,12 +15,16 @@ fn write_config(path: &Path, content: &str) {
 #[serial]
 fn precedence_is_flags_over_env_over_config_over_defaults() {
   let config = FileConfig {
-    token: Some("config-token".into()),
-    owner: Some("config-owner".into()),
-    repo: Some("config-repo".into()),
-    tag_pattern: Some("config-tag".into()),
-    changelog_template: Some("config-template".into()),
-    ignore_patterns: Some(vec!["config-ignore".into()]),
+    common: CommandConfig {
+      token: Some("config-token".into()),
+      owner: Some("config-owner".into()),
+      repo: Some("config-repo".into()),
+      tag_pattern: Some("config-tag".into()),
+      changelog_template: Some("config-template".into()),
+      ignore_patterns: Some(vec!["config-ignore".into()]),
+      ..CommandConfig::default()
+    },
+    ..FileConfig::default()
   };
 
   let env = HashMap::from([
@@ -31,37 +35,110 @@ fn precedence_is_flags_over_env_over_config_over_defaults() {
     ("CAMBI_CHANGELOG_TEMPLATE".to_string(), "env-template".to_string()),
     ("CAMBI_IGNORE_PATTERNS".to_string(), "env-a; env-b".to_string()),
     ("CAMBI_VERBOSE".to_string(), "yes".to_string()),
+    ("CAMBI_MAX_COMMITS".to_string(), "10".to_string()),
   ]);
 
   let flags = ConfigOverrides {
+    provider: None,
+    api_base: None,
+    web_host: None,
     token: Some("flag-token".into()),
     owner: Some("flag-owner".into()),
     repo: Some("flag-repo".into()),
     tag_pattern: Some("flag-tag".into()),
+    tag_prefix: None,
+    initial_version: None,
+    max_commits: Some(5),
+    paths: None,
+    authors: None,
+    exclude_authors: None,
+    include_merge_prs: None,
+    continue_on_error: None,
+    fetch: None,
+    max_retries: None,
+    format: None,
+    offline: None,
     verbose: Some(true),
   };
 
-  let resolved = EffectiveConfig::from_sources(Some(config), &env, flags);
+  let resolved = EffectiveConfig::from_sources(Some(config), "release", &env, flags).expect("config");
 
   assert_eq!(resolved.token.as_deref(), Some("flag-token"));
   assert_eq!(resolved.owner.as_deref(), Some("flag-owner"));
   assert_eq!(resolved.repo.as_deref(), Some("flag-repo"));
   assert_eq!(resolved.tag_pattern, "flag-tag");
+  assert_eq!(resolved.max_commits, Some(5));
   assert_eq!(resolved.changelog_template.as_deref(), Some("env-template"));
   assert_eq!(resolved.ignore_patterns, vec!["env-a", "env-b"]);
   assert!(resolved.verbose);
 }
 
+#[test]
+fn invalid_provider_from_env_is_reported() {
+  let env = HashMap::from([("CAMBI_PROVIDER".to_string(), "bitbucket".to_string())]);
+
+  let error = EffectiveConfig::from_sources(None, "release", &env, ConfigOverrides::default()).expect_err("provider");
+  assert_eq!(
+    error.to_string(),
+    "Invalid provider 'bitbucket' (expected github, gitlab or gitea)"
+  );
+}
+
+#[test]
+fn invalid_max_commits_from_env_is_reported() {
+  let env = HashMap::from([("CAMBI_MAX_COMMITS".to_string(), "100k".to_string())]);
+
+  let error =
+    EffectiveConfig::from_sources(None, "release", &env, ConfigOverrides::default()).expect_err("max commits");
+  assert_eq!(
+    error.to_string(),
+    "Invalid CAMBI_MAX_COMMITS '100k' (expected a number)"
+  );
+}
+
 #[test]
 #[serial]
 fn defaults_are_applied_when_no_source_provides_values() {
-  let resolved = EffectiveConfig::from_sources(None, &HashMap::new(), ConfigOverrides::default());
+  let resolved =
+    EffectiveConfig::from_sources(None, "release", &HashMap::new(), ConfigOverrides::default()).expect("config");
 
   assert_eq!(resolved.tag_pattern, r"^v\d+\.\d+\.\d+$");
   assert_eq!(resolved.ignore_patterns.len(), 7);
   assert!(!resolved.verbose);
 }
 
+#[test]
+#[serial]
+fn command_section_overrides_top_level_only_for_that_command() {
+  let temp = TempDir::new().expect("temp dir");
+  let config_path = temp.path().join("sections.yml");
+  write_config(
+    &config_path,
+    "tag_pattern: loose\nowner: org\nrelease:\n  tag_pattern: strict\n  changelog:\n    tag_pattern: nested\n",
+  );
+
+  let loaded = load_file(Some(&config_path)).expect("load config");
+
+  let release = EffectiveConfig::from_sources(loaded.clone(), "release", &HashMap::new(), ConfigOverrides::default())
+    .expect("config");
+  assert_eq!(release.tag_pattern, "strict");
+  assert_eq!(release.owner.as_deref(), Some("org"));
+
+  let changelog =
+    EffectiveConfig::from_sources(loaded, "changelog", &HashMap::new(), ConfigOverrides::default()).expect("config");
+  assert_eq!(changelog.tag_pattern, "loose");
+
+  let env = HashMap::from([("CAMBI_TAG_PATTERN".to_string(), "env-tag".to_string())]);
+  let release = EffectiveConfig::from_sources(
+    load_file(Some(&config_path)).expect("load config"),
+    "release",
+    &env,
+    ConfigOverrides::default(),
+  )
+  .expect("config");
+  assert_eq!(release.tag_pattern, "env-tag");
+}
+
 #[test]
 #[serial]
 fn load_file_reads_explicit_override() {
@@ -72,9 +149,9 @@ fn load_file_reads_explicit_override() {
   let loaded = load_file(Some(&config_path))
     .expect("load config")
     .expect("config exists");
-  assert_eq!(loaded.token.as_deref(), Some("abc"));
-  assert_eq!(loaded.owner.as_deref(), Some("org"));
-  assert_eq!(loaded.repo.as_deref(), Some("proj"));
+  assert_eq!(loaded.common.token.as_deref(), Some("abc"));
+  assert_eq!(loaded.common.owner.as_deref(), Some("org"));
+  assert_eq!(loaded.common.repo.as_deref(), Some("proj"));
 }
 
 #[test]
@@ -103,11 +180,11 @@ fn load_file_merges_global_then_local_with_local_priority() {
 
   let loaded = load_file(None).expect("load config").expect("config exists");
 
-  assert_eq!(loaded.token.as_deref(), Some("global-token"));
-  assert_eq!(loaded.owner.as_deref(), Some("local-owner"));
-  assert_eq!(loaded.repo.as_deref(), Some("global-repo"));
-  assert_eq!(loaded.tag_pattern.as_deref(), Some("local-tag"));
-  assert_eq!(loaded.ignore_patterns.unwrap_or_default(), vec!["local-ignore"]);
+  assert_eq!(loaded.common.token.as_deref(), Some("global-token"));
+  assert_eq!(loaded.common.owner.as_deref(), Some("local-owner"));
+  assert_eq!(loaded.common.repo.as_deref(), Some("global-repo"));
+  assert_eq!(loaded.common.tag_pattern.as_deref(), Some("local-tag"));
+  assert_eq!(loaded.common.ignore_patterns.unwrap_or_default(), vec!["local-ignore"]);
 
   if let Some(old_home) = old_home {
     // SAFETY: restoring process env var in test teardown.
@@ -134,10 +211,18 @@ fn load_file_returns_error_for_invalid_yaml() {
 #[serial]
 fn env_can_enable_verbose_without_flag() {
   let env = HashMap::from([("CAMBI_VERBOSE".to_string(), "true".to_string())]);
-  let resolved = EffectiveConfig::from_sources(None, &env, ConfigOverrides::default());
+  let resolved = EffectiveConfig::from_sources(None, "release", &env, ConfigOverrides::default()).expect("config");
   assert!(resolved.verbose);
 }
 
+#[test]
+#[serial]
+fn web_host_strips_the_scheme_and_trailing_slash() {
+  let env = HashMap::from([("CAMBI_WEB_HOST".to_string(), "https://git.example.com/".to_string())]);
+  let resolved = EffectiveConfig::from_sources(None, "release", &env, ConfigOverrides::default()).expect("config");
+  assert_eq!(resolved.web_host(), "git.example.com");
+}
+
 #[test]
 #[serial]
 fn env_cambi_token_beats_gh_release_token() {
@@ -146,7 +231,7 @@ fn env_cambi_token_beats_gh_release_token() {
     ("GH_RELEASE_TOKEN".to_string(), "gh-token".to_string()),
   ]);
 
-  let resolved = EffectiveConfig::from_sources(None, &env, ConfigOverrides::default());
+  let resolved = EffectiveConfig::from_sources(None, "release", &env, ConfigOverrides::default()).expect("config");
   assert_eq!(resolved.token.as_deref(), Some("cambi-token"));
 }
 
@@ -154,7 +239,7 @@ fn env_cambi_token_beats_gh_release_token() {
 #[serial]
 fn ignore_patterns_empty_entries_are_trimmed() {
   let env = HashMap::from([("CAMBI_IGNORE_PATTERNS".to_string(), "a;; b ; ".to_string())]);
-  let resolved = EffectiveConfig::from_sources(None, &env, ConfigOverrides::default());
+  let resolved = EffectiveConfig::from_sources(None, "release", &env, ConfigOverrides::default()).expect("config");
   assert_eq!(resolved.ignore_patterns, vec!["a", "b"]);
 }
 
@@ -164,12 +249,14 @@ fn verbose_flag_false_overrides_true_env() {
   let env = HashMap::from([("CAMBI_VERBOSE".to_string(), "true".to_string())]);
   let resolved = EffectiveConfig::from_sources(
     None,
+    "release",
     &env,
     ConfigOverrides {
       verbose: Some(false),
       ..ConfigOverrides::default()
     },
-  );
+  )
+  .expect("config");
   assert!(!resolved.verbose);
 }
 
@@ -224,3 +311,42 @@ fn load_file_works_when_home_is_unset() {
   }
   env::set_current_dir(old_cwd).expect("restore cwd");
 }
+
+#[test]
+#[serial]
+fn api_base_prefers_config_over_legacy_env_and_validates() {
+  let config = FileConfig {
+    common: CommandConfig {
+      api_base: Some("https://ghe.example.com/api/v3/".into()),
+      ..CommandConfig::default()
+    },
+    ..FileConfig::default()
+  };
+  let env = HashMap::from([(
+    "CAMBI_GITHUB_API_BASE".to_string(),
+    "http://legacy.example.com".to_string(),
+  )]);
+
+  let resolved =
+    EffectiveConfig::from_sources(Some(config), "release", &env, ConfigOverrides::default()).expect("config");
+  assert_eq!(
+    resolved.api_base_url().expect("valid"),
+    "https://ghe.example.com/api/v3"
+  );
+
+  let resolved = EffectiveConfig::from_sources(None, "release", &env, ConfigOverrides::default()).expect("config");
+  assert_eq!(resolved.api_base, "http://legacy.example.com");
+
+  let resolved = EffectiveConfig::from_sources(
+    None,
+    "release",
+    &HashMap::new(),
+    ConfigOverrides {
+      api_base: Some("ghe.example.com".into()),
+      ..ConfigOverrides::default()
+    },
+  )
+  .expect("config");
+  let error = resolved.api_base_url().expect_err("should fail");
+  assert!(error.to_string().contains("Invalid API base URL"));
+}
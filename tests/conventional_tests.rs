@@ -1,33 +1,35 @@
-use cambi::conventional::{BumpLevel, infer_bump};
+use std::collections::HashMap;
+
+use cambi::conventional::{BumpLevel, infer_bump, strip_prefix, strip_scope};
 
 #[test]
 fn detects_major_from_bang() {
-  assert_eq!(infer_bump("feat(api)!: redesign", ""), BumpLevel::Major);
+  assert_eq!(infer_bump("feat(api)!: redesign", "", None, None), BumpLevel::Major);
 }
 
 #[test]
 fn detects_major_from_footer() {
   assert_eq!(
-    infer_bump("feat(api): redesign", "something\nBREAKING CHANGE: api"),
+    infer_bump("feat(api): redesign", "something\nBREAKING CHANGE: api", None, None),
     BumpLevel::Major
   );
 }
 
 #[test]
 fn detects_minor_from_feat() {
-  assert_eq!(infer_bump("feat(ui): add button", ""), BumpLevel::Minor);
+  assert_eq!(infer_bump("feat(ui): add button", "", None, None), BumpLevel::Minor);
 }
 
 #[test]
 fn defaults_to_patch() {
-  assert_eq!(infer_bump("docs: update", ""), BumpLevel::Patch);
-  assert_eq!(infer_bump("non conventional message", ""), BumpLevel::Patch);
+  assert_eq!(infer_bump("docs: update", "", None, None), BumpLevel::Patch);
+  assert_eq!(infer_bump("non conventional message", "", None, None), BumpLevel::Patch);
 }
 
 #[test]
 fn supports_breaking_change_dash_footer_and_as_str() {
   assert_eq!(
-    infer_bump("refactor(core): update", "BREAKING-CHANGE: api"),
+    infer_bump("refactor(core): update", "BREAKING-CHANGE: api", None, None),
     BumpLevel::Major
   );
 
@@ -35,3 +37,70 @@ fn supports_breaking_change_dash_footer_and_as_str() {
   assert_eq!(BumpLevel::Minor.as_str(), "minor");
   assert_eq!(BumpLevel::Major.as_str(), "major");
 }
+
+#[test]
+fn strip_scope_keeps_type_and_description() {
+  assert_eq!(strip_scope("feat(api): add endpoint"), "feat: add endpoint");
+  assert_eq!(strip_scope("fix(ui/button): align label"), "fix: align label");
+}
+
+#[test]
+fn strip_scope_keeps_breaking_marker() {
+  assert_eq!(strip_scope("feat(api)!: redesign"), "feat!: redesign");
+  assert_eq!(strip_scope("feat!: redesign"), "feat!: redesign");
+}
+
+#[test]
+fn strip_scope_leaves_unscoped_subjects_untouched() {
+  assert_eq!(strip_scope("feat: add endpoint"), "feat: add endpoint");
+  assert_eq!(strip_scope("fix: handle (x): y"), "fix: handle (x): y");
+  assert_eq!(strip_scope("Merge (upstream): sync"), "Merge (upstream): sync");
+  assert_eq!(strip_scope("non conventional message"), "non conventional message");
+}
+
+#[test]
+fn strip_prefix_keeps_description_and_breaking_note() {
+  assert_eq!(strip_prefix("feat(api): add endpoint"), "add endpoint");
+  assert_eq!(strip_prefix("fix: handle (x): y"), "handle (x): y");
+  assert_eq!(strip_prefix("feat(api)!: redesign"), "redesign (breaking)");
+  assert_eq!(strip_prefix("Merge (upstream): sync"), "Merge (upstream): sync");
+  assert_eq!(strip_prefix("non conventional message"), "non conventional message");
+}
+
+#[test]
+fn honors_custom_bump_map() {
+  let bump_map = HashMap::from([
+    ("perf".to_string(), BumpLevel::Minor),
+    ("feat".to_string(), BumpLevel::Patch),
+  ]);
+
+  assert_eq!(
+    infer_bump("perf(db): faster query", "", Some(&bump_map), None),
+    BumpLevel::Minor
+  );
+  assert_eq!(infer_bump("feat: add", "", Some(&bump_map), None), BumpLevel::Patch);
+  assert_eq!(infer_bump("fix: typo", "", Some(&bump_map), None), BumpLevel::Patch);
+  assert_eq!(
+    infer_bump("perf!: drop cache", "", Some(&bump_map), None),
+    BumpLevel::Major
+  );
+}
+
+#[test]
+fn breaking_scopes_limit_bang_escalation() {
+  let scopes = ["api".to_string()];
+
+  assert_eq!(
+    infer_bump("feat(api)!: redesign", "", None, Some(&scopes)),
+    BumpLevel::Major
+  );
+  assert_eq!(
+    infer_bump("feat(internal)!: redesign", "", None, Some(&scopes)),
+    BumpLevel::Minor
+  );
+  assert_eq!(infer_bump("fix!: redesign", "", None, Some(&scopes)), BumpLevel::Major);
+  assert_eq!(
+    infer_bump("fix(internal): redesign", "BREAKING CHANGE: gone", None, Some(&scopes)),
+    BumpLevel::Major
+  );
+}
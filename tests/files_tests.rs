@@ -0,0 +1,40 @@
+use std::fs;
+
+use cambi::files::write_atomic;
+use tempfile::TempDir;
+
+#[test]
+fn write_atomic_replaces_content_without_leaving_temp_files() {
+  let temp = TempDir::new().expect("tmp");
+  let file = temp.path().join("CHANGELOG.md");
+  fs::write(&file, "old content that is longer than the new one\n").expect("write");
+
+  write_atomic(&file, "new\n").expect("atomic write");
+
+  assert_eq!(fs::read_to_string(&file).expect("read"), "new\n");
+
+  let entries = fs::read_dir(temp.path())
+    .expect("read dir")
+    .map(|entry| entry.expect("entry").file_name().to_string_lossy().to_string())
+    .collect::<Vec<_>>();
+  assert_eq!(entries, vec!["CHANGELOG.md"]);
+}
+
+#[test]
+fn write_atomic_creates_missing_files() {
+  let temp = TempDir::new().expect("tmp");
+  let file = temp.path().join("VERSION");
+
+  write_atomic(&file, "1.2.3\n").expect("atomic write");
+
+  assert_eq!(fs::read_to_string(&file).expect("read"), "1.2.3\n");
+}
+
+#[test]
+fn write_atomic_fails_without_leftovers_when_directory_is_missing() {
+  let temp = TempDir::new().expect("tmp");
+  let file = temp.path().join("missing/VERSION");
+
+  assert!(write_atomic(&file, "1.2.3\n").is_err());
+  assert_eq!(fs::read_dir(temp.path()).expect("read dir").count(), 0);
+}
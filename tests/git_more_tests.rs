@@ -15,7 +15,7 @@ fn annotated_tags_are_supported() {
 
   let old = std::env::current_dir().expect("cwd");
   std::env::set_current_dir(repo.path()).expect("set cwd");
-  let commits = read_commits(None, r"^v\d+\.\d+\.\d+$").expect("read commits");
+  let commits = read_commits(None, r"^v\d+\.\d+\.\d+$", None, &[]).expect("read commits");
   std::env::set_current_dir(old).expect("restore cwd");
 
   assert!(commits.is_empty());
@@ -39,7 +39,7 @@ fn empty_subject_commits_are_skipped() {
 
   let old = std::env::current_dir().expect("cwd");
   std::env::set_current_dir(repo.path()).expect("set cwd");
-  let commits = read_commits(None, r"^v\d+\.\d+\.\d+$").expect("read commits");
+  let commits = read_commits(None, r"^v\d+\.\d+\.\d+$", None, &[]).expect("read commits");
   std::env::set_current_dir(old).expect("restore cwd");
 
   assert!(commits.is_empty());
@@ -5,7 +5,7 @@ use std::fs;
 use cambi::git::{read_commits, read_commits_between_tags, read_tags};
 use serial_test::serial;
 
-use crate::common::{commit_with_date, create_repo, git};
+use crate::common::{commit_with_date, create_repo, git, init_repo};
 
 #[test]
 #[serial]
@@ -34,7 +34,7 @@ fn reads_commits_since_latest_tag() {
 
   let old = std::env::current_dir().expect("cwd");
   std::env::set_current_dir(repo.path()).expect("set cwd");
-  let commits = read_commits(None, r"^v\d+\.\d+\.\d+$").expect("read commits");
+  let commits = read_commits(None, r"^v\d+\.\d+\.\d+$", None, &[]).expect("read commits");
   std::env::set_current_dir(old).expect("restore cwd");
 
   assert_eq!(commits.len(), 1);
@@ -55,7 +55,7 @@ fn reads_commits_between_tags() {
 
   let old = std::env::current_dir().expect("cwd");
   std::env::set_current_dir(repo.path()).expect("set cwd");
-  let commits = read_commits_between_tags(Some("v0.2.0"), "v0.2.1").expect("between tags");
+  let commits = read_commits_between_tags(Some("v0.2.0"), "v0.2.1", None, &[]).expect("between tags");
   std::env::set_current_dir(old).expect("restore cwd");
 
   assert_eq!(commits.len(), 1);
@@ -80,7 +80,7 @@ fn invalid_tag_lookup_returns_error() {
   let repo = create_repo();
   let old = std::env::current_dir().expect("cwd");
   std::env::set_current_dir(repo.path()).expect("set cwd");
-  let result = read_commits(Some("v9.9.9"), r"^v\d+\.\d+\.\d+$");
+  let result = read_commits(Some("v9.9.9"), r"^v\d+\.\d+\.\d+$", None, &[]);
   std::env::set_current_dir(old).expect("restore cwd");
 
   assert!(result.is_err());
@@ -95,7 +95,7 @@ fn read_commits_from_specific_tag() {
 
   let old = std::env::current_dir().expect("cwd");
   std::env::set_current_dir(repo.path()).expect("set cwd");
-  let commits = read_commits(Some("v0.1.0"), r"^v\d+\.\d+\.\d+$").expect("read commits");
+  let commits = read_commits(Some("v0.1.0"), r"^v\d+\.\d+\.\d+$", None, &[]).expect("read commits");
   std::env::set_current_dir(old).expect("restore cwd");
 
   assert_eq!(commits.len(), 1);
@@ -108,8 +108,45 @@ fn invalid_end_tag_between_tags_returns_error() {
   let repo = create_repo();
   let old = std::env::current_dir().expect("cwd");
   std::env::set_current_dir(repo.path()).expect("set cwd");
-  let result = read_commits_between_tags(Some("v0.1.0"), "v9.9.9");
+  let result = read_commits_between_tags(Some("v0.1.0"), "v9.9.9", None, &[]);
   std::env::set_current_dir(old).expect("restore cwd");
 
   assert!(result.is_err());
 }
+
+#[test]
+#[serial]
+fn path_scoped_commits_include_root_and_merge_commits() {
+  let repo = init_repo();
+  fs::create_dir_all(repo.path().join("crates/core")).expect("mkdir");
+  fs::write(repo.path().join("crates/core/lib.rs"), "a").expect("write");
+  commit_with_date(repo.path(), "feat: core init", "2026-02-20T10:00:00Z");
+
+  git(repo.path(), &["checkout", "-q", "-b", "topic"]);
+  fs::write(repo.path().join("crates/core/lib.rs"), "b").expect("write");
+  commit_with_date(repo.path(), "fix: core topic", "2026-02-21T10:00:00Z");
+
+  git(repo.path(), &["checkout", "-q", "-"]);
+  fs::create_dir_all(repo.path().join("crates/cli")).expect("mkdir");
+  fs::write(repo.path().join("crates/cli/main.rs"), "a").expect("write");
+  commit_with_date(repo.path(), "feat: cli only", "2026-02-22T10:00:00Z");
+  git(
+    repo.path(),
+    &["merge", "-q", "--no-ff", "-m", "Merge branch 'topic'", "topic"],
+  );
+
+  let old = std::env::current_dir().expect("cwd");
+  std::env::set_current_dir(repo.path()).expect("set cwd");
+  let commits = read_commits(None, r"^core-v\d+\.\d+\.\d+$", None, &["crates/core".to_string()]);
+  std::env::set_current_dir(old).expect("restore cwd");
+
+  let subjects = commits
+    .expect("read commits")
+    .into_iter()
+    .map(|commit| commit.subject)
+    .collect::<Vec<_>>();
+  assert!(subjects.contains(&"feat: core init".to_string()));
+  assert!(subjects.contains(&"fix: core topic".to_string()));
+  assert!(subjects.contains(&"Merge branch 'topic'".to_string()));
+  assert!(!subjects.contains(&"feat: cli only".to_string()));
+}
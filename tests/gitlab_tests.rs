@@ -0,0 +1,212 @@
+mod common;
+
+use std::fs;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tiny_http::Method;
+
+use crate::common::{commit_with_date, create_repo, git, spawn_mock_server};
+
+#[test]
+fn changelog_remote_calls_gitlab_changelog_api() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+
+  let tag_sha = git(repo.path(), &["rev-parse", "v0.1.0^{commit}"]);
+  let head_sha = git(repo.path(), &["rev-parse", "HEAD"]);
+
+  let (base, seen) = spawn_mock_server(vec![(
+    Method::Post,
+    "/projects/o%2Fr/repository/changelog".to_string(),
+    200,
+    "{}".to_string(),
+  )]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .env("CAMBI_GITLAB_API_BASE", base)
+    .env("CAMBI_PROVIDER", "gitlab")
+    .env("CAMBI_OWNER", "o")
+    .env("CAMBI_REPO", "r")
+    .env("CAMBI_TOKEN", "t")
+    .args(["changelog", "--remote"]);
+  cmd.assert().success();
+
+  let seen = seen.lock().expect("lock");
+  assert_eq!(seen.len(), 1);
+  assert_eq!(seen[0].header("PRIVATE-TOKEN"), Some("t"));
+
+  let payload: serde_json::Value = serde_json::from_str(&seen[0].body).expect("json payload");
+  assert_eq!(payload["version"], "0.2.0");
+  assert_eq!(payload["from"], tag_sha.trim());
+  assert_eq!(payload["to"], head_sha.trim());
+  assert!(!repo.path().join("CHANGELOG.md").exists());
+}
+
+#[test]
+fn changelog_remote_requires_gitlab_provider() {
+  let repo = create_repo();
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["changelog", "--remote"]);
+  cmd
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("--remote requires the gitlab provider"));
+}
+
+#[test]
+fn release_gitlab_provider_upserts_releases() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+  git(repo.path(), &["tag", "v0.2.0"]);
+
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/projects/o%2Fr/releases?per_page=100".to_string(),
+      200,
+      r#"[{"tag_name":"v0.1.0","name":"0.1.0","description":"- old"}]"#.to_string(),
+    ),
+    (
+      Method::Get,
+      "/projects/o%2Fr/releases?per_page=100".to_string(),
+      200,
+      r#"[{"tag_name":"v0.1.0","name":"0.1.0","description":"- old"}]"#.to_string(),
+    ),
+    (
+      Method::Put,
+      "/projects/o%2Fr/releases/v0.1.0".to_string(),
+      200,
+      "{}".to_string(),
+    ),
+    (
+      Method::Post,
+      "/projects/o%2Fr/releases".to_string(),
+      201,
+      "{}".to_string(),
+    ),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).env("CAMBI_GITLAB_API_BASE", base).args([
+    "release",
+    "--rebuild",
+    "--provider",
+    "gitlab",
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+  ]);
+  cmd.assert().success();
+
+  let seen = seen.lock().expect("lock");
+  assert_eq!(seen.len(), 4);
+  assert!(seen.iter().all(|request| request.header("PRIVATE-TOKEN") == Some("t")));
+  assert!(seen[0].header("Authorization").is_none());
+
+  let payload: serde_json::Value = serde_json::from_str(&seen[3].body).expect("json payload");
+  assert_eq!(payload["tag_name"], "v0.2.0");
+  assert_eq!(payload["name"], "0.2.0");
+  assert_eq!(payload["description"], "- feat: add output");
+}
+
+#[test]
+fn release_delete_encodes_the_tag_in_gitlab_paths() {
+  let repo = create_repo();
+
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/projects/o%2Fr/releases?per_page=100".to_string(),
+      200,
+      r#"[{"tag_name":"v0.2.0+build.1","name":"0.2.0+build.1","description":"- old"}]"#.to_string(),
+    ),
+    (
+      Method::Delete,
+      "/projects/o%2Fr/releases/v0.2.0%2Bbuild.1".to_string(),
+      200,
+      "{}".to_string(),
+    ),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).env("CAMBI_GITLAB_API_BASE", base).args([
+    "release",
+    "--delete",
+    "v0.2.0+build.1",
+    "--yes",
+    "--provider",
+    "gitlab",
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+  ]);
+  cmd.assert().success();
+
+  let seen = seen.lock().expect("lock");
+  assert_eq!(seen.len(), 2);
+  assert_eq!(seen[1].url, "/projects/o%2Fr/releases/v0.2.0%2Bbuild.1");
+}
+
+#[test]
+fn release_rejects_assets_before_calling_gitlab() {
+  let repo = create_repo();
+  fs::write(repo.path().join("dist.tar.gz"), "archive").expect("write asset");
+
+  let (base, seen) = spawn_mock_server(vec![]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).env("CAMBI_GITLAB_API_BASE", base).args([
+    "release",
+    "--provider",
+    "gitlab",
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+    "--asset",
+    "dist.tar.gz",
+  ]);
+  cmd
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Release assets are not supported by GitLab"));
+
+  assert!(seen.lock().expect("lock").is_empty());
+}
+
+#[test]
+fn release_rejects_drafts_on_gitlab_even_in_dry_run() {
+  let repo = create_repo();
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args([
+    "release",
+    "--provider",
+    "gitlab",
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--draft",
+    "--dry-run",
+  ]);
+  cmd
+    .assert()
+    .failure()
+    .stdout("")
+    .stderr(predicate::str::contains("Draft releases are not supported by GitLab"));
+}
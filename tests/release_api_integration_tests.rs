@@ -199,3 +199,53 @@ fn release_prerelease_flag_is_sent_to_github_api() {
   // SAFETY: serialized test restores process env in same scope.
   unsafe { std::env::remove_var("CAMBI_GITHUB_API_BASE") };
 }
+
+#[test]
+#[serial]
+fn release_json_summary_reports_created_and_skipped_releases() {
+  let repo = create_repo();
+  fs::write(repo.path().join("src/lib.rs"), "pub fn a() { println!(\"x\"); }\n").expect("write feat file");
+  commit_with_date(repo.path(), "feat: add output", "2026-02-22T10:00:00Z");
+  git(repo.path(), &["tag", "v0.2.0"]);
+
+  let existing = r#"[{"id":1,"tag_name":"v0.2.0","name":"0.2.0","body":"- feat: add output"}]"#;
+  let (base, _) = spawn_mock_github(vec![
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      existing.to_string(),
+    ),
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      existing.to_string(),
+    ),
+    (Method::Post, "/repos/o/r/releases".to_string(), 201, "{}".to_string()),
+  ]);
+
+  // SAFETY: serialized test restores process env in same scope.
+  unsafe { std::env::set_var("CAMBI_GITHUB_API_BASE", base) };
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args([
+    "release",
+    "--rebuild",
+    "--json",
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+  ]);
+
+  cmd
+    .assert()
+    .success()
+    .stdout("{\"created\":[\"v0.1.0\"],\"updated\":[],\"skipped\":[\"v0.2.0\"],\"deleted\":[]}\n");
+
+  // SAFETY: serialized test restores process env in same scope.
+  unsafe { std::env::remove_var("CAMBI_GITHUB_API_BASE") };
+}
@@ -4,8 +4,11 @@ use std::fs;
 
 use assert_cmd::Command;
 use predicates::prelude::*;
+use tiny_http::Method;
 
-use crate::common::{commit_with_date, init_repo, seed_single_file_repo};
+use crate::common::{
+  commit_with_date, init_repo, seed_single_file_repo, spawn_mock_server, spawn_mock_server_with_headers,
+};
 
 #[test]
 fn release_dry_run_autodetects_repo_from_package_json_string() {
@@ -137,3 +140,803 @@ fn release_target_accepts_major_minor_patch_shorthands() {
     .success()
     .stdout(predicate::str::contains("tag=v0.3.0 title=0.3.0"));
 }
+
+#[test]
+fn release_bump_targets_start_from_anchor_tags() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "Cargo.toml",
+    "[package]\nname=\"x\"\nversion=\"0.1.0\"\nrepository=\"https://github.com/octo/r\"\n",
+  );
+  fs::write(repo.path().join("cambi.yml"), "anchor_tags:\n  - v0.2.1\n").expect("write config");
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v0.2.0"]);
+  fs::write(repo.path().join("a.txt"), "y").expect("write");
+  commit_with_date(repo.path(), "fix: retag", "2026-02-23T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v0.2.1"]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["release", "patch", "--dry-run"]);
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("tag=v0.2.2 title=0.2.2"));
+}
+
+#[test]
+fn release_rebuild_skips_orphaned_tags_when_reachable_only() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "Cargo.toml",
+    "[package]\nname=\"x\"\nversion=\"0.1.0\"\nrepository=\"https://github.com/octo/r\"\n",
+  );
+
+  crate::common::git(repo.path(), &["checkout", "-q", "-b", "orphan"]);
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: side work", "2026-02-22T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v0.3.0"]);
+  crate::common::git(repo.path(), &["checkout", "-q", "-"]);
+  crate::common::git(repo.path(), &["branch", "-q", "-D", "orphan"]);
+
+  fs::write(repo.path().join("b.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: mainline", "2026-02-23T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v0.2.0"]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["release", "--rebuild", "--dry-run"]);
+  cmd.assert().success().stdout(predicate::str::contains("tag=v0.3.0"));
+
+  fs::write(repo.path().join("cambi.yml"), "reachable_only: true\n").expect("write config");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["release", "--rebuild", "--dry-run"]);
+  cmd.assert().success().stdout(
+    predicate::str::contains("tag=v0.2.0")
+      .and(predicate::str::contains("tag=v0.1.0"))
+      .and(predicate::str::contains("tag=v0.3.0").not()),
+  );
+}
+
+#[test]
+fn release_notes_only_appends_footer_file() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "Cargo.toml",
+    "[package]\nname=\"x\"\nversion=\"0.1.0\"\nrepository=\"https://github.com/octo/r\"\n",
+  );
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v0.2.0"]);
+  fs::write(repo.path().join("footer.md"), "Sponsor us!\n").expect("write footer");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["release", "--notes-only", "--notes-footer-file", "footer.md"]);
+  cmd.assert().success().stdout("- feat: add\n\nSponsor us!\n");
+}
+
+#[test]
+fn release_marks_titles_matching_prerelease_pattern() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "cambi.yml",
+    "tag_pattern: '^v\\d+\\.\\d+\\.\\d+(\\.beta)?$'\nprerelease_title_pattern: '(?i)beta'\n",
+  );
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v1.2.3.beta"]);
+
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      "[]".to_string(),
+    ),
+    (Method::Post, "/repos/o/r/releases".to_string(), 201, "{}".to_string()),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .env("CAMBI_GITHUB_API_BASE", base)
+    .args(["release", "--owner", "o", "--repo", "r", "--token", "t"]);
+  cmd.assert().success();
+
+  let seen = seen.lock().expect("lock");
+  let payload: serde_json::Value = serde_json::from_str(&seen[1].body).expect("json payload");
+  assert_eq!(payload["name"], "1.2.3.beta");
+  assert_eq!(payload["prerelease"], true);
+}
+
+#[test]
+fn release_draft_posts_draft_payload_and_shows_in_dry_run() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+  fs::write(repo.path().join("a.txt"), "y").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v0.2.0"]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["release", "--draft", "--dry-run", "--owner", "o", "--repo", "r"]);
+  cmd.assert().success().stdout(predicate::str::contains(
+    "dry-run: would upsert release tag=v0.2.0 title=0.2.0 draft=true",
+  ));
+
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      "[]".to_string(),
+    ),
+    (Method::Post, "/repos/o/r/releases".to_string(), 201, "{}".to_string()),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .env("CAMBI_GITHUB_API_BASE", base)
+    .args(["release", "--draft", "--owner", "o", "--repo", "r", "--token", "t"]);
+  cmd.assert().success();
+
+  let seen = seen.lock().expect("lock");
+  let payload: serde_json::Value = serde_json::from_str(&seen[1].body).expect("json payload");
+  assert_eq!(payload["tag_name"], "v0.2.0");
+  assert_eq!(payload["draft"], true);
+}
+
+#[test]
+fn release_publishes_an_existing_draft_without_draft() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+  fs::write(repo.path().join("a.txt"), "y").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v0.2.0"]);
+
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      r#"[{"id":7,"tag_name":"v0.2.0","name":"0.2.0","body":"- feat: add","draft":true}]"#.to_string(),
+    ),
+    (
+      Method::Patch,
+      "/repos/o/r/releases/7".to_string(),
+      200,
+      "{}".to_string(),
+    ),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .env("CAMBI_GITHUB_API_BASE", base)
+    .args(["release", "--owner", "o", "--repo", "r", "--token", "t"]);
+  cmd.assert().success();
+
+  let seen = seen.lock().expect("lock");
+  assert_eq!(seen.len(), 2);
+  let payload: serde_json::Value = serde_json::from_str(&seen[1].body).expect("json payload");
+  assert_eq!(payload["draft"], false);
+}
+
+#[test]
+fn release_rejects_the_gitea_provider() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+
+  let (base, seen) = spawn_mock_server(vec![]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args([
+    "release",
+    "--provider",
+    "gitea",
+    "--api-base",
+    &base,
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+  ]);
+  cmd
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Releases are not supported for Gitea"));
+
+  assert!(seen.lock().expect("lock").is_empty());
+}
+
+#[test]
+fn release_rebuild_marks_existing_semver_prereleases() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+  fs::write(repo.path().join("a.txt"), "y").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v0.2.0-rc.1"]);
+
+  // Both releases predate prerelease detection and were published as full
+  // releases.
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      r#"[{"id":1,"tag_name":"v0.1.0","name":"0.1.0","body":"- No notable changes."},{"id":2,"tag_name":"v0.2.0-rc.1","name":"0.2.0-rc.1","body":"- feat: add"}]"#.to_string(),
+    ),
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      r#"[{"id":1,"tag_name":"v0.1.0","name":"0.1.0","body":"- No notable changes."},{"id":2,"tag_name":"v0.2.0-rc.1","name":"0.2.0-rc.1","body":"- feat: add"}]"#.to_string(),
+    ),
+    (
+      Method::Patch,
+      "/repos/o/r/releases/2".to_string(),
+      200,
+      "{}".to_string(),
+    ),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).env("CAMBI_GITHUB_API_BASE", base).args([
+    "--tag-pattern",
+    r"^v\d+\.\d+\.\d+(-rc\.\d+)?$",
+    "release",
+    "--rebuild",
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+  ]);
+  cmd.assert().success();
+
+  let seen = seen.lock().expect("lock");
+  assert_eq!(seen.len(), 3);
+  assert_eq!(seen[2].url, "/repos/o/r/releases/2");
+  let payload: serde_json::Value = serde_json::from_str(&seen[2].body).expect("json payload");
+  assert_eq!(payload["prerelease"], true);
+}
+
+#[test]
+fn release_rebuild_continue_on_error_processes_remaining_releases() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+  fs::write(repo.path().join("a.txt"), "y").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v0.2.0"]);
+
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      "[]".to_string(),
+    ),
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      "[]".to_string(),
+    ),
+    (Method::Post, "/repos/o/r/releases".to_string(), 500, "boom".to_string()),
+    (Method::Post, "/repos/o/r/releases".to_string(), 201, "{}".to_string()),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).env("CAMBI_GITHUB_API_BASE", base).args([
+    "--continue-on-error",
+    "release",
+    "--rebuild",
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+  ]);
+  cmd
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("1 operation(s) failed").and(predicate::str::contains("v0.1.0")));
+
+  let seen = seen.lock().expect("lock");
+  assert_eq!(seen.len(), 4);
+  let payload: serde_json::Value = serde_json::from_str(&seen[3].body).expect("json payload");
+  assert_eq!(payload["tag_name"], "v0.2.0");
+}
+
+#[test]
+fn release_delete_removes_matching_release_and_tag() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+  fs::write(repo.path().join("a.txt"), "y").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v0.2.0"]);
+
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      r#"[{"id":7,"tag_name":"v0.1.0"},{"id":9,"tag_name":"v0.2.0"}]"#.to_string(),
+    ),
+    (Method::Delete, "/repos/o/r/releases/9".to_string(), 204, String::new()),
+    (
+      Method::Delete,
+      "/repos/o/r/git/refs/tags/v0.2.0".to_string(),
+      204,
+      String::new(),
+    ),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).env("CAMBI_GITHUB_API_BASE", base).args([
+    "release",
+    "--delete",
+    "v0.2.0",
+    "--delete-tag",
+    "--yes",
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+  ]);
+  cmd.assert().success();
+
+  assert_eq!(seen.lock().expect("lock").len(), 3);
+  let tags = crate::common::git(repo.path(), &["tag", "--list"]);
+  assert_eq!(tags.trim(), "v0.1.0");
+}
+
+#[test]
+fn release_delete_prefixes_an_unprefixed_version() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+  fs::write(repo.path().join("a.txt"), "y").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v0.2.0"]);
+
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      r#"[{"id":9,"tag_name":"v0.2.0"}]"#.to_string(),
+    ),
+    (Method::Delete, "/repos/o/r/releases/9".to_string(), 204, String::new()),
+    (
+      Method::Delete,
+      "/repos/o/r/git/refs/tags/v0.2.0".to_string(),
+      204,
+      String::new(),
+    ),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).env("CAMBI_GITHUB_API_BASE", base).args([
+    "release",
+    "--delete",
+    "0.2.0",
+    "--delete-tag",
+    "--yes",
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+  ]);
+  cmd.assert().success();
+
+  let seen = seen.lock().expect("lock");
+  assert_eq!(seen.len(), 3);
+  assert_eq!(seen[2].url, "/repos/o/r/git/refs/tags/v0.2.0");
+  assert_eq!(crate::common::git(repo.path(), &["tag", "--list"]).trim(), "v0.1.0");
+}
+
+#[test]
+fn release_delete_requires_confirmation() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args([
+      "release", "--delete", "v0.1.0", "--owner", "o", "--repo", "r", "--token", "t",
+    ])
+    .write_stdin("n\n");
+  cmd.assert().failure().stderr(predicate::str::contains("not confirmed"));
+}
+
+#[test]
+fn release_rebuild_resume_only_touches_remaining_releases() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+  fs::write(repo.path().join("a.txt"), "y").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v0.2.0"]);
+
+  // The interrupted run already published v0.1.0 (GitHub normalized its line
+  // endings) and deleted the stale v9.9.9 release, which still shows up in a
+  // cached listing.
+  let existing = r#"[{"id":1,"tag_name":"v0.1.0","name":"0.1.0","body":"- No notable changes.\r\n"},{"id":2,"tag_name":"v9.9.9","name":"9.9.9","body":""}]"#;
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      existing.to_string(),
+    ),
+    (Method::Delete, "/repos/o/r/releases/2".to_string(), 404, String::new()),
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      existing.to_string(),
+    ),
+    (Method::Post, "/repos/o/r/releases".to_string(), 201, "{}".to_string()),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).env("CAMBI_GITHUB_API_BASE", base).args([
+    "release",
+    "--rebuild",
+    "--resume",
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+  ]);
+  cmd.assert().success().stderr(
+    predicate::str::contains("skipped v0.1.0 (up to date)")
+      .and(predicate::str::contains("deleted v9.9.9"))
+      .and(predicate::str::contains("created v0.2.0")),
+  );
+
+  let seen = seen.lock().expect("lock");
+  assert_eq!(seen.len(), 4);
+  let payload: serde_json::Value = serde_json::from_str(&seen[3].body).expect("json payload");
+  assert_eq!(payload["tag_name"], "v0.2.0");
+}
+
+#[test]
+fn release_offline_fails_before_any_api_call() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+
+  let (base, seen) = spawn_mock_server(vec![]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).env("CAMBI_GITHUB_API_BASE", base).args([
+    "release",
+    "--offline",
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+  ]);
+  cmd.assert().failure().stderr(predicate::str::contains("offline mode"));
+  assert!(seen.lock().expect("lock").is_empty());
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["release", "--offline", "--dry-run", "--owner", "o", "--repo", "r"]);
+  cmd.assert().success().stdout(predicate::str::contains("dry-run"));
+}
+
+#[test]
+fn release_uses_api_base_flag() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/api/v3/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      "[]".to_string(),
+    ),
+    (
+      Method::Post,
+      "/api/v3/repos/o/r/releases".to_string(),
+      201,
+      "{}".to_string(),
+    ),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).env_remove("CAMBI_GITHUB_API_BASE").args([
+    "release",
+    "--api-base",
+    &format!("{base}/api/v3"),
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+  ]);
+  cmd.assert().success();
+  assert_eq!(seen.lock().expect("lock").len(), 2);
+}
+
+#[test]
+fn release_notes_exclude_configured_authors() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+  fs::write(repo.path().join("cambi.yml"), "exclude_authors:\n  - dependabot[bot]\n").expect("write config");
+
+  fs::write(repo.path().join("a.txt"), "y").expect("write");
+  commit_with_date(repo.path(), "feat: add human change", "2026-02-22T00:00:00Z");
+
+  fs::write(repo.path().join("b.txt"), "z").expect("write");
+  crate::common::git(repo.path(), &["add", "."]);
+  let output = std::process::Command::new("git")
+    .current_dir(repo.path())
+    .env("GIT_AUTHOR_NAME", "dependabot[bot]")
+    .env("GIT_AUTHOR_EMAIL", "support@github.com")
+    .args(["commit", "-m", "fix: bump dependency"])
+    .output()
+    .expect("failed to run git commit");
+  assert!(output.status.success());
+  crate::common::git(repo.path(), &["tag", "v0.2.0"]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["release", "--notes-only"]);
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("feat: add human change").and(predicate::str::contains("bump dependency").not()));
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args([
+    "release",
+    "--notes-only",
+    "--author",
+    "dependabot[bot]",
+    "--exclude-author",
+    "nobody",
+  ]);
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("fix: bump dependency").and(predicate::str::contains("human change").not()));
+}
+
+#[test]
+fn release_uploads_assets_replacing_existing_ones() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+  fs::write(repo.path().join("app.zip"), "zip-bytes").expect("write asset");
+
+  let (uploads, uploaded) = spawn_mock_server(vec![(
+    Method::Post,
+    "/repos/o/r/releases/5/assets?name=app.zip".to_string(),
+    201,
+    "{}".to_string(),
+  )]);
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      r#"[{"id":5,"tag_name":"v0.1.0","name":"old","body":"old"}]"#.to_string(),
+    ),
+    (
+      Method::Patch,
+      "/repos/o/r/releases/5".to_string(),
+      200,
+      format!(r#"{{"id":5,"upload_url":"{uploads}/repos/o/r/releases/5/assets{{?name,label}}"}}"#),
+    ),
+    (
+      Method::Get,
+      "/repos/o/r/releases/5/assets?per_page=100".to_string(),
+      200,
+      r#"[{"id":11,"name":"app.zip"}]"#.to_string(),
+    ),
+    (
+      Method::Delete,
+      "/repos/o/r/releases/assets/11".to_string(),
+      204,
+      String::new(),
+    ),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).env("CAMBI_GITHUB_API_BASE", base).args([
+    "release",
+    "--asset",
+    "missing.tar.gz",
+    "--asset",
+    "app.zip",
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+  ]);
+  cmd
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("1 operation(s) failed").and(predicate::str::contains("missing.tar.gz")));
+
+  assert_eq!(seen.lock().expect("lock").len(), 4);
+  let uploaded = uploaded.lock().expect("lock");
+  assert_eq!(uploaded.len(), 1);
+  assert_eq!(uploaded[0].header("Content-Type"), Some("application/zip"));
+  assert_eq!(uploaded[0].body, "zip-bytes");
+}
+
+#[test]
+fn release_retries_idempotent_calls_but_not_create_on_server_errors() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      503,
+      "unavailable".to_string(),
+    ),
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      "[]".to_string(),
+    ),
+    (
+      Method::Post,
+      "/repos/o/r/releases".to_string(),
+      502,
+      "bad gateway".to_string(),
+    ),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).env("CAMBI_GITHUB_API_BASE", base).args([
+    "release",
+    "--max-retries",
+    "1",
+    "--owner",
+    "o",
+    "--repo",
+    "r",
+    "--token",
+    "t",
+  ]);
+  cmd
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("creating release 'v0.1.0'").and(predicate::str::contains("502")));
+
+  assert_eq!(seen.lock().expect("lock").len(), 3);
+}
+
+#[test]
+fn release_reports_rate_limit_with_reset_time() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+
+  let (base, _) = spawn_mock_server_with_headers(vec![(
+    Method::Get,
+    "/repos/o/r/releases?per_page=100".to_string(),
+    403,
+    r#"{"message":"API rate limit exceeded"}"#.to_string(),
+    vec![
+      ("X-RateLimit-Remaining", "0".to_string()),
+      ("X-RateLimit-Reset", "1767225600".to_string()),
+    ],
+  )]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .env("CAMBI_GITHUB_API_BASE", base)
+    .args(["release", "--owner", "o", "--repo", "r", "--token", "t"]);
+  cmd.assert().failure().stderr(predicate::str::contains(
+    "GitHub rate limit exceeded, resets at 2026-01-01 00:00:00 UTC",
+  ));
+}
+
+#[test]
+fn release_groups_notes_by_type_when_configured() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.txt", "x");
+  fs::write(
+    repo.path().join("cambi.yml"),
+    "release_grouped: true\nrelease_group_labels:\n  docs: Documentation\n",
+  )
+  .expect("write config");
+
+  fs::write(repo.path().join("a.txt"), "y").expect("write");
+  commit_with_date(repo.path(), "fix: handle empty input", "2026-02-20T00:00:00Z");
+  fs::write(repo.path().join("a.txt"), "z").expect("write");
+  commit_with_date(repo.path(), "feat(cli): add flag", "2026-02-21T00:00:00Z");
+  fs::write(repo.path().join("a.txt"), "w").expect("write");
+  commit_with_date(repo.path(), "docs: explain flag", "2026-02-22T00:00:00Z");
+  fs::write(repo.path().join("a.txt"), "v").expect("write");
+  commit_with_date(repo.path(), "test: cover flag", "2026-02-23T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "v0.2.0"]);
+
+  let (base, seen) = spawn_mock_server(vec![
+    (
+      Method::Get,
+      "/repos/o/r/releases?per_page=100".to_string(),
+      200,
+      "[]".to_string(),
+    ),
+    (Method::Post, "/repos/o/r/releases".to_string(), 201, "{}".to_string()),
+  ]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .env("CAMBI_GITHUB_API_BASE", base)
+    .args(["release", "--owner", "o", "--repo", "r", "--token", "t"]);
+  cmd.assert().success();
+
+  let seen = seen.lock().expect("lock");
+  let payload: serde_json::Value = serde_json::from_str(&seen[1].body).expect("json payload");
+  assert_eq!(
+    payload["body"],
+    "### Features\n\n- feat(cli): add flag\n\n### Bug Fixes\n\n- fix: handle empty input\n\n### Documentation\n\n- \
+     docs: explain flag\n\n### Other Changes\n\n- test: cover flag"
+  );
+}
+
+#[test]
+fn release_notes_with_path_only_list_the_package_commits() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "packages/foo/package.json",
+    "{\"name\":\"foo\",\"version\":\"1.2.2\"}\n",
+  );
+  crate::common::git(repo.path(), &["tag", "foo-v1.2.2"]);
+
+  fs::create_dir_all(repo.path().join("packages/bar")).expect("mkdir");
+  fs::write(repo.path().join("packages/bar/index.js"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: bar feature", "2026-02-22T00:00:00Z");
+  fs::write(repo.path().join("packages/foo/index.js"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: foo fix", "2026-02-23T00:00:00Z");
+  crate::common::git(repo.path(), &["tag", "foo-v1.2.3"]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args([
+    "--tag-pattern",
+    r"^foo-v\d+\.\d+\.\d+$",
+    "--path",
+    "packages/foo",
+    "release",
+    "--notes-only",
+  ]);
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("fix: foo fix").and(predicate::str::contains("bar feature").not()));
+}
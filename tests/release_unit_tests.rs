@@ -1,33 +1,110 @@
 use cambi::{
   cli::ReleaseArgs,
-  config::EffectiveConfig,
+  config::{EffectiveConfig, Provider},
   release::{
-    execute_release_command, normalize_release_version, parse_github_repo_from_url, release_tag, release_title,
-    render_release_body,
+    execute_release_command, fit_release_body, is_prerelease_title, normalize_release_version, parse_repo_from_url,
+    release_tag, release_title, render_release_body, render_release_notes, resolve_owner_repo,
   },
 };
+use regex::Regex;
 
 #[test]
-fn parse_github_repo_from_url_handles_formats() {
+fn parse_repo_from_url_handles_github_formats() {
   assert_eq!(
-    parse_github_repo_from_url("git@github.com:org/repo.git"),
+    parse_repo_from_url("git@github.com:org/repo.git", "github.com"),
     Some(("org".to_string(), "repo".to_string()))
   );
   assert_eq!(
-    parse_github_repo_from_url("https://github.com/org/repo"),
+    parse_repo_from_url("https://github.com/org/repo", "github.com"),
     Some(("org".to_string(), "repo".to_string()))
   );
-  assert_eq!(parse_github_repo_from_url("https://example.com/org/repo"), None);
+  assert_eq!(parse_repo_from_url("https://example.com/org/repo", "github.com"), None);
+  assert_eq!(
+    parse_repo_from_url("https://notgithub.com/org/repo", "github.com"),
+    None
+  );
+}
+
+#[test]
+fn parse_repo_from_url_handles_gitlab_formats() {
+  assert_eq!(
+    parse_repo_from_url("git@gitlab.com:org/repo.git", "gitlab.com"),
+    Some(("org".to_string(), "repo".to_string()))
+  );
+  assert_eq!(
+    parse_repo_from_url("https://gitlab.com/org/sub/repo", "gitlab.com"),
+    Some(("org/sub".to_string(), "repo".to_string()))
+  );
+  assert_eq!(parse_repo_from_url("https://github.com/org/repo", "gitlab.com"), None);
+}
+
+#[test]
+fn parse_repo_from_url_handles_self_hosted_gitea_formats() {
+  assert_eq!(
+    parse_repo_from_url("ssh://git@git.example.com/org/repo.git", "git.example.com"),
+    Some(("org".to_string(), "repo".to_string()))
+  );
+  assert_eq!(
+    parse_repo_from_url("https://git.example.com/org/repo/", "git.example.com"),
+    Some(("org".to_string(), "repo".to_string()))
+  );
+  assert_eq!(
+    parse_repo_from_url("https://gitea.com/org/repo", "git.example.com"),
+    None
+  );
+}
+
+#[test]
+fn provider_links_follow_the_web_path_conventions() {
+  assert_eq!(
+    Provider::Github.compare_url("github.com", "o", "r", "v1.0.0", "v1.1.0"),
+    "https://github.com/o/r/compare/v1.0.0...v1.1.0"
+  );
+  assert_eq!(
+    Provider::Gitlab.compare_url("gitlab.example.com", "o/sub", "r", "v1.0.0", "v1.1.0"),
+    "https://gitlab.example.com/o/sub/r/-/compare/v1.0.0...v1.1.0"
+  );
+  assert_eq!(
+    Provider::Gitlab.commit_url("gitlab.com", "o", "r", "abc1234"),
+    "https://gitlab.com/o/r/-/commit/abc1234"
+  );
+  assert_eq!(
+    Provider::Gitlab.tag_url("gitlab.com", "o", "r", "v1.0.0"),
+    "https://gitlab.com/o/r/-/tags/v1.0.0"
+  );
+  assert_eq!(
+    Provider::Gitea.compare_url("git.example.com", "o", "r", "v1.0.0", "v1.1.0"),
+    "https://git.example.com/o/r/compare/v1.0.0...v1.1.0"
+  );
+  assert_eq!(
+    Provider::Gitea.commit_url("git.example.com", "o", "r", "abc1234"),
+    "https://git.example.com/o/r/commit/abc1234"
+  );
+  assert_eq!(
+    Provider::Gitea.issues_url("git.example.com", "o", "r"),
+    "https://git.example.com/o/r/issues"
+  );
+  assert_eq!(
+    Provider::Gitea.file_url("git.example.com", "o", "r", "v1.0.0", "CHANGELOG.md"),
+    "https://git.example.com/o/r/src/v1.0.0/CHANGELOG.md"
+  );
 }
 
 #[test]
 fn release_helpers_cover_edge_cases() {
   assert_eq!(normalize_release_version("v1.2.3"), "1.2.3");
   assert_eq!(release_tag("v1.2.3"), "v1.2.3");
-  assert_eq!(release_title("v1.2.3"), "1.2.3");
+  assert_eq!(release_title("v1.2.3", false), "1.2.3");
   assert_eq!(render_release_body(&[]), "- No notable changes.");
 }
 
+#[test]
+fn release_title_keeps_prefix_when_configured() {
+  assert_eq!(release_title("v1.2.3", true), "v1.2.3");
+  assert_eq!(release_title("v1.2.3", false), "1.2.3");
+  assert!(is_prerelease_title("v1.2.3-rc.1", None));
+}
+
 #[test]
 fn release_rejects_target_with_rebuild_at_runtime() {
   let args = ReleaseArgs {
@@ -36,13 +113,8 @@ fn release_rejects_target_with_rebuild_at_runtime() {
     ..ReleaseArgs::default()
   };
   let config = EffectiveConfig {
-    token: None,
-    owner: None,
-    repo: None,
     tag_pattern: r"^v\d+\.\d+\.\d+$".to_string(),
-    changelog_template: None,
-    ignore_patterns: vec![],
-    verbose: false,
+    ..EffectiveConfig::default()
   };
 
   let error = execute_release_command(&args, &config).expect_err("must fail");
@@ -60,13 +132,8 @@ fn release_rejects_prerelease_without_target_at_runtime() {
     ..ReleaseArgs::default()
   };
   let config = EffectiveConfig {
-    token: None,
-    owner: None,
-    repo: None,
     tag_pattern: r"^v\d+\.\d+\.\d+$".to_string(),
-    changelog_template: None,
-    ignore_patterns: vec![],
-    verbose: false,
+    ..EffectiveConfig::default()
   };
 
   let error = execute_release_command(&args, &config).expect_err("must fail");
@@ -76,3 +143,88 @@ fn release_rejects_prerelease_without_target_at_runtime() {
       .contains("--prerelease requires an explicit positional release target")
   );
 }
+
+#[test]
+fn prerelease_title_detection_uses_semver_and_pattern() {
+  let pattern = Regex::new(r"(?i)\b(alpha|beta|rc)\b").expect("regex");
+
+  assert!(is_prerelease_title("1.2.3-rc.1", None));
+  assert!(!is_prerelease_title("1.2.3", None));
+  assert!(!is_prerelease_title("1.2.3 Beta", None));
+  assert!(is_prerelease_title("1.2.3 Beta", Some(&pattern)));
+  assert!(!is_prerelease_title("1.2.3", Some(&pattern)));
+}
+
+#[test]
+fn resolve_owner_repo_falls_back_to_repository_url() {
+  let config = EffectiveConfig {
+    repository_url: Some("https://github.com/octo/widgets.git".to_string()),
+    ..EffectiveConfig::default()
+  };
+  assert_eq!(
+    resolve_owner_repo(&config).expect("resolve"),
+    ("octo".to_string(), "widgets".to_string())
+  );
+
+  let explicit = EffectiveConfig {
+    owner: Some("o".to_string()),
+    repo: Some("r".to_string()),
+    ..config.clone()
+  };
+  assert_eq!(
+    resolve_owner_repo(&explicit).expect("resolve"),
+    ("o".to_string(), "r".to_string())
+  );
+
+  let invalid = EffectiveConfig {
+    repository_url: Some("https://example.com/octo/widgets".to_string()),
+    ..EffectiveConfig::default()
+  };
+  assert!(resolve_owner_repo(&invalid).is_err());
+
+  let self_hosted = EffectiveConfig {
+    provider: Provider::Gitea,
+    web_host: Some("example.com".to_string()),
+    ..invalid
+  };
+  assert_eq!(
+    resolve_owner_repo(&self_hosted).expect("resolve"),
+    ("octo".to_string(), "widgets".to_string())
+  );
+}
+
+#[test]
+fn fit_release_body_links_the_full_changelog_when_too_long() {
+  let compare = || Some("https://github.com/o/r/compare/v1.0.0...v1.1.0".to_string());
+
+  assert_eq!(fit_release_body("- feat: a".to_string(), 9, compare), "- feat: a");
+  assert_eq!(
+    fit_release_body("- feat: a\n- fix: b".to_string(), 9, compare),
+    "See the full changelog: https://github.com/o/r/compare/v1.0.0...v1.1.0"
+  );
+  assert_eq!(
+    fit_release_body("- feat: a\n- fix: b".to_string(), 9, || None),
+    "See the full changelog in CHANGELOG.md."
+  );
+}
+
+#[test]
+fn render_release_notes_orders_groups_by_configured_type_order() {
+  let commits = [
+    "feat: add".to_string(),
+    "docs: explain".to_string(),
+    "fix: repair".to_string(),
+    "perf: speed up".to_string(),
+  ];
+  let config = EffectiveConfig {
+    release_grouped: true,
+    changelog_type_order: vec!["fix".to_string(), "other".to_string(), "feat".to_string()],
+    ..EffectiveConfig::default()
+  };
+
+  assert_eq!(
+    render_release_notes(&commits, &config),
+    "### Bug Fixes\n\n- fix: repair\n\n### Other Changes\n\n- docs: explain\n- perf: speed up\n\n### Features\n\n- \
+     feat: add"
+  );
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use std::fs;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tiny_http::Method;
+
+use crate::common::{commit_with_date, init_repo, seed_single_file_repo, spawn_mock_server};
+
+#[test]
+fn semver_bump_from_labels_overrides_inferred_bump() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "VERSION", "1.2.3\n");
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: squashed change (#12)", "2026-02-22T00:00:00Z");
+  fs::write(repo.path().join("b.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: direct push", "2026-02-23T00:00:00Z");
+
+  let (base, seen) = spawn_mock_server(vec![(
+    Method::Get,
+    "/repos/o/r/pulls/12".to_string(),
+    200,
+    r#"{"number":12,"labels":[{"name":"bug"},{"name":"semver:minor"}]}"#.to_string(),
+  )]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .env("CAMBI_GITHUB_API_BASE", base)
+    .env("CAMBI_OWNER", "o")
+    .env("CAMBI_REPO", "r")
+    .env("CAMBI_TOKEN", "t")
+    .args(["semver", "--bump-from-labels"]);
+  cmd.assert().success().stdout("minor\n");
+
+  assert_eq!(seen.lock().expect("lock").len(), 1);
+}
+
+#[test]
+fn semver_without_bump_from_labels_ignores_pull_requests() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "VERSION", "1.2.3\n");
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: squashed change (#12)", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["semver"]);
+  cmd.assert().success().stdout("patch\n");
+}
+
+#[test]
+fn semver_labels_never_lower_a_breaking_change() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "VERSION", "1.2.3\n");
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat!: drop the old api (#12)", "2026-02-22T00:00:00Z");
+
+  let (base, _) = spawn_mock_server(vec![(
+    Method::Get,
+    "/repos/o/r/pulls/12".to_string(),
+    200,
+    r#"{"number":12,"labels":[{"name":"semver:patch"}]}"#.to_string(),
+  )]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .env("CAMBI_GITHUB_API_BASE", base)
+    .env("CAMBI_OWNER", "o")
+    .env("CAMBI_REPO", "r")
+    .env("CAMBI_TOKEN", "t")
+    .args(["semver", "--bump-from-labels"]);
+  cmd
+    .assert()
+    .success()
+    .stdout("major\n")
+    .stderr(predicate::str::contains(
+      "Ignoring label bump patch from pull request #12: the commit is a breaking change.",
+    ));
+}
+
+#[test]
+fn semver_stats_count_the_label_bump() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "VERSION", "1.2.3\n");
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: rework the api (#12)", "2026-02-22T00:00:00Z");
+
+  let (base, _) = spawn_mock_server(vec![(
+    Method::Get,
+    "/repos/o/r/pulls/12".to_string(),
+    200,
+    r#"{"number":12,"labels":[{"name":"semver:major"}]}"#.to_string(),
+  )]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .env("CAMBI_GITHUB_API_BASE", base)
+    .env("CAMBI_OWNER", "o")
+    .env("CAMBI_REPO", "r")
+    .env("CAMBI_TOKEN", "t")
+    .args(["semver", "--bump-from-labels", "--stats"]);
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("features: 0, fixes: 0, breaking: 1, other: 0"));
+}
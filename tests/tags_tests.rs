@@ -0,0 +1,45 @@
+mod common;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+use crate::common::{create_repo, git};
+
+#[test]
+fn tags_prune_lists_then_deletes_only_unmatched_tags() {
+  let repo = create_repo();
+  git(repo.path(), &["tag", "v0.2.0"]);
+  git(repo.path(), &["tag", "oops-0.2.0"]);
+  git(repo.path(), &["tag", "v0.3"]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["tags"]);
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("v0.2.0\n").and(predicate::str::contains("oops").not()));
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["tags", "--prune"]);
+  cmd
+    .assert()
+    .success()
+    .stdout("oops-0.2.0\nv0.3\n")
+    .stderr("Pass --yes to delete these tags.\n");
+  assert_eq!(git(repo.path(), &["tag", "--list"]).lines().count(), 4);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["tags", "--prune", "--yes"]);
+  cmd
+    .assert()
+    .success()
+    .stdout("Deleted tag oops-0.2.0.\nDeleted tag v0.3.\n");
+
+  assert_eq!(git(repo.path(), &["tag", "--list"]), "v0.1.0\nv0.2.0\n");
+}
+
+#[test]
+fn tags_yes_requires_prune() {
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.args(["tags", "--yes"]).assert().failure();
+}
@@ -3,6 +3,7 @@ mod common;
 use std::fs;
 
 use assert_cmd::Command;
+use predicates::prelude::*;
 
 use crate::common::{commit_with_date, init_repo, seed_single_file_repo};
 
@@ -76,3 +77,462 @@ fn cargo_toml_update_preserves_condensed_dependency_tables() {
   assert!(!cargo.contains("[dependencies.anyhow]"));
   assert!(!cargo.contains("[dev-dependencies.assert_cmd]"));
 }
+
+#[test]
+fn update_write_env_outputs_version_tag_and_bump() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "VERSION", "1.2.3\n");
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "--write-env", "out.env"]);
+  cmd.assert().success();
+
+  let env = fs::read_to_string(repo.path().join("out.env")).expect("read env");
+  assert_eq!(
+    env,
+    "CAMBI_VERSION=1.3.0\nCAMBI_TAG=v1.3.0\nCAMBI_BUMP=minor\nCAMBI_PREVIOUS_VERSION=1.2.3\n"
+  );
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "--dry-run", "--write-env", "dry.env"]);
+  cmd.assert().success().stdout(predicate::str::contains(
+    "dry-run: would write dry.env with CAMBI_VERSION=1.4.0 and CAMBI_PREVIOUS_VERSION=1.3.0",
+  ));
+  assert!(!repo.path().join("dry.env").exists());
+}
+
+#[test]
+fn update_file_bumps_nested_json_version_path() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "config/app.json",
+    "{\n  \"app\": {\n    \"name\": \"x\",\n    \"meta\": {\n      \"version\": \"1.2.3\"\n    }\n  }\n}\n",
+  );
+  fs::write(repo.path().join("cambi.yml"), "version_json_path: app.meta.version\n").expect("write config");
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "--file", "config/app.json"]);
+  cmd.assert().success().stdout("Updated version to 1.3.0.\n");
+
+  let json = fs::read_to_string(repo.path().join("config/app.json")).expect("read");
+  assert_eq!(
+    json,
+    "{\n  \"app\": {\n    \"name\": \"x\",\n    \"meta\": {\n      \"version\": \"1.3.0\"\n    }\n  }\n}\n"
+  );
+}
+
+#[test]
+fn update_file_bumps_nested_yaml_version_path_keeping_comments() {
+  let repo = init_repo();
+  let content = "# App settings\napp:\n  other:\n    version: 9.9.9\n  meta:\n    # Bumped on release\n    version: \
+                 \"1.2.3\" # keep me\n  name: x\n";
+  seed_single_file_repo(&repo, "config/app.yaml", content);
+  fs::write(repo.path().join("cambi.yml"), "version_json_path: app.meta.version\n").expect("write config");
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "--file", "config/app.yaml"]);
+  cmd.assert().success().stdout("Updated version to 1.3.0.\n");
+
+  let yaml = fs::read_to_string(repo.path().join("config/app.yaml")).expect("read");
+  assert_eq!(yaml, content.replace("\"1.2.3\"", "\"1.3.0\""));
+}
+
+#[test]
+fn update_bumps_pubspec_version_keeping_comments() {
+  let repo = init_repo();
+  let content =
+    "name: x\n# The version of the app\nversion: 1.2.3+4 # build\n\ndependencies:\n  flutter:\n    sdk: flutter\n";
+  seed_single_file_repo(&repo, "pubspec.yaml", content);
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: patch", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update"]);
+  cmd.assert().success().stdout("Updated version to 1.2.4+5.\n");
+
+  let yaml = fs::read_to_string(repo.path().join("pubspec.yaml")).expect("read");
+  assert_eq!(yaml, content.replace("1.2.3+4", "1.2.4+5"));
+}
+
+#[test]
+fn update_file_bumps_toml_version_path_keeping_comments() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "config/app.toml",
+    "[tool.myapp]\n# Bumped on release\nversion = \"1.2.3\" # keep me\nname = \"x\"\n",
+  );
+  fs::write(repo.path().join("cambi.yml"), "toml_version_path: tool.myapp.version\n").expect("write config");
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "--file", "config/app.toml"]);
+  cmd.assert().success().stdout("Updated version to 1.3.0.\n");
+
+  let toml = fs::read_to_string(repo.path().join("config/app.toml")).expect("read");
+  assert_eq!(
+    toml,
+    "[tool.myapp]\n# Bumped on release\nversion = \"1.3.0\" # keep me\nname = \"x\"\n"
+  );
+}
+
+#[test]
+fn update_file_sniffs_json_content_without_extension() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "meta", "{\n  \"name\": \"x\",\n  \"version\": \"1.2.3\"\n}\n");
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "--file", "meta"]);
+  cmd.assert().success().stdout("Updated version to 1.3.0.\n");
+
+  let json = fs::read_to_string(repo.path().join("meta")).expect("read");
+  assert_eq!(json, "{\n  \"name\": \"x\",\n  \"version\": \"1.3.0\"\n}\n");
+}
+
+#[test]
+fn update_file_sniffs_toml_content_without_extension() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "conf", "# release settings\nname = \"x\"\nversion = \"1.2.3\"\n");
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "--file", "conf"]);
+  cmd.assert().success().stdout("Updated version to 1.3.0.\n");
+
+  let toml = fs::read_to_string(repo.path().join("conf")).expect("read");
+  assert_eq!(toml, "# release settings\nname = \"x\"\nversion = \"1.3.0\"\n");
+}
+
+#[test]
+fn explicit_lower_target_is_rejected_without_allow_downgrade() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "Cargo.toml", "[package]\nname=\"x\"\nversion=\"2.0.0\"\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "1.0.0"]);
+  let output = cmd.assert().failure().get_output().stderr.clone();
+  let stderr = String::from_utf8(output).expect("utf8");
+  assert!(stderr.contains("Refusing to set version 1.0.0, which is not greater than current 2.0.0"));
+
+  let cargo = fs::read_to_string(repo.path().join("Cargo.toml")).expect("read");
+  assert!(cargo.contains("version=\"2.0.0\""));
+}
+
+#[test]
+fn explicit_lower_target_is_applied_with_allow_downgrade() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "Cargo.toml", "[package]\nname=\"x\"\nversion=\"2.0.0\"\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "1.0.0", "--allow-downgrade"]);
+  cmd.assert().success().stdout("Updated version to 1.0.0.\n");
+
+  let mut same = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  same.current_dir(repo.path()).args(["update", "1.0.0"]);
+  same.assert().failure();
+
+  let mut same = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  same
+    .current_dir(repo.path())
+    .args(["update", "1.0.0", "--allow-downgrade"]);
+  same.assert().success().stdout("Updated version to 1.0.0.\n");
+}
+
+#[test]
+fn debian_changelog_bumps_upstream_and_keeps_epoch_and_revision() {
+  let repo = init_repo();
+  fs::write(repo.path().join("cambi.yml"), "debian_changelog: true\n").expect("write config");
+  seed_single_file_repo(
+    &repo,
+    "debian/changelog",
+    "mytool (1:1.2.3-2) unstable; urgency=medium\n\n  * Initial release.\n\n -- Jane Doe <jane@example.com>  Mon, 02 \
+     Feb 2026 10:00:00 +0000\n",
+  );
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add feature", "2026-02-22T00:00:00Z");
+
+  let output = run_update(repo.path());
+  assert_eq!(output, "Updated version to 1.3.0.\n");
+
+  let changelog = fs::read_to_string(repo.path().join("debian/changelog")).expect("read");
+  let mut lines = changelog.lines();
+  assert_eq!(lines.next(), Some("mytool (1:1.3.0-2) unstable; urgency=medium"));
+  assert!(changelog.contains("  * New upstream release 1.3.0."));
+  assert!(changelog.contains(" -- Jane Doe <jane@example.com>  "));
+  assert!(changelog.contains("mytool (1:1.2.3-2) unstable; urgency=medium\n\n  * Initial release."));
+}
+
+#[test]
+fn sync_workspace_deps_updates_member_pins_and_commits_them() {
+  let repo = init_repo();
+  fs::write(repo.path().join("cambi.yml"), "sync_workspace_deps: true\n").expect("write config");
+  fs::create_dir_all(repo.path().join("crates/b")).expect("create member dir");
+  fs::create_dir_all(repo.path().join("crates/c")).expect("create member dir");
+  fs::write(
+    repo.path().join("crates/b/Cargo.toml"),
+    "[package]\nname = \"b\"\nversion = \"0.3.0\"\n\n[dependencies]\na = { path = \"../..\", version = \"=1.2.3\" } # \
+     pinned\n\n[dev-dependencies.a]\npath = \"../..\"\nversion = \"1.2.3\"\n",
+  )
+  .expect("write member manifest");
+  fs::write(
+    repo.path().join("crates/c/Cargo.toml"),
+    "[package]\nname = \"c\"\nversion = \"0.1.0\"\n\n[dependencies]\na = \"~1.2.3\" # plain\nb = \"0.3.0\"\n",
+  )
+  .expect("write member manifest");
+  seed_single_file_repo(
+    &repo,
+    "Cargo.toml",
+    "[package]\nname = \"a\"\nversion = \"1.2.3\"\n\n[workspace]\nmembers = \
+     [\"crates/*\"]\n\n[workspace.dependencies]\na = { path = \".\", version = \"1.2.3\" }\nb = \"0.3.0\"\n",
+  );
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add feature", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "--commit"]);
+  cmd.assert().success().stdout("Updated version to 1.3.0.\n");
+
+  assert_eq!(
+    fs::read_to_string(repo.path().join("Cargo.toml")).expect("read root"),
+    "[package]\nname = \"a\"\nversion = \"1.3.0\"\n\n[workspace]\nmembers = \
+     [\"crates/*\"]\n\n[workspace.dependencies]\na = { path = \".\", version = \"1.3.0\" }\nb = \"0.3.0\"\n"
+  );
+  assert_eq!(
+    fs::read_to_string(repo.path().join("crates/b/Cargo.toml")).expect("read member"),
+    "[package]\nname = \"b\"\nversion = \"0.3.0\"\n\n[dependencies]\na = { path = \"../..\", version = \"=1.3.0\" } # \
+     pinned\n\n[dev-dependencies.a]\npath = \"../..\"\nversion = \"1.3.0\"\n"
+  );
+  assert_eq!(
+    fs::read_to_string(repo.path().join("crates/c/Cargo.toml")).expect("read member"),
+    "[package]\nname = \"c\"\nversion = \"0.1.0\"\n\n[dependencies]\na = \"~1.3.0\" # plain\nb = \"0.3.0\"\n"
+  );
+
+  let status = crate::common::git(repo.path(), &["status", "--short"]);
+  assert_eq!(status.trim(), "");
+}
+
+#[test]
+fn current_version_env_is_the_base_without_files_or_tags() {
+  let repo = init_repo();
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add feature", "2026-02-22T00:00:00Z");
+
+  let mut version = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  version
+    .current_dir(repo.path())
+    .env("CAMBI_CURRENT_VERSION", "v1.2.3")
+    .args(["version"]);
+  version.assert().success().stdout("1.2.3\n");
+
+  let mut update = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  update
+    .current_dir(repo.path())
+    .env("CAMBI_CURRENT_VERSION", "1.2.3")
+    .args(["update", "--write-env", "release.env"]);
+  update.assert().success().stdout("Updated version to 1.3.0.\n");
+
+  let env = fs::read_to_string(repo.path().join("release.env")).expect("read env file");
+  assert!(env.contains("CAMBI_VERSION=1.3.0"));
+}
+
+#[test]
+fn update_bumps_setup_py_version_preserving_quotes() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "setup.py",
+    "from setuptools import setup\n\nsetup(\n    name='x',\n    version='1.2.3',\n)\n",
+  );
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  assert_eq!(run_update(repo.path()), "Updated version to 1.3.0.\n");
+
+  let setup = fs::read_to_string(repo.path().join("setup.py")).expect("read");
+  assert_eq!(
+    setup,
+    "from setuptools import setup\n\nsetup(\n    name='x',\n    version='1.3.0',\n)\n"
+  );
+}
+
+#[test]
+fn update_version_file_bumps_dunder_version() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "pkg/__init__.py",
+    "\"\"\"Package.\"\"\"\n\n__version__ = \"1.2.3\"  # managed by cambi\n",
+  );
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: patch", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "--version-file", "pkg/__init__.py"]);
+  cmd.assert().success().stdout("Updated version to 1.2.4.\n");
+
+  let init = fs::read_to_string(repo.path().join("pkg/__init__.py")).expect("read");
+  assert_eq!(
+    init,
+    "\"\"\"Package.\"\"\"\n\n__version__ = \"1.2.4\"  # managed by cambi\n"
+  );
+}
+
+#[test]
+fn update_file_dispatches_nested_cargo_toml_and_commits() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "crates/core/Cargo.toml",
+    "[package]\nname=\"core\"\nversion=\"1.2.3\"\n",
+  );
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "--file", "crates/core/Cargo.toml", "--commit", "--tag"]);
+  cmd.assert().success().stdout("Updated version to 1.3.0.\n");
+
+  let cargo = fs::read_to_string(repo.path().join("crates/core/Cargo.toml")).expect("read");
+  assert!(cargo.contains("\"1.3.0\""));
+  assert_eq!(crate::common::git(repo.path(), &["status", "--short"]).trim(), "");
+  assert!(crate::common::git(repo.path(), &["tag", "--list"]).contains("v1.3.0"));
+}
+
+#[test]
+fn update_file_rejects_unknown_files() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "project.clj", "(defproject x \"1.2.3\")\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "--file", "project.clj"]);
+  let output = cmd.assert().failure().get_output().stderr.clone();
+  assert!(String::from_utf8_lossy(&output).contains("Unsupported version file project.clj"));
+}
+
+#[test]
+fn update_all_syncs_every_version_file_in_one_commit() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "Cargo.toml", "[package]\nname=\"x\"\nversion=\"1.2.3\"\n");
+  fs::write(
+    repo.path().join("package.json"),
+    "{\n  \"name\": \"x\",\n  \"version\": \"1.2.5\"\n}\n",
+  )
+  .expect("write");
+  commit_with_date(repo.path(), "chore: add package.json", "2026-02-21T00:00:00Z");
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: patch", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--verbose", "update", "--all", "--commit"]);
+  let output = cmd.assert().success().get_output().clone();
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  assert!(stdout.contains("Updated Cargo.toml to 1.2.6."));
+  assert!(stdout.contains("Updated package.json to 1.2.6."));
+  assert!(String::from_utf8_lossy(&output.stderr).contains("out of sync"));
+
+  assert!(
+    fs::read_to_string(repo.path().join("Cargo.toml"))
+      .expect("read")
+      .contains("\"1.2.6\"")
+  );
+  assert!(
+    fs::read_to_string(repo.path().join("package.json"))
+      .expect("read")
+      .contains("\"version\": \"1.2.6\"")
+  );
+
+  let files = crate::common::git(repo.path(), &["show", "--name-only", "--format=", "HEAD"]);
+  assert_eq!(files.trim(), "Cargo.toml\npackage.json");
+}
+
+#[test]
+fn update_detection_stops_at_the_first_version_file() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "Cargo.toml", "[package]\nname=\"x\"\nversion=\"1.2.3\"\n");
+  fs::write(repo.path().join("a.nimble"), "version = \"0.1.0\"\n").expect("write");
+  fs::write(repo.path().join("b.nimble"), "version = \"0.2.0\"\n").expect("write");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "--show", "patch"]);
+  cmd.assert().success().stdout("1.2.4\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "--all", "patch"]);
+  cmd.assert().success().stderr(predicate::str::contains(
+    "Multiple .nimble files found (a.nimble, b.nimble); pass --file to choose one. Skipping it.",
+  ));
+  assert!(
+    fs::read_to_string(repo.path().join("Cargo.toml"))
+      .expect("read")
+      .contains("\"1.2.4\"")
+  );
+}
+
+#[test]
+fn update_all_writes_nothing_when_a_file_cannot_be_bumped() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "Cargo.toml", "[package]\nname=\"x\"\nversion=\"1.2.3\"\n");
+  fs::write(repo.path().join("package.json"), "{\n  \"name\": \"x\"\n}\n").expect("write");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "--all", "patch"]);
+  cmd.assert().failure();
+
+  assert_eq!(
+    fs::read_to_string(repo.path().join("Cargo.toml")).expect("read"),
+    "[package]\nname=\"x\"\nversion=\"1.2.3\"\n"
+  );
+}
+
+#[test]
+fn update_json_prints_summary_and_moves_message_to_stderr() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "Cargo.toml", "[package]\nname=\"x\"\nversion=\"1.2.3\"\n");
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "--json", "--commit"]);
+  let output = cmd.assert().success().get_output().clone();
+
+  let summary: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json summary");
+  assert_eq!(
+    summary,
+    serde_json::json!({
+      "previous": "1.2.3",
+      "next": "1.3.0",
+      "bump": "minor",
+      "file": "Cargo.toml",
+      "committed": true,
+      "tagged": false,
+    })
+  );
+  assert_eq!(String::from_utf8_lossy(&output.stderr), "Updated version to 1.3.0.\n");
+}
@@ -76,6 +76,65 @@ fn updates_gemspec_version() {
   assert!(file.contains("spec.version = '1.3.0'"));
 }
 
+#[test]
+fn updates_podspec_version() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "X.podspec",
+    "Pod::Spec.new do |s|\n  s.name = 'X'\n  s.version = '1.2.3'\n  s.swift_version = '5.0'\nend\n",
+  );
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  run_update(repo.path());
+
+  let file = fs::read_to_string(repo.path().join("X.podspec")).expect("read");
+  assert!(file.contains("s.version = '1.3.0'"));
+  assert!(file.contains("s.swift_version = '5.0'"));
+}
+
+#[test]
+fn updates_setup_cfg_version() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "setup.cfg",
+    "[options]\nversion = 9.9.9\n\n[metadata]\nname = x\nversion = 1.2.3\n",
+  );
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  run_update(repo.path());
+
+  let file = fs::read_to_string(repo.path().join("setup.cfg")).expect("read");
+  assert_eq!(
+    file,
+    "[options]\nversion = 9.9.9\n\n[metadata]\nname = x\nversion = 1.3.0\n"
+  );
+}
+
+#[test]
+fn updates_setup_py_when_setup_cfg_has_no_version() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "setup.py",
+    "from setuptools import setup\n\nsetup(name=\"x\", version=\"1.2.3\")\n",
+  );
+  fs::write(repo.path().join("setup.cfg"), "[metadata]\nname = x\n").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  run_update(repo.path());
+
+  let file = fs::read_to_string(repo.path().join("setup.py")).expect("read");
+  assert!(file.contains("version=\"1.3.0\""));
+  assert_eq!(
+    fs::read_to_string(repo.path().join("setup.cfg")).expect("read"),
+    "[metadata]\nname = x\n"
+  );
+}
+
 #[test]
 fn updates_mix_exs_version() {
   let repo = init_repo();
@@ -106,6 +165,87 @@ fn updates_pubspec_yaml_version() {
   assert!(file.contains("version: 1.3.0"));
 }
 
+#[test]
+fn updates_pubspec_yaml_flutter_build_number() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "pubspec.yaml", "name: x\nversion: 1.2.3+4\n");
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: patch", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update"]);
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Updated version to 1.2.4+5"));
+
+  let file = fs::read_to_string(repo.path().join("pubspec.yaml")).expect("read");
+  assert!(file.contains("version: 1.2.4+5"));
+}
+
+#[test]
+fn update_rejects_a_non_numeric_flutter_build() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "pubspec.yaml", "name: x\nversion: 1.0.0+abc\n");
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: patch", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update"]);
+  cmd
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("pubspec.yaml").and(predicate::str::contains(
+      "Invalid Flutter build number 'abc' in 1.0.0+abc",
+    )));
+
+  let file = fs::read_to_string(repo.path().join("pubspec.yaml")).expect("read");
+  assert_eq!(file, "name: x\nversion: 1.0.0+abc\n");
+}
+
+#[test]
+fn updates_gradle_properties_version() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "gradle.properties",
+    "org.gradle.jvmargs=-Xmx2g\nversion=1.2.3\nkotlin.code.style=official\n",
+  );
+  fs::write(repo.path().join("build.gradle.kts"), "version = \"9.9.9\"\n").expect("write");
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  run_update(repo.path());
+
+  let file = fs::read_to_string(repo.path().join("gradle.properties")).expect("read");
+  assert_eq!(
+    file,
+    "org.gradle.jvmargs=-Xmx2g\nversion=1.3.0\nkotlin.code.style=official\n"
+  );
+}
+
+#[test]
+fn updates_gradle_build_versions() {
+  for (file_name, content, expected) in [
+    (
+      "build.gradle.kts",
+      "plugins {}\nversion = \"1.2.3\"\n",
+      "version = \"1.3.0\"",
+    ),
+    ("build.gradle", "plugins {}\nversion = '1.2.3'\n", "version = '1.3.0'"),
+  ] {
+    let repo = init_repo();
+    seed_single_file_repo(&repo, file_name, content);
+    fs::write(repo.path().join("a.txt"), "x").expect("write");
+    commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+    run_update(repo.path());
+
+    let file = fs::read_to_string(repo.path().join(file_name)).expect("read");
+    assert!(file.contains(expected), "{file_name}: {file}");
+  }
+}
+
 #[test]
 fn updates_package_swift_version() {
   let repo = init_repo();
@@ -144,3 +284,46 @@ fn updates_version_lowercase_file() {
   let file = fs::read_to_string(repo.path().join("version")).expect("read");
   assert_eq!(file, "1.3.0\n");
 }
+
+#[test]
+fn updates_nimble_version() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "pkg.nimble",
+    "# Package\n\nversion       = \"1.2.3\"\nauthor        = \"x\"\n",
+  );
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  run_update(repo.path());
+
+  let file = fs::read_to_string(repo.path().join("pkg.nimble")).expect("read");
+  assert_eq!(file, "# Package\n\nversion       = \"1.3.0\"\nauthor        = \"x\"\n");
+}
+
+#[test]
+fn multiple_gemspec_files_require_explicit_file() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.gemspec", "  spec.version = \"1.2.3\"\n");
+  fs::write(repo.path().join("b.gemspec"), "  spec.version = \"1.2.3\"\n").expect("write");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update"]);
+  cmd.assert().failure().stderr(predicate::str::contains(
+    "Multiple .gemspec files found (a.gemspec, b.gemspec); pass --file",
+  ));
+}
+
+#[test]
+fn multiple_nimble_files_require_explicit_file() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "a.nimble", "version = \"1.2.3\"\n");
+  fs::write(repo.path().join("b.nimble"), "version = \"1.2.3\"\n").expect("write");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update"]);
+  cmd.assert().failure().stderr(predicate::str::contains(
+    "Multiple .nimble files found (a.nimble, b.nimble); pass --file",
+  ));
+}
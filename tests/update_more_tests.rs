@@ -127,6 +127,49 @@ fn package_swift_version_argument_form_is_supported() {
   assert!(swift.contains("version: \"1.3.0\""));
 }
 
+#[test]
+fn build_sbt_plain_and_this_build_versions_are_bumped() {
+  for (original, bumped) in [
+    (
+      "name := \"x\"\nscalaVersion := \"3.3.1\"\nversion := \"1.2.3\"\n",
+      "name := \"x\"\nscalaVersion := \"3.3.1\"\nversion := \"1.3.0\"\n",
+    ),
+    (
+      "ThisBuild / scalaVersion := \"3.3.1\"\nThisBuild / version := \"1.2.3\" // release\n",
+      "ThisBuild / scalaVersion := \"3.3.1\"\nThisBuild / version := \"1.3.0\" // release\n",
+    ),
+  ] {
+    let repo = init_repo();
+    seed_single_file_repo(&repo, "build.sbt", original);
+    fs::write(repo.path().join("a.txt"), "x").expect("write");
+    commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+    cmd.current_dir(repo.path()).args(["update"]);
+    cmd.assert().success().stdout("Updated version to 1.3.0.\n");
+
+    let sbt = fs::read_to_string(repo.path().join("build.sbt")).expect("read");
+    assert_eq!(sbt, bumped);
+  }
+}
+
+#[test]
+fn makefile_version_variable_is_bumped_with_explicit_file() {
+  let original =
+    "APP_VERSION := 9.9.9\nVERSION := 1.2.3\nPREFIX ?= /usr/local\n\nbuild:\n\tcc -DVERSION=$(VERSION) main.c\n";
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "Makefile", original);
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "--file", "Makefile"]);
+  cmd.assert().success().stdout("Updated version to 1.3.0.\n");
+
+  let makefile = fs::read_to_string(repo.path().join("Makefile")).expect("read");
+  assert_eq!(makefile, original.replace("VERSION := 1.2.3", "VERSION := 1.3.0"));
+}
+
 #[test]
 fn version_prints_latest_tag_version() {
   let repo = init_repo();
@@ -146,6 +189,17 @@ fn version_with_from_tag_prints_normalized_input() {
   cmd.assert().success().stdout("1.2.3\n");
 }
 
+#[test]
+fn version_next_previews_each_requested_bump() {
+  let repo = init_repo();
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["version", "--from-tag", "v1.2.3", "--next", "patch,minor,major"]);
+  cmd.assert().success().stdout("patch=1.2.4 minor=1.3.0 major=2.0.0\n");
+}
+
 #[test]
 fn update_with_commit_uses_default_message_even_if_repo_is_dirty() {
   let repo = init_repo();
@@ -200,6 +254,61 @@ fn update_with_commit_stages_all_tracked_changes_on_dirty_branch() {
   assert_eq!(status.trim(), "");
 }
 
+#[test]
+fn update_with_commit_scope_version_file_leaves_other_changes_unstaged() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "Cargo.toml",
+    "[package]\nname=\"x\"\nversion=\"1.2.3\"\nrepository=\"https://github.com/octo/r\"\n",
+  );
+
+  fs::write(repo.path().join("tracked.txt"), "one\n").expect("write tracked file");
+  commit_with_date(repo.path(), "fix: add tracked file", "2026-02-22T00:00:00Z");
+
+  fs::write(repo.path().join("cambi.yml"), "commit_scope: version-file\n").expect("write config");
+  // A different size keeps the change visible when the index is written within
+  // the same second.
+  fs::write(repo.path().join("tracked.txt"), "one and two\n").expect("modify tracked file");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "--commit"]);
+  cmd.assert().success().stdout("Updated version to 1.2.4.\n");
+
+  let committed = git(repo.path(), &["show", "--pretty=", "--name-only", "HEAD"]);
+  assert_eq!(committed.trim(), "Cargo.toml");
+
+  let status = git(repo.path(), &["status", "--short"]);
+  assert!(status.contains(" M tracked.txt"));
+}
+
+#[test]
+fn update_with_commit_all_overrides_version_file_scope() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "Cargo.toml",
+    "[package]\nname=\"x\"\nversion=\"1.2.3\"\nrepository=\"https://github.com/octo/r\"\n",
+  );
+
+  fs::write(repo.path().join("tracked.txt"), "one\n").expect("write tracked file");
+  commit_with_date(repo.path(), "fix: add tracked file", "2026-02-22T00:00:00Z");
+
+  fs::write(repo.path().join("cambi.yml"), "commit_scope: version-file\n").expect("write config");
+  fs::write(repo.path().join("tracked.txt"), "two\n").expect("modify tracked file");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "--commit-all", "--tag"]);
+  cmd.assert().success().stdout("Updated version to 1.2.4.\n");
+
+  let committed = git(repo.path(), &["show", "--pretty=", "--name-only", "HEAD"]);
+  assert!(committed.lines().any(|line| line == "Cargo.toml"));
+  assert!(committed.lines().any(|line| line == "tracked.txt"));
+
+  let tags = git(repo.path(), &["tag", "--list", "v1.2.4"]);
+  assert_eq!(tags.trim(), "v1.2.4");
+}
+
 #[test]
 fn update_with_commit_uses_custom_message() {
   let repo = init_repo();
@@ -242,6 +351,119 @@ fn update_with_commit_and_tag_creates_prefixed_tag_by_default() {
   assert!(tags.lines().any(|line| line == "v1.2.4"));
 }
 
+#[test]
+fn update_with_tag_format_creates_bare_tag() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "Cargo.toml",
+    "[package]\nname=\"x\"\nversion=\"1.2.3\"\nrepository=\"https://github.com/octo/r\"\n",
+  );
+
+  fs::write(
+    repo.path().join("cambi.yml"),
+    "tag_pattern: '^v?\\d+\\.\\d+\\.\\d+$'\ntag_format: \"{version}\"\n",
+  )
+  .expect("write config");
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: patch", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "--commit", "--tag"]);
+  cmd.assert().success().stdout("Updated version to 1.2.4.\n");
+
+  let tags = git(repo.path(), &["tag", "--list"]);
+  assert!(tags.lines().any(|line| line == "1.2.4"));
+  assert!(!tags.lines().any(|line| line == "v1.2.4"));
+}
+
+#[test]
+fn update_with_annotated_tag_uses_release_notes_in_message() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "Cargo.toml",
+    "[package]\nname=\"x\"\nversion=\"1.2.3\"\nrepository=\"https://github.com/octo/r\"\n",
+  );
+
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: patch", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .env("CAMBI_TAG_MESSAGE_TEMPLATE", "Version $VERSION\n\n$NOTES")
+    .args(["update", "--commit", "--tag", "--annotate"]);
+  cmd.assert().success().stdout("Updated version to 1.2.4.\n");
+
+  let kind = git(repo.path(), &["cat-file", "-t", "v1.2.4"]);
+  assert_eq!(kind.trim(), "tag");
+
+  let message = git(repo.path(), &["tag", "-l", "--format=%(contents)", "v1.2.4"]);
+  assert!(message.starts_with("Version 1.2.4\n\n"));
+  assert!(message.contains("- fix: patch"));
+}
+
+#[test]
+fn update_with_annotated_tag_defaults_message_and_uses_repo_identity() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "Cargo.toml",
+    "[package]\nname=\"x\"\nversion=\"1.2.3\"\nrepository=\"https://github.com/octo/r\"\n",
+  );
+
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: patch", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "--commit", "--tag", "--annotate"]);
+  cmd.assert().success().stdout("Updated version to 1.2.4.\n");
+
+  let kind = git(repo.path(), &["cat-file", "-t", "v1.2.4"]);
+  assert_eq!(kind.trim(), "tag");
+
+  let tag = git(
+    repo.path(),
+    &[
+      "tag",
+      "-l",
+      "--format=%(taggername) %(taggeremail)|%(contents)",
+      "v1.2.4",
+    ],
+  );
+  assert_eq!(tag.trim(), "Tests <tests@example.com>|Release 1.2.4");
+}
+
+#[test]
+fn update_with_tag_message_overrides_annotated_tag_message() {
+  let repo = init_repo();
+  seed_single_file_repo(
+    &repo,
+    "Cargo.toml",
+    "[package]\nname=\"x\"\nversion=\"1.2.3\"\nrepository=\"https://github.com/octo/r\"\n",
+  );
+
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: patch", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args([
+    "update",
+    "--commit",
+    "--tag",
+    "--annotate",
+    "--tag-message",
+    "Shipping $VERSION\n\n$NOTES",
+  ]);
+  cmd.assert().success().stdout("Updated version to 1.2.4.\n");
+
+  let message = git(repo.path(), &["tag", "-l", "--format=%(contents)", "v1.2.4"]);
+  assert_eq!(message.trim(), "Shipping 1.2.4\n\n- fix: patch");
+}
+
 #[test]
 fn update_with_commit_and_tag_uses_plain_version_when_pattern_matches_plain_semver() {
   let repo = init_repo();
@@ -301,10 +523,9 @@ fn update_dry_run_does_not_write_files() {
 
   let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
   cmd.current_dir(repo.path()).args(["update", "--dry-run"]);
-  cmd
-    .assert()
-    .success()
-    .stdout(predicate::str::contains("dry-run: would update Cargo.toml to 1.2.4"));
+  cmd.assert().success().stdout(predicate::str::contains(
+    "dry-run: would update Cargo.toml from 1.2.3 to 1.2.4",
+  ));
 
   let cargo = fs::read_to_string(repo.path().join("Cargo.toml")).expect("read");
   assert_eq!(cargo, original);
@@ -328,7 +549,9 @@ fn update_dry_run_propagates_to_changelog_and_logs_commit_and_tag() {
     .args(["update", "--dry-run", "--changelog", "--commit", "--tag"]);
   cmd.assert().success().stdout(
     predicate::str::contains("/ 1.2.4")
-      .and(predicate::str::contains("dry-run: would update Cargo.toml to 1.2.4"))
+      .and(predicate::str::contains(
+        "dry-run: would update Cargo.toml from 1.2.3 to 1.2.4",
+      ))
       .and(predicate::str::contains(
         "dry-run: would commit Cargo.toml, CHANGELOG.md with message 'chore: Updated version.'",
       ))
@@ -379,3 +602,277 @@ fn update_show_exits_before_changelog_commit_and_tag() {
   let tags = git(repo.path(), &["tag", "--list"]);
   assert!(!tags.lines().any(|line| line == "v1.2.4"));
 }
+
+#[test]
+fn update_keeps_build_metadata_unless_build_is_passed() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "package.json", "{\n  \"version\": \"1.2.3+build.1\"\n}\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args(["update", "patch"]);
+  cmd.assert().success().stdout("Updated version to 1.2.4+build.1.\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "patch", "--build", "build.2"]);
+  cmd.assert().success().stdout("Updated version to 1.2.5+build.2.\n");
+
+  let json = fs::read_to_string(repo.path().join("package.json")).expect("read");
+  assert_eq!(json, "{\n  \"version\": \"1.2.5+build.2\"\n}\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "patch", "--build", "bad..build"]);
+  cmd
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Invalid build metadata 'bad..build'"));
+}
+
+#[test]
+fn prefix_matches_and_creates_tags_with_regex_special_characters() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "package.json", "{\n  \"version\": \"1.2.3\"\n}\n");
+  git(repo.path(), &["tag", "app+rel.1.2.3"]);
+
+  fs::write(repo.path().join("src.rs"), "x").expect("write");
+  commit_with_date(repo.path(), "fix: patch", "2026-02-22T00:00:00Z");
+  // Matches the prefix only if its characters were treated as a regex.
+  git(repo.path(), &["tag", "apppprelx1.2.9"]);
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--prefix", "app+rel.", "semver", "--print-range"]);
+  cmd
+    .assert()
+    .success()
+    .stdout("patch\n")
+    .stderr(predicate::str::contains("app+rel.1.2.3").and(predicate::str::contains("apppprelx").not()));
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--prefix", "app+rel.", "update", "--commit", "--tag"]);
+  cmd.assert().success().stdout("Updated version to 1.2.4.\n");
+
+  let tags = git(repo.path(), &["tag", "--list"]);
+  assert!(tags.lines().any(|line| line == "app+rel.1.2.4"));
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--prefix", "v", "--tag-pattern", "^v.*$", "version"]);
+  cmd.assert().failure();
+}
+
+#[test]
+fn initial_version_sets_the_first_release_when_no_tag_exists() {
+  let repo = init_repo();
+  fs::write(
+    repo.path().join("Cargo.toml"),
+    "[package]\nname=\"x\"\nversion=\"0.1.0\"\nrepository=\"https://github.com/octo/r\"\n",
+  )
+  .expect("write manifest");
+  commit_with_date(repo.path(), "feat: first feature", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--initial-version", "1.0.0", "changelog"]);
+  cmd.assert().success();
+
+  let changelog = fs::read_to_string(repo.path().join("CHANGELOG.md")).expect("read changelog");
+  assert_eq!(changelog, "### 2026-02-22 / 1.0.0\n\n- feat: first feature\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--initial-version", "1.0.0", "update", "--commit", "--tag"]);
+  cmd.assert().success().stdout("Updated version to 1.0.0.\n");
+
+  // Once tagged, later releases bump as usual.
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: second feature", "2026-02-23T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--initial-version", "1.0.0", "update"]);
+  cmd.assert().success().stdout("Updated version to 1.1.0.\n");
+}
+
+#[test]
+fn initial_version_never_downgrades_a_file_already_ahead() {
+  let repo = init_repo();
+  fs::write(
+    repo.path().join("package.json"),
+    "{\n  \"name\": \"x\",\n  \"version\": \"2.3.0\"\n}\n",
+  )
+  .expect("write manifest");
+  commit_with_date(repo.path(), "feat: first feature", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["--initial-version", "1.0.0", "update"]);
+  cmd.assert().success().stdout("Updated version to 2.4.0.\n");
+
+  assert!(
+    fs::read_to_string(repo.path().join("package.json"))
+      .expect("read manifest")
+      .contains("\"version\": \"2.4.0\"")
+  );
+}
+
+#[test]
+fn update_sign_fails_without_a_signing_key() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "VERSION", "1.2.3\n");
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add a", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "--commit", "--tag", "--sign"]);
+  cmd.assert().failure().stderr(predicate::str::contains(
+    "Cannot sign without a signing key. Set user.signingkey in the git config.",
+  ));
+
+  assert_eq!(
+    fs::read_to_string(repo.path().join("VERSION")).expect("read"),
+    "1.2.3\n"
+  );
+}
+
+#[cfg(unix)]
+#[test]
+fn update_sign_signs_the_commit_and_the_tag() {
+  use std::os::unix::fs::PermissionsExt;
+
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "VERSION", "1.2.3\n");
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add a", "2026-02-22T00:00:00Z");
+
+  // Stands in for gpg: checks the key and prints a fixed signature.
+  let gpg = tempfile::TempDir::new().expect("temp dir");
+  let program = gpg.path().join("fake-gpg");
+  fs::write(
+    &program,
+    "#!/bin/sh\n[ \"$3\" = TESTKEY ] || exit 1\ncat > /dev/null\nprintf -- '-----BEGIN PGP \
+     SIGNATURE-----\\n\\nfake\\n-----END PGP SIGNATURE-----\\n'\n",
+  )
+  .expect("write fake gpg");
+  fs::set_permissions(&program, fs::Permissions::from_mode(0o755)).expect("chmod");
+  git(repo.path(), &["config", "user.signingkey", "TESTKEY"]);
+  git(
+    repo.path(),
+    &["config", "gpg.program", program.to_str().expect("utf-8 path")],
+  );
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "--commit", "--tag", "--sign"]);
+  cmd.assert().success().stdout("Updated version to 1.3.0.\n");
+
+  let commit = git(repo.path(), &["cat-file", "commit", "HEAD"]);
+  assert!(commit.contains("gpgsig -----BEGIN PGP SIGNATURE-----"));
+  assert!(commit.ends_with("\n\nchore: Updated version."));
+  assert_eq!(git(repo.path(), &["status", "--porcelain", "--untracked-files=no"]), "");
+
+  let tag = git(repo.path(), &["cat-file", "tag", "v1.3.0"]);
+  assert!(tag.contains("type commit\ntag v1.3.0\ntagger Tests <tests@example.com>"));
+  assert!(tag.ends_with("-----END PGP SIGNATURE-----\n"));
+  assert_eq!(
+    git(repo.path(), &["rev-parse", "v1.3.0^{commit}"]),
+    git(repo.path(), &["rev-parse", "HEAD"])
+  );
+}
+
+/// Publishes `repo` as a bare repository, added as its `upstream` remote.
+fn add_bare_remote(repo: &tempfile::TempDir) -> tempfile::TempDir {
+  let remote = tempfile::TempDir::new().expect("temp dir");
+  let bare = remote.path().to_str().expect("utf-8 path");
+  git(repo.path(), &["clone", "-q", "--bare", ".", bare]);
+  git(repo.path(), &["remote", "add", "upstream", bare]);
+  remote
+}
+
+#[test]
+fn update_push_sends_the_commit_and_the_tag_to_the_remote() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "VERSION", "1.2.3\n");
+  let remote = add_bare_remote(&repo);
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add a", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd.current_dir(repo.path()).args([
+    "update",
+    "--commit",
+    "--tag",
+    "--push",
+    "--remote",
+    "upstream",
+    "--dry-run",
+  ]);
+  cmd
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("dry-run: would push to upstream\n"));
+  assert_eq!(git(remote.path(), &["tag", "--list"]), "v0.1.0\n");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "--commit", "--tag", "--push", "--remote", "upstream"]);
+  cmd.assert().success().stdout("Updated version to 1.3.0.\n");
+
+  assert_eq!(
+    git(remote.path(), &["rev-parse", "HEAD"]),
+    git(repo.path(), &["rev-parse", "HEAD"])
+  );
+  assert_eq!(
+    git(remote.path(), &["rev-parse", "v1.3.0"]),
+    git(repo.path(), &["rev-parse", "v1.3.0"])
+  );
+}
+
+#[test]
+fn update_push_reports_rejected_pushes() {
+  let repo = init_repo();
+  seed_single_file_repo(&repo, "VERSION", "1.2.3\n");
+  let remote = add_bare_remote(&repo);
+
+  // Someone else pushed in the meantime.
+  let other = tempfile::TempDir::new().expect("temp dir");
+  git(
+    other.path(),
+    &["clone", "-q", remote.path().to_str().expect("utf-8 path"), "."],
+  );
+  git(other.path(), &["config", "user.email", "other@example.com"]);
+  git(other.path(), &["config", "user.name", "Other"]);
+  fs::write(other.path().join("b.txt"), "y").expect("write");
+  commit_with_date(other.path(), "fix: add b", "2026-02-21T00:00:00Z");
+  git(other.path(), &["push", "-q", "origin", "HEAD"]);
+
+  fs::write(repo.path().join("a.txt"), "x").expect("write");
+  commit_with_date(repo.path(), "feat: add a", "2026-02-22T00:00:00Z");
+
+  let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cambi"));
+  cmd
+    .current_dir(repo.path())
+    .args(["update", "--commit", "--push", "--remote", "upstream"]);
+  cmd
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("upstream rejected the push of refs/heads/"))
+    .stderr(predicate::str::contains(
+      "(non-fast-forward). Pull the remote changes and push again.",
+    ));
+}
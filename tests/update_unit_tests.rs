@@ -1,11 +1,13 @@
 use std::{env, fs, path::Path};
 
 use cambi::{
+  config::FlutterBuild,
   conventional::BumpLevel,
   version::{
     UpdateTarget, find_gemspec_path, latest_tag_version, normalize_semver, update_cargo_toml_version,
-    update_gemspec_version, update_mix_exs_version, update_package_json_version, update_package_swift_version,
-    update_plain_version_file, update_pubspec_yaml_version, update_pyproject_toml_version,
+    update_gemspec_version, update_gradle_build_version, update_gradle_properties_version, update_mix_exs_version,
+    update_nested_yaml_version, update_package_json_version, update_package_swift_version, update_plain_version_file,
+    update_pubspec_yaml_version, update_pyproject_toml_version,
   },
 };
 use serial_test::serial;
@@ -126,7 +128,7 @@ fn update_pubspec_yaml_non_mapping_errors() {
   let temp = TempDir::new().expect("tmp");
   let file = temp.path().join("pubspec.yaml");
   fs::write(&file, "- 1\n- 2\n").expect("write");
-  assert!(update_pubspec_yaml_version(&file, &UpdateTarget::Bump(BumpLevel::Patch)).is_err());
+  assert!(update_pubspec_yaml_version(&file, &UpdateTarget::Bump(BumpLevel::Patch), FlutterBuild::Increment).is_err());
 }
 
 #[test]
@@ -142,7 +144,7 @@ fn latest_tag_version_returns_zero_when_no_semver_tags() {
     .output()
     .expect("git init");
 
-  let v = latest_tag_version(r"^v\d+\.\d+\.\d+$").expect("version");
+  let v = latest_tag_version(r"^v\d+\.\d+\.\d+$", false).expect("version");
   let _ = env::set_current_dir(&old);
   if env::current_dir().is_err() {
     env::set_current_dir(env!("CARGO_MANIFEST_DIR")).expect("restore manifest dir");
@@ -190,7 +192,7 @@ fn latest_tag_version_skips_non_semver_tag_names_when_pattern_matches_all() {
     .output()
     .expect("git tag");
 
-  let v = latest_tag_version(r".+").expect("version");
+  let v = latest_tag_version(r".+", false).expect("version");
   let _ = env::set_current_dir(&old);
   if env::current_dir().is_err() {
     env::set_current_dir(env!("CARGO_MANIFEST_DIR")).expect("restore manifest dir");
@@ -207,3 +209,128 @@ fn update_package_swift_var_form_supported() {
   assert_eq!(new_v, "1.2.4");
   assert!(Path::new(&file).exists());
 }
+
+#[test]
+fn update_nested_yaml_version_follows_dotted_path() {
+  let temp = TempDir::new().expect("tmp");
+  let file = temp.path().join("app.yaml");
+  fs::write(&file, "app:\n  version: 1.2.3\nversion: 9.9.9\n").expect("write");
+
+  let new_v = update_nested_yaml_version(&file, &UpdateTarget::Bump(BumpLevel::Minor), "app.version").expect("update");
+  let updated = fs::read_to_string(&file).expect("read");
+
+  assert_eq!(new_v, "1.3.0");
+  assert!(updated.contains("app:\n  version: 1.3.0\n"));
+  assert!(updated.contains("version: 9.9.9"));
+
+  let missing = update_nested_yaml_version(&file, &UpdateTarget::Bump(BumpLevel::Minor), "app.meta.version");
+  assert!(missing.is_err());
+}
+
+#[test]
+fn update_pubspec_yaml_flutter_build_modes() {
+  let temp = TempDir::new().expect("tempdir");
+  let file = temp.path().join("pubspec.yaml");
+  let cases = [
+    (FlutterBuild::Increment, "1.2.4+5"),
+    (FlutterBuild::Reset, "1.2.4+1"),
+    (FlutterBuild::Keep, "1.2.4+4"),
+  ];
+
+  for (mode, expected) in cases {
+    fs::write(&file, "name: x\nversion: 1.2.3+4\n").expect("write");
+    let updated = update_pubspec_yaml_version(&file, &UpdateTarget::Bump(BumpLevel::Patch), mode).expect("update");
+    assert_eq!(updated, expected);
+  }
+}
+
+#[test]
+fn update_gradle_without_version_assignment_errors() {
+  let temp = TempDir::new().expect("tempdir");
+  let properties = temp.path().join("gradle.properties");
+  fs::write(&properties, "org.gradle.jvmargs=-Xmx2g\n").expect("write");
+  let build = temp.path().join("build.gradle");
+  fs::write(&build, "plugins {}\n").expect("write");
+
+  let target = UpdateTarget::Bump(BumpLevel::Patch);
+  let error = update_gradle_properties_version(&properties, &target).expect_err("missing version");
+  assert!(error.to_string().contains("No version assignment found"));
+  let error = update_gradle_build_version(&build, &target).expect_err("missing version");
+  assert!(error.to_string().contains("No version assignment found"));
+}
+
+#[test]
+fn prerelease_targets_start_advance_and_finalize_prereleases() {
+  let temp = TempDir::new().expect("tmp");
+  let file = temp.path().join("package.json");
+  let update = |current: &str, target: UpdateTarget| {
+    fs::write(&file, format!("{{\"version\":\"{current}\"}}\n")).expect("write");
+    update_package_json_version(&file, &target)
+  };
+  let rc = |bump: BumpLevel| UpdateTarget::Prerelease(bump, "rc".to_string());
+
+  assert_eq!(update("1.2.3", rc(BumpLevel::Minor)).expect("start"), "1.3.0-rc.1");
+  assert_eq!(
+    update("1.3.0-rc.1", rc(BumpLevel::Minor)).expect("advance"),
+    "1.3.0-rc.2"
+  );
+  assert_eq!(
+    update("1.3.0-beta.2", rc(BumpLevel::Patch)).expect("switch"),
+    "1.3.0-rc.1"
+  );
+  assert_eq!(update("1.3.0-rc", rc(BumpLevel::Patch)).expect("number"), "1.3.0-rc.1");
+  assert_eq!(
+    update("1.3.0-rc.1", rc(BumpLevel::Major)).expect("restart"),
+    "2.0.0-rc.1"
+  );
+  assert_eq!(
+    update("1.3.1-rc.1", rc(BumpLevel::Minor)).expect("restart"),
+    "1.4.0-rc.1"
+  );
+  assert_eq!(
+    update("2.0.0-rc.1", rc(BumpLevel::Major)).expect("advance"),
+    "2.0.0-rc.2"
+  );
+  assert!(
+    update(
+      "1.3.0-rc.2",
+      UpdateTarget::Prerelease(BumpLevel::Patch, "beta".to_string())
+    )
+    .is_err()
+  );
+
+  assert_eq!(update("1.3.0-rc.2", UpdateTarget::Finalize).expect("finalize"), "1.3.0");
+  assert!(update("1.3.0", UpdateTarget::Finalize).is_err());
+  assert_eq!(
+    update("1.3.0-rc.2", UpdateTarget::Bump(BumpLevel::Patch)).expect("bump"),
+    "1.3.1"
+  );
+}
+
+#[test]
+fn exact_targets_must_be_greater_than_the_current_version() {
+  let temp = TempDir::new().expect("tmp");
+  let file = temp.path().join("package.json");
+  let update = |target: UpdateTarget| {
+    fs::write(&file, "{\"version\":\"2.3.0\"}\n").expect("write");
+    update_package_json_version(&file, &target)
+  };
+  let exact = |raw: &str| UpdateTarget::Exact(normalize_semver(raw).expect("parse"));
+
+  let error = update(exact("2.3.0")).expect_err("equal");
+  assert!(
+    error
+      .to_string()
+      .contains("Refusing to set version 2.3.0, which is not greater than current 2.3.0")
+  );
+  let error = update(exact("1.0.0")).expect_err("lower");
+  assert!(
+    error
+      .to_string()
+      .contains("Refusing to set version 1.0.0, which is not greater than current 2.3.0")
+  );
+  assert_eq!(update(exact("2.4.0")).expect("higher"), "2.4.0");
+
+  let downgrade = UpdateTarget::Downgrade(normalize_semver("1.0.0").expect("parse"));
+  assert_eq!(update(downgrade).expect("downgrade"), "1.0.0");
+}